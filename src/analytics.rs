@@ -0,0 +1,391 @@
+//! Occupancy and capacity analytics for [`Session`]s and [`Screen`]s
+//!
+//! The primary type is [`OccupancyReport`], which computes the occupancy of
+//! every session in a [`SessionList`] and aggregates it by film, screen, day,
+//! week and attribute. All types in this module are serializable so reports
+//! can be handed straight to spreadsheets, dashboards or templates.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+
+use crate::{
+    attr::{Attribute, AttributeId},
+    film::FilmId,
+    screen::{Screen, ScreenId},
+    session::{Session, SessionId, SessionList},
+};
+
+/// Internal helper to compute a percentage, treating an empty total as 0%
+fn percent(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        f64::from(part) / f64::from(total) * 100.0
+    }
+}
+
+/// The seat counts and occupancy of a single [`Session`]
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct SessionOccupancy {
+    /// The ID of the session
+    pub session_id: SessionId,
+    /// The ID of the film shown in the session
+    pub film_id: FilmId,
+    /// The title of the film shown in the session
+    pub title: String,
+    /// The ID of the screen the session is shown on
+    pub screen_id: ScreenId,
+    /// The time the session starts
+    pub pre_show_start_time: NaiveDateTime,
+    /// The number of seats sold
+    pub seats_sold: u32,
+    /// The number of seats held
+    pub seats_held: u32,
+    /// The number of house seats
+    pub seats_house: u32,
+    /// The number of seats still available
+    pub seats_available: u32,
+    /// The capacity of the session
+    ///
+    /// This is the [`Screen::total_seats`] of the session's screen if it is
+    /// known, otherwise the sum of the session's seat counts.
+    pub capacity: u32,
+    /// The percentage of the capacity that has been sold
+    pub occupancy_percent: f64,
+    /// Whether the session is sold out
+    pub sold_out: bool,
+    /// The difference between the sum of the session's seat counts and the
+    /// screen capacity, if the screen is known and they do not add up
+    pub capacity_mismatch: Option<i64>,
+}
+impl SessionOccupancy {
+    /// Compute the occupancy of a [`Session`], optionally checking its seat
+    /// counts against the capacity of its [`Screen`]
+    #[must_use]
+    pub fn new(session: &Session, screen: Option<&Screen>) -> Self {
+        let counted = session
+            .seats_sold
+            .saturating_add(session.seats_held)
+            .saturating_add(session.seats_house)
+            .saturating_add(session.seats_available);
+        let capacity = screen.map_or(counted, |screen| screen.total_seats);
+        let capacity_mismatch = screen
+            .map(|screen| i64::from(counted) - i64::from(screen.total_seats))
+            .filter(|difference| *difference != 0);
+
+        Self {
+            session_id: session.id,
+            film_id: session.film_id.clone(),
+            title: session.title.clone(),
+            screen_id: session.screen_id,
+            pre_show_start_time: session.pre_show_start_time,
+            seats_sold: session.seats_sold,
+            seats_held: session.seats_held,
+            seats_house: session.seats_house,
+            seats_available: session.seats_available,
+            capacity,
+            occupancy_percent: percent(session.seats_sold, capacity),
+            sold_out: session.tickets_sold_out,
+            capacity_mismatch,
+        }
+    }
+}
+
+/// Aggregated seat counts and occupancy over a group of sessions
+#[derive(Serialize, Debug, PartialEq, Clone, Default)]
+pub struct OccupancySummary {
+    /// The number of sessions in the group
+    pub sessions: u32,
+    /// The number of sold out sessions in the group
+    pub sold_out_sessions: u32,
+    /// The total capacity across all sessions in the group
+    pub capacity: u32,
+    /// The total number of seats sold
+    pub seats_sold: u32,
+    /// The total number of seats held
+    pub seats_held: u32,
+    /// The total number of house seats
+    pub seats_house: u32,
+    /// The total number of seats still available
+    pub seats_available: u32,
+    /// The percentage of the total capacity that has been sold
+    pub occupancy_percent: f64,
+}
+impl OccupancySummary {
+    /// Add a single session's occupancy to this summary
+    pub fn add(&mut self, occupancy: &SessionOccupancy) {
+        self.sessions = self.sessions.saturating_add(1);
+        if occupancy.sold_out {
+            self.sold_out_sessions = self.sold_out_sessions.saturating_add(1);
+        }
+        self.capacity = self.capacity.saturating_add(occupancy.capacity);
+        self.seats_sold = self.seats_sold.saturating_add(occupancy.seats_sold);
+        self.seats_held = self.seats_held.saturating_add(occupancy.seats_held);
+        self.seats_house = self.seats_house.saturating_add(occupancy.seats_house);
+        self.seats_available = self
+            .seats_available
+            .saturating_add(occupancy.seats_available);
+        self.occupancy_percent = percent(self.seats_sold, self.capacity);
+    }
+}
+impl<'a> FromIterator<&'a SessionOccupancy> for OccupancySummary {
+    fn from_iter<I: IntoIterator<Item = &'a SessionOccupancy>>(iter: I) -> Self {
+        let mut summary = Self::default();
+        for occupancy in iter {
+            summary.add(occupancy);
+        }
+        summary
+    }
+}
+
+/// An [`OccupancySummary`] for a group of sessions sharing a key
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct OccupancyGroup<K> {
+    /// The key shared by every session in this group
+    pub key: K,
+    /// A human readable label for the key
+    pub label: String,
+    /// The aggregated occupancy of the group
+    pub summary: OccupancySummary,
+}
+
+/// Internal helper to aggregate sessions into [`OccupancyGroup`]s
+///
+/// `keys` returns every `(key, label)` pair a session belongs to, so a session
+/// may be counted in several groups, but at most once in each even if a key is
+/// repeated. Groups are returned sorted by label.
+fn group_by<K, F>(sessions: &[SessionOccupancy], keys: F) -> Vec<OccupancyGroup<K>>
+where
+    K: Eq + Hash + Clone,
+    F: Fn(&SessionOccupancy) -> Vec<(K, String)>,
+{
+    let mut groups: HashMap<K, OccupancyGroup<K>> = HashMap::new();
+    for occupancy in sessions {
+        let mut seen = HashSet::new();
+        for (key, label) in keys(occupancy) {
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            groups
+                .entry(key.clone())
+                .or_insert_with(|| OccupancyGroup {
+                    key,
+                    label,
+                    summary: OccupancySummary::default(),
+                })
+                .summary
+                .add(occupancy);
+        }
+    }
+    let mut groups: Vec<OccupancyGroup<K>> = groups.into_values().collect();
+    groups.sort_by(|a, b| a.label.cmp(&b.label));
+    groups
+}
+
+/// A full occupancy report over a [`SessionList`]
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct OccupancyReport {
+    /// The aggregated occupancy of every session in the report
+    pub total: OccupancySummary,
+    /// The occupancy of each individual session, in the original order
+    pub sessions: Vec<SessionOccupancy>,
+    /// The occupancy grouped by film, labelled by film title
+    pub by_film: Vec<OccupancyGroup<FilmId>>,
+    /// The occupancy grouped by screen, labelled by screen name
+    pub by_screen: Vec<OccupancyGroup<ScreenId>>,
+    /// The occupancy grouped by the date of [`Session::pre_show_start_time`]
+    pub by_day: Vec<OccupancyGroup<NaiveDate>>,
+    /// The occupancy grouped by ISO week, keyed by the Monday starting it
+    pub by_week: Vec<OccupancyGroup<NaiveDate>>,
+    /// The occupancy grouped by attribute, labelled by attribute description;
+    /// a session listing an attribute twice is counted once
+    pub by_attribute: Vec<OccupancyGroup<AttributeId>>,
+    /// The IDs of sessions whose seat counts do not add up to the capacity
+    /// of their screen
+    pub inconsistent_sessions: Vec<SessionId>,
+}
+impl OccupancyReport {
+    /// Build an [`OccupancyReport`] for the given sessions
+    ///
+    /// `screens` provides the capacity of each session; sessions on screens
+    /// not in the list fall back to the sum of their seat counts and are never
+    /// reported as inconsistent. `attributes` provides the labels for
+    /// [`OccupancyReport::by_attribute`]; unknown attributes are labelled by
+    /// their ID.
+    #[must_use]
+    pub fn new(sessions: &SessionList, screens: &[Screen], attributes: &[Attribute]) -> Self {
+        let screens_by_id: HashMap<ScreenId, &Screen> =
+            screens.iter().map(|screen| (screen.id, screen)).collect();
+        let attribute_names: HashMap<&AttributeId, &str> = attributes
+            .iter()
+            .map(|attribute| (&attribute.id, attribute.description.as_str()))
+            .collect();
+
+        let occupancies: Vec<SessionOccupancy> = sessions
+            .iter()
+            .map(|session| {
+                SessionOccupancy::new(session, screens_by_id.get(&session.screen_id).copied())
+            })
+            .collect();
+        // Attribute IDs live on the sessions, so look them up by session ID
+        let session_attributes: HashMap<SessionId, &Vec<AttributeId>> = sessions
+            .iter()
+            .map(|session| (session.id, &session.attributes))
+            .collect();
+
+        Self {
+            total: occupancies.iter().collect(),
+            by_film: group_by(&occupancies, |occupancy| {
+                vec![(occupancy.film_id.clone(), occupancy.title.clone())]
+            }),
+            by_screen: group_by(&occupancies, |occupancy| {
+                let label = screens_by_id.get(&occupancy.screen_id).map_or_else(
+                    || occupancy.screen_id.to_string(),
+                    |screen| screen.name.clone(),
+                );
+                vec![(occupancy.screen_id, label)]
+            }),
+            by_day: group_by(&occupancies, |occupancy| {
+                let day = occupancy.pre_show_start_time.date();
+                vec![(day, day.to_string())]
+            }),
+            by_week: group_by(&occupancies, |occupancy| {
+                let day = occupancy.pre_show_start_time.date();
+                let week = day.iso_week();
+                let monday = day - Days::new(u64::from(day.weekday().num_days_from_monday()));
+                vec![(monday, format!("{}-W{:02}", week.year(), week.week()))]
+            }),
+            by_attribute: group_by(&occupancies, |occupancy| {
+                session_attributes
+                    .get(&occupancy.session_id)
+                    .map(|ids| {
+                        ids.iter()
+                            .map(|id| {
+                                let label = attribute_names
+                                    .get(id)
+                                    .map_or_else(|| id.to_string(), |name| (*name).to_string());
+                                (id.clone(), label)
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }),
+            inconsistent_sessions: occupancies
+                .iter()
+                .filter(|occupancy| occupancy.capacity_mismatch.is_some())
+                .map(|occupancy| occupancy.session_id)
+                .collect(),
+            sessions: occupancies,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for computing [`OccupancyReport`]s
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::testing::{AttributeBuilder, FilmBuilder, ScreenBuilder, SessionBuilder};
+
+    /// Internal helper to get a time on a day of June 2025
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, day)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .expect("the time should exist")
+    }
+
+    #[test]
+    fn occupancy_uses_the_screen_capacity() {
+        let film = FilmBuilder::new("ST00000001").build();
+        let screen = ScreenBuilder::new(1).with_seats(200, 0).build();
+        let session = SessionBuilder::new(1, &film, &screen, at(1, 18))
+            .with_seats_sold(50)
+            .build();
+
+        let occupancy = SessionOccupancy::new(&session, Some(&screen));
+        assert_eq!(occupancy.capacity, 200);
+        assert!((occupancy.occupancy_percent - 25.0).abs() < f64::EPSILON);
+        assert_eq!(occupancy.capacity_mismatch, None);
+
+        let smaller = ScreenBuilder::new(1).with_seats(180, 0).build();
+        let occupancy = SessionOccupancy::new(&session, Some(&smaller));
+        assert_eq!(occupancy.capacity_mismatch, Some(20));
+
+        let occupancy = SessionOccupancy::new(&session, None);
+        assert_eq!(occupancy.capacity, 200);
+        assert_eq!(occupancy.capacity_mismatch, None);
+    }
+
+    #[test]
+    fn reports_aggregate_and_flag_inconsistent_sessions() {
+        let film = FilmBuilder::new("ST00000001").build();
+        let screen = ScreenBuilder::new(1).with_seats(100, 0).build();
+        let mut inconsistent = SessionBuilder::new(2, &film, &screen, at(2, 18))
+            .with_seats_sold(100)
+            .build();
+        inconsistent.seats_held = 5;
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screen, at(1, 18))
+                .with_seats_sold(40)
+                .build(),
+            inconsistent,
+        ]);
+
+        let report = OccupancyReport::new(&sessions, &[screen], &[]);
+        assert_eq!(report.total.sessions, 2);
+        assert_eq!(report.total.sold_out_sessions, 1);
+        assert_eq!(report.total.capacity, 200);
+        assert_eq!(report.total.seats_sold, 140);
+        assert!((report.total.occupancy_percent - 70.0).abs() < f64::EPSILON);
+        assert_eq!(report.by_film.len(), 1);
+        assert_eq!(report.by_day.len(), 2);
+        assert_eq!(report.by_screen[0].label, "Screen 1");
+        assert_eq!(
+            report.inconsistent_sessions,
+            [report.sessions[1].session_id]
+        );
+    }
+
+    #[test]
+    fn attributes_count_each_session_once() {
+        let film = FilmBuilder::new("ST00000001").build();
+        let screen = ScreenBuilder::new(1).build();
+        let matinee = AttributeBuilder::new("0000000001")
+            .with_name("MAT", "Matinee")
+            .build();
+        let captions = AttributeBuilder::new("0000000002").build();
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screen, at(1, 13))
+                .with_attribute(&matinee)
+                .with_attribute(&matinee)
+                .with_attribute(&captions)
+                .with_seats_sold(10)
+                .build(),
+            SessionBuilder::new(2, &film, &screen, at(1, 15))
+                .with_attribute(&matinee)
+                .with_seats_sold(20)
+                .build(),
+        ]);
+
+        let report = OccupancyReport::new(&sessions, &[screen], &[matinee]);
+        let groups: Vec<(&str, u32, u32)> = report
+            .by_attribute
+            .iter()
+            .map(|group| {
+                (
+                    group.label.as_str(),
+                    group.summary.sessions,
+                    group.summary.seats_sold,
+                )
+            })
+            .collect();
+        assert_eq!(groups, [("0000000002", 1, 10), ("Matinee", 2, 30)]);
+    }
+}
@@ -3,14 +3,16 @@
 
 use std::fmt::{self, Debug, Display, Formatter};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "blocking")]
+use crate::blocking;
 use crate::{client::Client, error::ApiResult, session::SessionList};
 
 /// The unique ID of an [`Attribute`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Hash)]
 #[serde(transparent)]
-pub struct AttributeId(String);
+pub struct AttributeId(pub(crate) String);
 impl AttributeId {
     /// Get the string representation of this [`AttributeId`]
     #[must_use]
@@ -26,6 +28,16 @@ impl AttributeId {
     pub async fn fetch(&self, client: &Client) -> ApiResult<Attribute> {
         client.get_attribute(self).await
     }
+
+    /// Blocking version of [`Self::fetch`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(&self, client: &blocking::Client) -> ApiResult<Attribute> {
+        client.block_on(self.fetch(client.inner()))
+    }
 }
 impl Display for AttributeId {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -34,7 +46,7 @@ impl Display for AttributeId {
 }
 
 /// An attribute that can be associated with [`crate::session::Session`]s
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Attribute {
     /// The unique ID of the attribute
@@ -64,4 +76,14 @@ impl Attribute {
             .await?
             .filter_containing_attribute(&self.id))
     }
+
+    /// Blocking version of [`Self::sessions`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn sessions_blocking(&self, client: &blocking::Client) -> ApiResult<SessionList> {
+        client.block_on(self.sessions(client.inner()))
+    }
 }
@@ -0,0 +1,64 @@
+//! Run a Prometheus exporter for the occupancy of upcoming sessions
+//!
+//! The exporter is configured with environment variables:
+//!
+//! - `VEEZI_API_KEY`: the Veezi access token (required)
+//! - `VEEZI_BASE_URL`: the API, as a region (`us`, `au` or `uk`) or a base URL,
+//!   defaulting to the US API
+//! - `VEEZI_EXPORTER_ADDR`: the address to listen on, defaulting to
+//!   `127.0.0.1:9898`
+//! - `VEEZI_EXPORTER_HOURS`: how many hours ahead sessions are exported,
+//!   defaulting to 24
+//!
+//! Metrics are served at `GET /metrics`.
+
+use std::{env, error::Error, process::ExitCode, sync::Arc};
+
+use chrono::TimeDelta;
+use libveezi::{client::ClientBuilder, exporter::OccupancyExporter, region::VeeziRegion};
+use tokio::net::TcpListener;
+
+/// The address listened on if `VEEZI_EXPORTER_ADDR` is not set
+const DEFAULT_ADDR: &str = "127.0.0.1:9898";
+
+/// Run the exporter until it fails
+async fn run() -> Result<(), Box<dyn Error>> {
+    let region: VeeziRegion = env::var("VEEZI_BASE_URL")
+        .map_or_else(|_| Ok(VeeziRegion::default()), |value| value.parse())?;
+    let token = env::var("VEEZI_API_KEY").map_err(|_| "VEEZI_API_KEY must be set")?;
+    let addr = env::var("VEEZI_EXPORTER_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+
+    let client = ClientBuilder::for_region(&region, token)
+        .with_default_caching()
+        .build()?;
+    let mut exporter = OccupancyExporter::new(Arc::new(client));
+    if let Ok(hours) = env::var("VEEZI_EXPORTER_HOURS") {
+        let window = hours
+            .parse()
+            .ok()
+            .filter(|hours: &i64| *hours >= 0)
+            .and_then(TimeDelta::try_hours)
+            .ok_or("VEEZI_EXPORTER_HOURS must be a whole number of hours")?;
+        exporter = exporter.with_window(window);
+    }
+
+    let listener = TcpListener::bind(&addr).await?;
+    println!(
+        "Exporting {} occupancy on http://{}/metrics",
+        region.base_url(),
+        listener.local_addr()?
+    );
+    exporter.serve(listener).await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("veezi-exporter: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
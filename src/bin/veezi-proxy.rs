@@ -0,0 +1,81 @@
+//! Run a caching proxy server exposing a Veezi-compatible API
+//!
+//! The server is configured with environment variables:
+//!
+//! - `VEEZI_API_KEY`: the real Veezi access token (required)
+//! - `VEEZI_BASE_URL`: the upstream API, as a region (`us`, `au` or `uk`) or a
+//!   base URL, defaulting to the US API
+//! - `VEEZI_PROXY_ADDR`: the address to listen on, defaulting to
+//!   `127.0.0.1:8080`
+//! - `VEEZI_PROXY_KEYS`: comma separated API keys downstream apps must send in
+//!   the `VeeziAccessToken` header; if unset, any request is allowed
+//! - `VEEZI_PROXY_CORS_ORIGINS`: comma separated origins allowed to make
+//!   cross-origin requests, or `*` for any origin
+//! - `VEEZI_PROXY_FACADE`: set to `1` or `true` to also serve the website
+//!   endpoints (`/showtimes`, `/films/now-playing`, `/films/coming-soon` and
+//!   `/screens/{id}/timeline`)
+//!
+//! When built with the `graphql` feature, a GraphQL endpoint is also served at
+//! `POST /graphql`.
+
+use std::{env, error::Error, process::ExitCode, sync::Arc};
+
+use libveezi::{client::ClientBuilder, region::VeeziRegion, server::ProxyServer};
+use tokio::net::TcpListener;
+
+/// The address listened on if `VEEZI_PROXY_ADDR` is not set
+const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+
+/// Read a comma separated list from an environment variable
+fn env_list(name: &str) -> Option<Vec<String>> {
+    env::var(name).ok().map(|value| {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+/// Run the proxy server until it fails
+async fn run() -> Result<(), Box<dyn Error>> {
+    let region: VeeziRegion = env::var("VEEZI_BASE_URL")
+        .map_or_else(|_| Ok(VeeziRegion::default()), |value| value.parse())?;
+    let token = env::var("VEEZI_API_KEY").map_err(|_| "VEEZI_API_KEY must be set")?;
+    let addr = env::var("VEEZI_PROXY_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+
+    let client = ClientBuilder::for_region(&region, token)
+        .with_default_caching()
+        .build()?;
+    let mut server = ProxyServer::new(Arc::new(client));
+    if let Some(keys) = env_list("VEEZI_PROXY_KEYS") {
+        server = server.with_api_keys(keys);
+    }
+    if let Some(origins) = env_list("VEEZI_PROXY_CORS_ORIGINS") {
+        server = server.with_cors_origins(origins);
+    }
+    if env::var("VEEZI_PROXY_FACADE").is_ok_and(|value| value == "1" || value == "true") {
+        server = server.with_facade();
+    }
+
+    let listener = TcpListener::bind(&addr).await?;
+    println!(
+        "Proxying {} on http://{}",
+        region.base_url(),
+        listener.local_addr()?
+    );
+    server.serve(listener).await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("veezi-proxy: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
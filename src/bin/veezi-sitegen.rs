@@ -0,0 +1,57 @@
+//! Generate a static showtimes site from the Veezi API
+//!
+//! Usage: `veezi-sitegen <output dir> [template dir]`
+//!
+//! The API is configured with the `VEEZI_BASE_URL` (a region such as `us`, `au`
+//! or `uk`, or a base URL, defaulting to the US API) and `VEEZI_API_KEY`
+//! environment variables. The number of days in the
+//! schedule can be set with `VEEZI_SITEGEN_DAYS`.
+
+use std::{env, error::Error, path::PathBuf, process::ExitCode};
+
+use libveezi::{
+    client::ClientBuilder,
+    region::VeeziRegion,
+    sitegen::{SiteGenerator, Snapshot},
+};
+
+/// Generate the site, returning the number of pages written
+async fn run() -> Result<usize, Box<dyn Error>> {
+    let mut args = env::args_os().skip(1);
+    let out_dir = PathBuf::from(
+        args.next()
+            .ok_or("usage: veezi-sitegen <output dir> [template dir]")?,
+    );
+    let template_dir = args.next().map(PathBuf::from);
+
+    let region: VeeziRegion = env::var("VEEZI_BASE_URL")
+        .map_or_else(|_| Ok(VeeziRegion::default()), |value| value.parse())?;
+    let token = env::var("VEEZI_API_KEY").map_err(|_| "VEEZI_API_KEY must be set")?;
+    let client = ClientBuilder::for_region(&region, token).build()?;
+
+    let mut generator = SiteGenerator::new();
+    if let Ok(days) = env::var("VEEZI_SITEGEN_DAYS") {
+        generator = generator.with_days(days.parse()?);
+    }
+    if let Some(template_dir) = template_dir {
+        generator = generator.with_template_dir(&template_dir)?;
+    }
+
+    let snapshot = Snapshot::fetch(&client).await?;
+    let now = snapshot.site.now();
+    Ok(generator.generate(&snapshot, now, &out_dir)?.len())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(pages) => {
+            println!("Wrote {pages} pages");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("veezi-sitegen: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,462 @@
+//! A command-line tool for querying the Veezi API
+//!
+//! The API key and base URL are read from the `--api-key` and `--base-url`
+//! options, the `VEEZI_API_KEY` and `VEEZI_BASE_URL` environment variables, or
+//! a TOML config file with `api_key` and `base_url` keys, in that order. The
+//! base URL may also be a region (`us`, `au` or `uk`), defaulting to the US
+//! API. The config file is read from `--config`, `VEEZI_CONFIG`, or
+//! `$XDG_CONFIG_HOME/veezi/config.toml` (defaulting to
+//! `~/.config/veezi/config.toml`).
+
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use chrono::{Days, NaiveDate, NaiveTime, TimeDelta};
+use clap::{Parser, Subcommand, ValueEnum};
+use libveezi::{
+    client::{Client, ClientBuilder},
+    csv::{
+        self, AttributeColumn, CsvColumn, FilmColumn, PackageFilmColumn, PackageFilmRow,
+        ScreenColumn, SessionColumn,
+    },
+    region::VeeziRegion,
+    site::Site,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The result type of the commands
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+/// Query the Veezi API from the command line
+#[derive(Parser, Debug)]
+#[command(name = "veezi", version, about)]
+struct Cli {
+    /// The Veezi API key
+    #[arg(long, env = "VEEZI_API_KEY", hide_env_values = true, global = true)]
+    api_key: Option<String>,
+    /// The region (`us`, `au` or `uk`) or base URL of the Veezi API
+    #[arg(long, env = "VEEZI_BASE_URL", global = true)]
+    base_url: Option<VeeziRegion>,
+    /// The path of the config file
+    #[arg(long, env = "VEEZI_CONFIG", global = true)]
+    config: Option<PathBuf>,
+    /// The output format
+    #[arg(long, short, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+    /// The command to run
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The output formats
+#[derive(ValueEnum, Debug, PartialEq, Eq, Clone, Copy)]
+enum Format {
+    /// An aligned table for humans
+    Table,
+    /// Pretty-printed JSON
+    Json,
+    /// CSV with every column
+    Csv,
+}
+
+/// The available commands
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List sessions
+    Sessions {
+        /// Only sessions on this date (YYYY-MM-DD, "today" or "tomorrow")
+        #[arg(long)]
+        date: Option<String>,
+        /// Only sessions of this film (ID or title)
+        #[arg(long)]
+        film: Option<String>,
+        /// Only sessions on this screen (ID, number or name)
+        #[arg(long)]
+        screen: Option<String>,
+        /// Only sessions with this attribute (ID or short name)
+        #[arg(long)]
+        attribute: Option<String>,
+        /// Only sessions that are available for web sales
+        #[arg(long)]
+        web: bool,
+    },
+    /// List films
+    Films,
+    /// List film packages, one row per film in each package
+    Packages,
+    /// List screens
+    Screens,
+    /// List attributes
+    Attributes,
+    /// Show the site details
+    Site,
+}
+
+/// The contents of the config file
+#[derive(Deserialize, Debug, Default)]
+struct Config {
+    /// The Veezi API key
+    api_key: Option<String>,
+    /// The region or base URL of the Veezi API
+    base_url: Option<String>,
+}
+impl Config {
+    /// Load the config file, if it exists
+    fn load(path: Option<PathBuf>) -> CliResult<Self> {
+        let explicit = path.is_some();
+        let Some(path) = path.or_else(default_config_path) else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => Ok(toml::from_str(&contents)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !explicit => Ok(Self::default()),
+            Err(err) => Err(format!("could not read {}: {err}", path.display()).into()),
+        }
+    }
+}
+
+/// Get the default path of the config file
+fn default_config_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("veezi").join("config.toml"))
+}
+
+/// Write rows as an aligned table, with a header
+fn write_table(out: &mut impl Write, headers: &[&str], rows: &[Vec<String>]) -> io::Result<()> {
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|header| header.chars().count())
+        .collect();
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+    let write_row = |out: &mut dyn Write, values: &mut dyn Iterator<Item = &str>| {
+        let line: Vec<String> = values
+            .zip(&widths)
+            .map(|(value, width)| format!("{value:<width$}"))
+            .collect();
+        writeln!(out, "{}", line.join("  ").trim_end())
+    };
+    write_row(out, &mut headers.iter().copied())?;
+    for row in rows {
+        write_row(out, &mut row.iter().map(String::as_str))?;
+    }
+    Ok(())
+}
+
+/// Write rows as an aligned table with the given columns
+fn write_column_table<T, C>(out: &mut impl Write, rows: &[T], columns: &[C]) -> io::Result<()>
+where
+    C: CsvColumn<T>,
+{
+    let headers: Vec<&str> = columns.iter().map(|column| column.header()).collect();
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| column.value(row, None))
+                .collect()
+        })
+        .collect();
+    write_table(out, &headers, &rows)
+}
+
+/// Write rows in the requested format, using `table_columns` for tables and
+/// `all_columns` for CSV
+fn write_rows<T, C>(
+    format: Format,
+    rows: &[T],
+    table_columns: &[C],
+    all_columns: &[C],
+    site: Option<&Site>,
+) -> CliResult<()>
+where
+    T: Serialize,
+    C: CsvColumn<T>,
+{
+    let mut out = io::stdout().lock();
+    match format {
+        Format::Table => write_column_table(&mut out, rows, table_columns)?,
+        Format::Json => writeln!(out, "{}", serde_json::to_string_pretty(rows)?)?,
+        Format::Csv => csv::write_csv(out, rows, all_columns, site)?,
+    }
+    Ok(())
+}
+
+/// Parse a date filter
+fn parse_date(date: &str, today: NaiveDate) -> CliResult<NaiveDate> {
+    match date {
+        "today" | "tonight" => Ok(today),
+        "tomorrow" => today
+            .checked_add_days(Days::new(1))
+            .ok_or_else(|| "date out of range".into()),
+        _ => Ok(NaiveDate::parse_from_str(date, "%Y-%m-%d")?),
+    }
+}
+
+/// List sessions matching the given filters
+async fn sessions(
+    client: &Client,
+    format: Format,
+    date: Option<String>,
+    film: Option<String>,
+    screen: Option<String>,
+    attribute: Option<String>,
+    web: bool,
+) -> CliResult<()> {
+    let site = client.get_site().await?;
+    let mut sessions = if web {
+        client.list_web_sessions().await?
+    } else {
+        client.list_sessions().await?
+    };
+
+    if let Some(date) = date {
+        let date = parse_date(&date, site.now().date())?;
+        let next = date
+            .checked_add_days(Days::new(1))
+            .ok_or("date out of range")?;
+        // The end of the range is inclusive, so stop just before midnight
+        sessions = sessions.filter_by_time_range(
+            date.and_time(NaiveTime::MIN),
+            next.and_time(NaiveTime::MIN) - TimeDelta::seconds(1),
+        );
+    }
+    if let Some(film) = film {
+        let films = client.list_films().await?;
+        let film = films
+            .iter()
+            .find(|candidate| candidate.id.as_str() == film)
+            .or_else(|| {
+                films
+                    .iter()
+                    .find(|candidate| candidate.title.eq_ignore_ascii_case(&film))
+            })
+            .ok_or_else(|| format!("no film matches {film:?}"))?;
+        sessions = sessions.filter_by_film(&film.id);
+    }
+    if let Some(screen) = screen {
+        let screens = client.list_screens().await?;
+        let screen = screens
+            .iter()
+            .find(|candidate| candidate.id.to_string() == screen)
+            .or_else(|| {
+                screens.iter().find(|candidate| {
+                    candidate.screen_number == screen
+                        || candidate.name.eq_ignore_ascii_case(&screen)
+                })
+            })
+            .ok_or_else(|| format!("no screen matches {screen:?}"))?;
+        sessions = sessions.filter_by_screen(screen.id);
+    }
+    if let Some(attribute) = attribute {
+        let attributes = client.list_attributes().await?;
+        let attribute = attributes
+            .iter()
+            .find(|candidate| {
+                candidate.id.as_str() == attribute
+                    || candidate.short_name.eq_ignore_ascii_case(&attribute)
+            })
+            .ok_or_else(|| format!("no attribute matches {attribute:?}"))?;
+        sessions = sessions.filter_containing_attribute(&attribute.id);
+    }
+
+    let mut sessions = sessions.into_vec();
+    sessions.sort_by_key(|session| session.feature_start_time);
+    write_rows(
+        format,
+        &sessions,
+        &[
+            SessionColumn::Id,
+            SessionColumn::FeatureStartTime,
+            SessionColumn::Title,
+            SessionColumn::ScreenId,
+            SessionColumn::Status,
+            SessionColumn::SeatsAvailable,
+            SessionColumn::SeatsSold,
+        ],
+        SessionColumn::ALL,
+        Some(&site),
+    )
+}
+
+/// Show the site details, as field/value pairs for tables and CSV
+fn site(format: Format, site: &Site) -> CliResult<()> {
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(site)?);
+        return Ok(());
+    }
+
+    let Value::Object(fields) = serde_json::to_value(site)? else {
+        return Err("site should serialize to an object".into());
+    };
+    let rows: Vec<Vec<String>> = fields
+        .into_iter()
+        .map(|(field, value)| {
+            let value = match value {
+                Value::Null => String::new(),
+                Value::String(value) => value,
+                Value::Bool(_) | Value::Number(_) | Value::Array(_) | Value::Object(_) => {
+                    value.to_string()
+                }
+            };
+            vec![field, value]
+        })
+        .collect();
+
+    let mut out = io::stdout().lock();
+    let columns = [KeyValueColumn::Field, KeyValueColumn::Value];
+    if format == Format::Csv {
+        csv::write_csv(out, &rows, &columns, None)?;
+    } else {
+        write_column_table(&mut out, &rows, &columns)?;
+    }
+    Ok(())
+}
+
+/// The columns of a field/value listing
+#[derive(Debug, Clone, Copy)]
+enum KeyValueColumn {
+    /// The name of the field
+    Field,
+    /// The value of the field
+    Value,
+}
+impl CsvColumn<Vec<String>> for KeyValueColumn {
+    fn header(self) -> &'static str {
+        match self {
+            Self::Field => "Field",
+            Self::Value => "Value",
+        }
+    }
+
+    fn value(self, row: &Vec<String>, _site: Option<&Site>) -> String {
+        let index = match self {
+            Self::Field => 0,
+            Self::Value => 1,
+        };
+        row.get(index).cloned().unwrap_or_default()
+    }
+}
+
+/// Run the command line tool
+async fn run(cli: Cli) -> CliResult<()> {
+    let config = Config::load(cli.config)?;
+    let token = cli
+        .api_key
+        .or(config.api_key)
+        .ok_or("no API key configured; set VEEZI_API_KEY or api_key in the config file")?;
+    let region = match (cli.base_url, config.base_url) {
+        (Some(region), _) => region,
+        (None, Some(base_url)) => base_url.parse()?,
+        (None, None) => VeeziRegion::default(),
+    };
+    let client = ClientBuilder::for_region(&region, token).build()?;
+
+    match cli.command {
+        Command::Sessions {
+            date,
+            film,
+            screen,
+            attribute,
+            web,
+        } => sessions(&client, cli.format, date, film, screen, attribute, web).await,
+        Command::Films => {
+            let mut films = client.list_films().await?;
+            films.sort_by_key(|film| film.display_sequence);
+            write_rows(
+                cli.format,
+                &films,
+                &[
+                    FilmColumn::Id,
+                    FilmColumn::Title,
+                    FilmColumn::Rating,
+                    FilmColumn::Duration,
+                    FilmColumn::Status,
+                    FilmColumn::OpeningDate,
+                ],
+                FilmColumn::ALL,
+                None,
+            )
+        }
+        Command::Packages => {
+            let packages = client.list_film_packages().await?;
+            if cli.format == Format::Json {
+                println!("{}", serde_json::to_string_pretty(&packages)?);
+                return Ok(());
+            }
+            let rows = PackageFilmRow::from_packages(&packages);
+            let mut out = io::stdout().lock();
+            if cli.format == Format::Csv {
+                csv::write_csv(out, &rows, PackageFilmColumn::ALL, None)?;
+            } else {
+                write_column_table(
+                    &mut out,
+                    &rows,
+                    &[
+                        PackageFilmColumn::PackageId,
+                        PackageFilmColumn::PackageTitle,
+                        PackageFilmColumn::FilmTitle,
+                        PackageFilmColumn::SplitPercent,
+                        PackageFilmColumn::Order,
+                    ],
+                )?;
+            }
+            Ok(())
+        }
+        Command::Screens => {
+            let mut screens = client.list_screens().await?;
+            screens.sort_by(|a, b| a.screen_number.cmp(&b.screen_number));
+            write_rows(
+                cli.format,
+                &screens,
+                &[
+                    ScreenColumn::Id,
+                    ScreenColumn::ScreenNumber,
+                    ScreenColumn::Name,
+                    ScreenColumn::TotalSeats,
+                ],
+                ScreenColumn::ALL,
+                None,
+            )
+        }
+        Command::Attributes => {
+            let attributes = client.list_attributes().await?;
+            write_rows(
+                cli.format,
+                &attributes,
+                &[
+                    AttributeColumn::Id,
+                    AttributeColumn::ShortName,
+                    AttributeColumn::Description,
+                ],
+                AttributeColumn::ALL,
+                None,
+            )
+        }
+        Command::Site => site(cli.format, &client.get_site().await?),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("veezi: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
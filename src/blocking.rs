@@ -0,0 +1,528 @@
+//! A blocking (synchronous) [`Client`] for the Veezi API
+//!
+//! The primary type is [`Client`], which wraps an asynchronous
+//! [`crate::client::Client`] with its own single-threaded Tokio runtime, so it
+//! can be used from synchronous programs such as scripts and cron jobs. It is
+//! built from the same [`ClientBuilder`], so it shares the same models,
+//! caching, transport and middleware configuration.
+//!
+//! The relationship helpers on the models have blocking equivalents with a
+//! `_blocking` suffix, such as [`FilmId::fetch_blocking`] and
+//! [`Session::film_blocking`].
+//!
+//! A blocking [`Client`] must not be used from within an asynchronous
+//! context, as blocking on its runtime there will panic.
+//!
+//! This module is only available with the `blocking` feature.
+
+use std::fmt::{self, Debug, Formatter};
+
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{
+    attr::{Attribute, AttributeId},
+    client::{self, ClientBuilder, ClientCacheStats},
+    error::{ApiResult, LibVeeziError},
+    feed::Feed,
+    film::{Film, FilmId, PlayingStatus},
+    package::{FilmPackage, FilmPackageId},
+    screen::{Screen, ScreenId},
+    session::{Session, SessionId, SessionList},
+    site::Site,
+};
+
+/// The blocking client for interacting with the Veezi API
+pub struct Client {
+    /// The asynchronous client making the requests
+    inner: client::Client,
+    /// The runtime the requests are run on
+    runtime: Runtime,
+}
+impl Debug for Client {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client").finish_non_exhaustive()
+    }
+}
+impl Client {
+    /// Create a new blocking Veezi API client from a [`ClientBuilder`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the URL provided is invalid or
+    /// the runtime cannot be started.
+    pub fn from_builder(builder: ClientBuilder) -> ApiResult<Self> {
+        Ok(Self {
+            inner: builder.build()?,
+            runtime: Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(LibVeeziError::Runtime)?,
+        })
+    }
+
+    /// Create a new blocking Veezi API client with the given base URL and
+    /// access token, without caching
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the URL provided is invalid or
+    /// the runtime cannot be started.
+    pub fn new(base_url: &str, token: String) -> ApiResult<Self> {
+        Self::from_builder(ClientBuilder::new(base_url, token))
+    }
+
+    /// Get the asynchronous [`crate::client::Client`] wrapped by this client
+    #[must_use]
+    pub const fn inner(&self) -> &client::Client {
+        &self.inner
+    }
+
+    /// Internal helper to run a future on this client's runtime
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Invalidate all cached data
+    pub fn invalidate_all_caches(&self) {
+        self.inner.invalidate_all_caches();
+    }
+
+    /// Get the hit and miss counts and the approximate number of entries of
+    /// each cache
+    #[must_use]
+    pub fn cache_stats(&self) -> ClientCacheStats {
+        self.inner.cache_stats()
+    }
+
+    /// Record the approximate number of entries of each cache as a gauge with
+    /// the `metrics` feature
+    ///
+    /// Cache entry counts are not tracked as requests are made, so this should
+    /// be called before each scrape of the installed recorder.
+    #[cfg(feature = "metrics")]
+    pub fn record_cache_metrics(&self) {
+        self.block_on(self.inner.record_cache_metrics());
+    }
+
+    /// Get a list of all future [Session]s.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_sessions(&self) -> ApiResult<SessionList> {
+        self.block_on(self.inner.list_sessions())
+    }
+
+    /// Invalidate a cached [`Session`] by its ID
+    ///
+    /// As a side effect, this also invalidates the full session list cache
+    pub fn invalidate_cached_session(&self, id: SessionId) {
+        self.block_on(self.inner.invalidate_cached_session(id));
+    }
+
+    /// Invalidate all cached [`Session`]s
+    pub fn invalidate_all_cached_sessions(&self) {
+        self.inner.invalidate_all_cached_sessions();
+    }
+
+    /// Get a list of all future [`Session`]s that should be available for
+    /// online sales.
+    ///
+    /// This asserts the following for each [`Session`]:
+    /// - [`Session::sales_cut_off_time`] is in the future
+    /// - [`Session::status`] is [`crate::session::SessionStatus::Open`]
+    /// - [`Session::show_type`] is [`crate::session::ShowType::Public`]
+    /// - [`Session::sales_via`] allows [`crate::session::SalesVia::www`] sales
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_web_sessions(&self) -> ApiResult<SessionList> {
+        self.block_on(self.inner.list_web_sessions())
+    }
+
+    /// Invalidate all cached web [`Session`]s
+    pub fn invalidate_all_cached_web_sessions(&self) {
+        self.inner.invalidate_all_cached_web_sessions();
+    }
+
+    /// Get a specific [Session] by its ID.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn get_session(&self, id: SessionId) -> ApiResult<Session> {
+        self.block_on(self.inner.get_session(id))
+    }
+
+    /// Get a list of all [Film]s in the Veezi system.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_films(&self) -> ApiResult<Vec<Film>> {
+        self.block_on(self.inner.list_films())
+    }
+
+    /// Invalidate all cached [`Film`]s
+    pub fn invalidate_all_cached_films(&self) {
+        self.inner.invalidate_all_cached_films();
+    }
+
+    /// Invalidate a cached [`Film`] by its ID
+    ///
+    /// As a side effect, this also invalidates the full film list cache
+    pub fn invalidate_cached_film(&self, id: &FilmId) {
+        self.block_on(self.inner.invalidate_cached_film(id));
+    }
+
+    /// Get a specific [`Film`] by its ID.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn get_film(&self, id: &FilmId) -> ApiResult<Film> {
+        self.block_on(self.inner.get_film(id))
+    }
+
+    /// Get a specific [`Film`] by its exact [`Film::title`]. If multiple films
+    /// have the same title, the first one found will be returned.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails, or None if
+    /// no film with the given title is found.
+    pub fn get_film_by_title(&self, title: &str) -> ApiResult<Option<Film>> {
+        self.block_on(self.inner.get_film_by_title(title))
+    }
+
+    /// Get a specific [`Film`] by its exact [`Film::short_name`]. If multiple
+    /// films have the same short name, the first one found will be
+    /// returned.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails, or None if
+    /// no film with the given short name is found.
+    pub fn get_film_by_short_name(&self, short_name: &str) -> ApiResult<Option<Film>> {
+        self.block_on(self.inner.get_film_by_short_name(short_name))
+    }
+
+    /// Get a specific [`Film`] by its exact [`Film::signage_text`]. If multiple
+    /// films have the same signage text, the first one found will be
+    /// returned.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails, or None if
+    /// no film with the given signage text is found.
+    pub fn get_film_by_signage_text(&self, signage_text: &str) -> ApiResult<Option<Film>> {
+        self.block_on(self.inner.get_film_by_signage_text(signage_text))
+    }
+
+    /// Get a list of all [`Film`]s with a specific [`Film::genre`] string.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_films_by_genre(&self, genre: &str) -> ApiResult<Vec<Film>> {
+        self.block_on(self.inner.list_films_by_genre(genre))
+    }
+
+    /// Get a list of all [`Film`]s by a specific [`Film::distributor`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_films_by_distributor(&self, distributor: &str) -> ApiResult<Vec<Film>> {
+        self.block_on(self.inner.list_films_by_distributor(distributor))
+    }
+
+    /// Get only the films that have sessions scheduled in the included time
+    /// range.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_films_with_sessions_in_time_range(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> ApiResult<Vec<Film>> {
+        self.block_on(
+            self.inner
+                .list_films_with_sessions_in_time_range(start, end),
+        )
+    }
+
+    /// Get only the films that have sessions scheduled in the included date
+    /// range.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_films_with_sessions_in_date_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> ApiResult<Vec<Film>> {
+        self.block_on(
+            self.inner
+                .list_films_with_sessions_in_date_range(start, end),
+        )
+    }
+
+    /// Classify every [`Film`] as now playing, on advance sale or coming soon,
+    /// using a window of `window` from now to decide what is now playing.
+    ///
+    /// Films that have opened but have no upcoming sessions are omitted. See
+    /// [`SessionList::classify_film`] for the exact rules.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn classify_films(&self, window: TimeDelta) -> ApiResult<Vec<(Film, PlayingStatus)>> {
+        self.block_on(self.inner.classify_films(window))
+    }
+
+    /// Get the [`Film`]s that are now playing, i.e. have opened and have a
+    /// session within `window` from now.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_now_playing_films(&self, window: TimeDelta) -> ApiResult<Vec<Film>> {
+        self.block_on(self.inner.list_now_playing_films(window))
+    }
+
+    /// Get the [`Film`]s that are coming soon, i.e. open in the future without
+    /// sessions on sale, or whose first session is beyond `window` from now.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_coming_soon_films(&self, window: TimeDelta) -> ApiResult<Vec<Film>> {
+        self.block_on(self.inner.list_coming_soon_films(window))
+    }
+
+    /// Get the [`Film`]s on advance sale, i.e. open in the future but with
+    /// sessions already on sale.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_advance_sales_films(&self) -> ApiResult<Vec<Film>> {
+        self.block_on(self.inner.list_advance_sales_films())
+    }
+
+    /// Get the next [`Session`] of a [`Film`] that can be booked online, if
+    /// any.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn get_next_session_for_film(&self, film_id: &FilmId) -> ApiResult<Option<Session>> {
+        self.block_on(self.inner.get_next_session_for_film(film_id))
+    }
+
+    /// Get up to `count` of the next [`Session`]s on a [`Screen`] that can be
+    /// booked online.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_next_sessions_on_screen(
+        &self,
+        screen_id: ScreenId,
+        count: usize,
+    ) -> ApiResult<Vec<Session>> {
+        self.block_on(self.inner.list_next_sessions_on_screen(screen_id, count))
+    }
+
+    /// Build a [`Feed`] of the [`Film`]s that are now playing, on advance sale
+    /// or coming soon, each listing up to `showtimes` of its next sessions that
+    /// can be booked online.
+    ///
+    /// See [`Feed::films`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any API request fails.
+    pub fn film_feed(&self, link: &str, window: TimeDelta, showtimes: usize) -> ApiResult<Feed> {
+        self.block_on(self.inner.film_feed(link, window, showtimes))
+    }
+
+    /// Build a [`Feed`] of the web [`Session`]s that are not in `previous`,
+    /// e.g. the [`SessionList`] the last feed was generated from.
+    ///
+    /// See [`Feed::new_sessions`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any API request fails.
+    pub fn new_session_feed(&self, link: &str, previous: &SessionList) -> ApiResult<Feed> {
+        self.block_on(self.inner.new_session_feed(link, previous))
+    }
+
+    /// Get a list of all [`FilmPackage`]s in the Veezi system.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_film_packages(&self) -> ApiResult<Vec<FilmPackage>> {
+        self.block_on(self.inner.list_film_packages())
+    }
+
+    /// Invalidate all cached [`FilmPackage`]s
+    pub fn invalidate_all_cached_film_packages(&self) {
+        self.inner.invalidate_all_cached_film_packages();
+    }
+
+    /// Invalidate a cached [`FilmPackage`] by its ID
+    ///
+    /// As a side effect, this also invalidates the full film package list cache
+    pub fn invalidate_cached_film_package(&self, id: FilmPackageId) {
+        self.block_on(self.inner.invalidate_cached_film_package(id));
+    }
+
+    /// Get a specific [`FilmPackage`] by its exact [`FilmPackage::title`]. If
+    /// multiple packages have the same title, the first one found will be
+    /// returned.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails, or None if
+    /// no package with the given title is found.
+    pub fn get_film_package_by_title(&self, title: &str) -> ApiResult<Option<FilmPackage>> {
+        self.block_on(self.inner.get_film_package_by_title(title))
+    }
+
+    /// Get a list of all [`FilmPackage`]s containing a specific [`FilmId`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_film_packages_by_film_id(&self, film_id: &FilmId) -> ApiResult<Vec<FilmPackage>> {
+        self.block_on(self.inner.list_film_packages_by_film_id(film_id))
+    }
+
+    /// Get a specific [`FilmPackage`] by its ID.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn get_film_package(&self, id: FilmPackageId) -> ApiResult<FilmPackage> {
+        self.block_on(self.inner.get_film_package(id))
+    }
+
+    /// Get a list of all [`Screen`]s in the current site.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_screens(&self) -> ApiResult<Vec<Screen>> {
+        self.block_on(self.inner.list_screens())
+    }
+
+    /// Invalidate all cached [`Screen`]s
+    pub fn invalidate_all_cached_screens(&self) {
+        self.inner.invalidate_all_cached_screens();
+    }
+
+    /// Invalidate a cached [`Screen`] by its ID
+    ///
+    /// As a side effect, this also invalidates the full screen list cache
+    pub fn invalidate_cached_screen(&self, id: ScreenId) {
+        self.block_on(self.inner.invalidate_cached_screen(id));
+    }
+
+    /// Get a specific [`Screen`] by its ID.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn get_screen(&self, id: ScreenId) -> ApiResult<Screen> {
+        self.block_on(self.inner.get_screen(id))
+    }
+
+    /// Get a specific [`Screen`] by its exact [`Screen::screen_number`]. If
+    /// multiple screens have the same screen number, the first one found will
+    /// be returned.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails, or None if
+    /// no screen with the given screen number is found.
+    pub fn get_screen_by_number(&self, screen_number: String) -> ApiResult<Option<Screen>> {
+        self.block_on(self.inner.get_screen_by_number(screen_number))
+    }
+
+    /// Get the [`Site`] information for the current Veezi site.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn get_site(&self) -> ApiResult<Site> {
+        self.block_on(self.inner.get_site())
+    }
+
+    /// Invalidate the cached [`Site`]
+    pub fn invalidate_cached_site(&self) {
+        self.inner.invalidate_cached_site();
+    }
+
+    /// Get a list of all [`Attribute`]s set in the site.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn list_attributes(&self) -> ApiResult<Vec<Attribute>> {
+        self.block_on(self.inner.list_attributes())
+    }
+
+    /// Invalidate all cached [`Attribute`]s
+    pub fn invalidate_all_cached_attributes(&self) {
+        self.inner.invalidate_all_cached_attributes();
+    }
+
+    /// Invalidate a cached [`Attribute`] by its ID
+    ///
+    /// As a side effect, this also invalidates the full attribute list cache
+    pub fn invalidate_cached_attribute(&self, id: &AttributeId) {
+        self.block_on(self.inner.invalidate_cached_attribute(id));
+    }
+
+    /// Get a specific [`Attribute`] by its ID.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub fn get_attribute(&self, id: &AttributeId) -> ApiResult<Attribute> {
+        self.block_on(self.inner.get_attribute(id))
+    }
+
+    /// Get a specific [`Attribute`] by its exact [`Attribute::short_name`]. If
+    /// multiple attributes have the same short name, the first one found
+    /// will be returned.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails, or None if
+    /// no attribute with the given short name is found.
+    pub fn get_attribute_by_short_name(&self, short_name: &str) -> ApiResult<Option<Attribute>> {
+        self.block_on(self.inner.get_attribute_by_short_name(short_name))
+    }
+
+    /// Get a specific [`Attribute`] by its exact [`Attribute::description`]. If
+    /// multiple attributes have the same description, the first one found
+    /// will be returned.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails, or None if
+    /// no attribute with the given description is found.
+    pub fn get_attribute_by_description(&self, description: &str) -> ApiResult<Option<Attribute>> {
+        self.block_on(self.inner.get_attribute_by_description(description))
+    }
+}
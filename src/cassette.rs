@@ -0,0 +1,327 @@
+//! Record/replay HTTP cassettes for the [`Client`]
+//!
+//! The primary type is [`Cassette`], a list of request/response
+//! [`Interaction`]s stored as a JSON file. A [`Client`] built with
+//! [`ClientBuilder::with_cassette_recording`] records every request it makes
+//! through a [`RecordingTransport`], which writes the cassette file when it is
+//! dropped along with the client or flushed, and one built with
+//! [`ClientBuilder::with_cassette_replay`] serves responses from a cassette
+//! through a [`ReplayTransport`] without touching the network.
+//!
+//! The `VeeziAccessToken` header is always redacted before an interaction is
+//! stored, so cassettes recorded against production can be committed and used
+//! in CI. Because replayed responses are deserialized by the current model
+//! types, a replayed cassette catches any drift between real payloads and
+//! [`crate::session::Session`], [`crate::film::Film`] and so on as a
+//! [`LibVeeziError::Decode`](crate::error::LibVeeziError::Decode) error.
+//!
+//! [`ClientBuilder::with_cassette_recording`]:
+//!     crate::client::ClientBuilder::with_cassette_recording
+//! [`ClientBuilder::with_cassette_replay`]:
+//!     crate::client::ClientBuilder::with_cassette_replay
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use log::warn;
+use reqwest::{
+    StatusCode,
+    header::{self, HeaderMap, HeaderValue},
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[cfg(doc)]
+use crate::client::Client;
+use crate::transport::{Transport, TransportFuture, TransportRequest, TransportResponse};
+
+/// The header the access token is sent in
+pub const ACCESS_TOKEN_HEADER: &str = "VeeziAccessToken";
+
+/// The value stored in place of the access token
+pub const REDACTED: &str = "[REDACTED]";
+
+/// The list of errors that can occur when recording or replaying a
+/// [`Cassette`]
+#[derive(Debug)]
+pub enum CassetteError {
+    /// An error occurred while reading or writing a cassette file
+    Io(io::Error),
+    /// A cassette file is not valid JSON
+    Format(serde_json::Error),
+    /// A request was replayed that the cassette has no interaction for
+    NoInteraction {
+        /// The HTTP method of the request
+        method: String,
+        /// The endpoint of the request (e.g., `v1/session`)
+        endpoint: String,
+    },
+}
+impl Display for CassetteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::Format(err) => write!(f, "Invalid cassette: {err}"),
+            Self::NoInteraction { method, endpoint } => {
+                write!(f, "No recorded interaction for {method} {endpoint}")
+            }
+        }
+    }
+}
+impl Error for CassetteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Format(err) => Some(err),
+            Self::NoInteraction { .. } => None,
+        }
+    }
+}
+impl From<io::Error> for CassetteError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+impl From<serde_json::Error> for CassetteError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Format(err)
+    }
+}
+
+/// A request stored in a [`Cassette`]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct CassetteRequest {
+    /// The HTTP method of the request
+    pub method: String,
+    /// The path and query of the request, without the leading slash (e.g.,
+    /// `v1/session`)
+    pub endpoint: String,
+    /// The full URL of the request
+    pub url: String,
+    /// The headers of the request, with the access token redacted
+    pub headers: BTreeMap<String, String>,
+}
+
+/// A response stored in a [`Cassette`]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct CassetteResponse {
+    /// The HTTP status code of the response
+    pub status: u16,
+    /// The raw body of the response
+    pub body: String,
+}
+
+/// A request/response pair stored in a [`Cassette`]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct Interaction {
+    /// The request
+    pub request: CassetteRequest,
+    /// The response
+    pub response: CassetteResponse,
+}
+impl Interaction {
+    /// Create a new [`Interaction`] for a request with a redacted access token
+    #[must_use]
+    pub fn new(method: &str, url: &Url, status: u16, body: String) -> Self {
+        Self {
+            request: CassetteRequest {
+                method: method.to_string(),
+                endpoint: endpoint(url),
+                url: url.to_string(),
+                headers: BTreeMap::from([(ACCESS_TOKEN_HEADER.to_string(), REDACTED.to_string())]),
+            },
+            response: CassetteResponse { status, body },
+        }
+    }
+}
+
+/// A list of recorded [`Interaction`]s
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Eq, Clone)]
+pub struct Cassette {
+    /// The interactions, in the order they were recorded
+    pub interactions: Vec<Interaction>,
+}
+impl Cassette {
+    /// Load a [`Cassette`] from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be read or is not
+    /// a valid cassette.
+    pub fn load(path: &Path) -> Result<Self, CassetteError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Save this [`Cassette`] to a JSON file
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), CassetteError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Whether a [`Client`] records to or replays from a [`Cassette`]
+#[derive(Debug, Clone)]
+pub enum CassetteMode {
+    /// Make real requests and write every interaction to the given file
+    Record(PathBuf),
+    /// Serve every request from the given cassette with no network access
+    Replay(Cassette),
+}
+impl CassetteMode {
+    /// Wrap a [`Transport`] to record to or replay from the cassette
+    #[must_use]
+    pub fn wrap(self, transport: Arc<dyn Transport>) -> Arc<dyn Transport> {
+        match self {
+            Self::Record(path) => Arc::new(RecordingTransport::new(transport, path)),
+            Self::Replay(cassette) => Arc::new(ReplayTransport::new(cassette)),
+        }
+    }
+}
+
+/// Internal helper to lock a [`Mutex`] of a cassette
+#[allow(clippy::missing_panics_doc)]
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().expect("cassette lock should not be poisoned")
+}
+
+/// Internal helper to get the endpoint of a URL, relative to the root
+fn endpoint(url: &Url) -> String {
+    let path = url.path().trim_start_matches('/');
+    url.query()
+        .map_or_else(|| path.to_string(), |query| format!("{path}?{query}"))
+}
+
+/// A [`Transport`] that records every interaction of another transport to a
+/// [`Cassette`] file
+///
+/// Interactions are kept in memory and written to the file by
+/// [`RecordingTransport::flush`], which is called automatically when the
+/// transport is dropped.
+pub struct RecordingTransport {
+    /// The transport making the real requests
+    inner: Arc<dyn Transport>,
+    /// The file to write the cassette to
+    path: PathBuf,
+    /// The interactions recorded so far
+    cassette: Mutex<Cassette>,
+}
+impl RecordingTransport {
+    /// Create a new [`RecordingTransport`] writing to a file
+    #[must_use]
+    pub fn new(inner: Arc<dyn Transport>, path: PathBuf) -> Self {
+        Self {
+            inner,
+            path,
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+
+    /// Write the interactions recorded so far to the cassette file
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be written.
+    pub fn flush(&self) -> Result<(), CassetteError> {
+        let cassette = lock(&self.cassette).clone();
+        cassette.save(&self.path)
+    }
+}
+impl Drop for RecordingTransport {
+    fn drop(&mut self) {
+        if let Err(err) = self.flush() {
+            warn!("Failed to write cassette {}: {err}", self.path.display());
+        }
+    }
+}
+impl Transport for RecordingTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let method = request.method.to_string();
+            let url = request.url.clone();
+            let response = self.inner.send(request).await?;
+            let interaction = Interaction::new(
+                &method,
+                &url,
+                response.status.as_u16(),
+                String::from_utf8_lossy(&response.body).into_owned(),
+            );
+            lock(&self.cassette).interactions.push(interaction);
+            Ok(response)
+        })
+    }
+}
+
+/// A [`Transport`] that serves every request from a [`Cassette`]
+///
+/// Identical requests are answered with their recorded responses in order,
+/// repeating the last one once they run out.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    /// The cassette being replayed
+    cassette: Cassette,
+    /// Whether each interaction has been replayed yet
+    played: Mutex<Vec<bool>>,
+}
+impl ReplayTransport {
+    /// Create a new [`ReplayTransport`] for a cassette
+    #[must_use]
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            played: Mutex::new(vec![false; cassette.interactions.len()]),
+            cassette,
+        }
+    }
+
+    /// Find the recorded response to a request
+    fn replay(&self, method: &str, endpoint: &str) -> Result<CassetteResponse, CassetteError> {
+        let matching: Vec<usize> = self
+            .cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| {
+                interaction.request.method == method && interaction.request.endpoint == endpoint
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let mut played = lock(&self.played);
+        let index = matching
+            .iter()
+            .find(|index| !played[**index])
+            .or_else(|| matching.last())
+            .copied()
+            .ok_or_else(|| CassetteError::NoInteraction {
+                method: method.to_string(),
+                endpoint: endpoint.to_string(),
+            })?;
+        played[index] = true;
+        drop(played);
+        Ok(self.cassette.interactions[index].response.clone())
+    }
+}
+impl Transport for ReplayTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let response = self.replay(request.method.as_str(), &endpoint(&request.url))?;
+            Ok(TransportResponse {
+                status: StatusCode::from_u16(response.status)
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                headers: HeaderMap::from_iter([(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/json"),
+                )]),
+                body: response.body.into_bytes(),
+            })
+        })
+    }
+}
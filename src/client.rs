@@ -1,27 +1,54 @@
 //! The [`Client`] for interfacing with the Veezi API
+//!
+//! With the `tracing` feature, every [`Client`] operation that may make API
+//! requests runs in a `tracing` span carrying its entity ID, if any, and
+//! whether it was served from the cache (`cache = "hit"` or `"miss"`). Each
+//! HTTP request then gets a child `request` span with its endpoint, status
+//! code, response size in bytes and latency in milliseconds.
+
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
-use std::{fmt::Debug, time::Duration};
-
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
 use log::debug;
 use moka::future::{Cache, CacheBuilder};
-use reqwest::Url;
-use serde::de::DeserializeOwned;
+use reqwest::{
+    Method, Url,
+    header::{HeaderMap, HeaderValue},
+};
+use serde::{Serialize, de::DeserializeOwned};
+#[cfg(feature = "tracing")]
+use tracing::{Span, field::Empty, instrument};
 
+#[cfg(feature = "metrics")]
+use crate::metrics;
 use crate::{
     attr::{Attribute, AttributeId},
-    error::ApiResult,
-    film::{Film, FilmId},
+    cassette::{ACCESS_TOKEN_HEADER, Cassette, CassetteMode},
+    error::{ApiResult, LibVeeziError},
+    feed::Feed,
+    film::{Film, FilmId, PlayingStatus},
+    middleware::Middleware,
     package::{FilmPackage, FilmPackageId},
+    region::{BaseUrlError, VeeziRegion, normalize_base_url},
     screen::{Screen, ScreenId},
     session::{Session, SessionId, SessionList},
     site::Site,
+    transport::{ReqwestTransport, Transport, TransportRequest},
 };
 
 /// A structure for building a libveezi [`Client`] with various options
 pub struct ClientBuilder {
-    /// The underlying HTTP client
-    http: reqwest::Client,
+    /// The transport used to make HTTP requests
+    transport: Arc<dyn Transport>,
     /// The base URL for the Veezi API
     base_url: String,
     /// The access token for authenticating with the Veezi API
@@ -38,14 +65,33 @@ pub struct ClientBuilder {
     pub attribute_cache: Option<(Duration, u64)>,
     /// Enable caching for the current [`Site`] with the given TTL
     pub site_cache: Option<Duration>,
+    /// Record requests to or replay responses from a [`Cassette`]
+    pub cassette: Option<CassetteMode>,
+    /// The [`Middleware`] called around every request, in order
+    pub middleware: Vec<Arc<dyn Middleware>>,
 }
 impl ClientBuilder {
     /// Create a new [`ClientBuilder`] with the given base URL, access token,
     /// and underlying HTTP client
     #[must_use]
     pub fn new_with_http(base_url: &str, token: String, http_client: reqwest::Client) -> Self {
+        Self::new_with_transport(
+            base_url,
+            token,
+            Arc::new(ReqwestTransport::from(http_client)),
+        )
+    }
+
+    /// Create a new [`ClientBuilder`] with the given base URL, access token,
+    /// and [`Transport`]
+    #[must_use]
+    pub fn new_with_transport(
+        base_url: &str,
+        token: String,
+        transport: Arc<dyn Transport>,
+    ) -> Self {
         Self {
-            http: http_client,
+            transport,
             base_url: base_url.to_string(),
             token,
             session_cache: None,
@@ -54,24 +100,76 @@ impl ClientBuilder {
             screen_cache: None,
             attribute_cache: None,
             site_cache: None,
+            cassette: None,
+            middleware: Vec::new(),
         }
     }
 
     /// Create a new [`ClientBuilder`] with the given base URL and access token
+    ///
+    /// The base URL is validated and normalized by [`normalize_base_url`] when
+    /// the [`Client`] is built.
     #[must_use]
     pub fn new(base_url: &str, token: String) -> Self {
         Self::new_with_http(base_url, token, reqwest::Client::new())
     }
 
+    /// Create a new [`ClientBuilder`] for the given [`VeeziRegion`] and access
+    /// token
+    #[must_use]
+    pub fn for_region(region: &VeeziRegion, token: String) -> Self {
+        Self::new(region.base_url().as_str(), token)
+    }
+
+    /// Use the base URL of the given [`VeeziRegion`]
+    #[must_use]
+    pub fn with_region(mut self, region: &VeeziRegion) -> Self {
+        self.base_url = region.base_url().to_string();
+        self
+    }
+
     /// Build the [`Client`] from this builder
     ///
     /// # Errors
     ///
-    /// This function will return an error if the URL provided is invalid.
-    pub fn build(self) -> Result<Client, url::ParseError> {
+    /// This function will return an error if the base URL is not a valid
+    /// `http` or `https` URL.
+    pub fn build(self) -> Result<Client, BaseUrlError> {
         Client::from_builder(self)
     }
 
+    /// Make HTTP requests with the given [`Transport`]
+    #[must_use]
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Add a [`Middleware`] to the end of the chain called around every
+    /// request
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Record every request and response to a [`Cassette`] file, with the
+    /// access token redacted
+    ///
+    /// The file is written when the [`Client`] is dropped.
+    #[must_use]
+    pub fn with_cassette_recording(mut self, path: PathBuf) -> Self {
+        self.cassette = Some(CassetteMode::Record(path));
+        self
+    }
+
+    /// Serve every request from a [`Cassette`] instead of the network
+    #[must_use]
+    pub fn with_cassette_replay(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(CassetteMode::Replay(cassette));
+        self
+    }
+
     /// Enable caching for [`Session`]s with the given TTL and max capacity
     #[must_use]
     pub const fn with_session_cache(mut self, ttl: Duration, max: u64) -> Self {
@@ -126,11 +224,156 @@ impl ClientBuilder {
     }
 }
 
+/// The hit and miss counts of one of a [`Client`]'s caches
+#[derive(Serialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct CacheStats {
+    /// Whether the cache is enabled
+    pub enabled: bool,
+    /// The number of requests served from the cache
+    pub hits: u64,
+    /// The number of requests that had to be fetched from the API
+    pub misses: u64,
+    /// The approximate number of entries in the cache
+    pub entries: u64,
+}
+
+/// The [`CacheStats`] of each of a [`Client`]'s caches
+#[derive(Serialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct ClientCacheStats {
+    /// The cache for individual [`Session`]s
+    pub sessions: CacheStats,
+    /// The cache for the full [`SessionList`]
+    pub session_list: CacheStats,
+    /// The cache for the full web [`SessionList`]
+    pub web_session_list: CacheStats,
+    /// The cache for individual [`Film`]s
+    pub films: CacheStats,
+    /// The cache for the full list of [`Film`]s
+    pub film_list: CacheStats,
+    /// The cache for individual [`FilmPackage`]s
+    pub film_packages: CacheStats,
+    /// The cache for the full list of [`FilmPackage`]s
+    pub film_package_list: CacheStats,
+    /// The cache for individual [`Screen`]s
+    pub screens: CacheStats,
+    /// The cache for the full list of [`Screen`]s
+    pub screen_list: CacheStats,
+    /// The cache for individual [`Attribute`]s
+    pub attributes: CacheStats,
+    /// The cache for the full list of [`Attribute`]s
+    pub attribute_list: CacheStats,
+    /// The cache for the current [`Site`]
+    pub site: CacheStats,
+}
+
+/// The hit and miss counters of a single cache
+#[derive(Debug)]
+struct CacheCounters {
+    /// The name of the cache, as in [`ClientCacheStats`]
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    name: &'static str,
+    /// The number of cache hits
+    hits: AtomicU64,
+    /// The number of cache misses
+    misses: AtomicU64,
+}
+impl CacheCounters {
+    /// Create new counters for the named cache
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a cache hit
+    fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::record_cache_hit(self.name);
+        #[cfg(feature = "tracing")]
+        Span::current().record("cache", "hit");
+    }
+
+    /// Record a cache miss
+    fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::record_cache_miss(self.name);
+        #[cfg(feature = "tracing")]
+        Span::current().record("cache", "miss");
+    }
+
+    /// Get the [`CacheStats`] of these counters and their cache
+    fn stats<K, V>(&self, cache: Option<&Cache<K, V>>) -> CacheStats
+    where
+        K: Hash + Eq + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        CacheStats {
+            enabled: cache.is_some(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: cache.map_or(0, Cache::entry_count),
+        }
+    }
+}
+
+/// Internal helper to apply any pending inserts and evictions of a cache, so
+/// its entry count is up to date
+#[cfg(feature = "metrics")]
+async fn run_pending_tasks<K, V>(cache: Option<&Cache<K, V>>)
+where
+    K: Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    if let Some(cache) = cache {
+        cache.run_pending_tasks().await;
+    }
+}
+
+/// The [`CacheCounters`] of each of a [`Client`]'s caches
+#[derive(Debug)]
+#[allow(clippy::missing_docs_in_private_items)]
+struct CacheCounterSet {
+    sessions: CacheCounters,
+    session_list: CacheCounters,
+    web_session_list: CacheCounters,
+    films: CacheCounters,
+    film_list: CacheCounters,
+    film_packages: CacheCounters,
+    film_package_list: CacheCounters,
+    screens: CacheCounters,
+    screen_list: CacheCounters,
+    attributes: CacheCounters,
+    attribute_list: CacheCounters,
+    site: CacheCounters,
+}
+impl Default for CacheCounterSet {
+    fn default() -> Self {
+        Self {
+            sessions: CacheCounters::new("sessions"),
+            session_list: CacheCounters::new("session_list"),
+            web_session_list: CacheCounters::new("web_session_list"),
+            films: CacheCounters::new("films"),
+            film_list: CacheCounters::new("film_list"),
+            film_packages: CacheCounters::new("film_packages"),
+            film_package_list: CacheCounters::new("film_package_list"),
+            screens: CacheCounters::new("screens"),
+            screen_list: CacheCounters::new("screen_list"),
+            attributes: CacheCounters::new("attributes"),
+            attribute_list: CacheCounters::new("attribute_list"),
+            site: CacheCounters::new("site"),
+        }
+    }
+}
+
 #[allow(clippy::doc_markdown)]
 /// The main client for interacting with the Veezi API
 pub struct Client {
-    /// The underlying HTTP client
-    http: reqwest::Client,
+    /// The transport used to make HTTP requests
+    transport: Arc<dyn Transport>,
     /// The base URL for the Veezi API
     base: Url,
     /// The access token for authenticating with the Veezi API
@@ -162,6 +405,10 @@ pub struct Client {
     attribute_list_cache: Option<Cache<(), Vec<Attribute>>>,
     /// The MiniLFU cache for the current [`Site`]
     site_cache: Option<Cache<(), Site>>,
+    /// The hit and miss counters of each cache
+    cache_counters: CacheCounterSet,
+    /// The middleware called around every request, in order
+    middleware: Vec<Arc<dyn Middleware>>,
 }
 impl Client {
     /// Create a new Veezi API client from a given base URL, access token, and
@@ -169,10 +416,11 @@ impl Client {
     ///
     /// # Errors
     ///
-    /// This function will return an error if the URL provided is invalid.
-    pub fn from_builder(builder: ClientBuilder) -> Result<Self, url::ParseError> {
+    /// This function will return an error if the base URL is not a valid
+    /// `http` or `https` URL.
+    pub fn from_builder(builder: ClientBuilder) -> Result<Self, BaseUrlError> {
         let ClientBuilder {
-            http: http_client,
+            transport,
             base_url,
             token,
             session_cache,
@@ -181,12 +429,17 @@ impl Client {
             screen_cache,
             attribute_cache,
             site_cache,
+            cassette,
+            middleware,
         } = builder;
 
-        debug!("Spawning new libveezi Client for API base: {base_url}");
-        let base = Url::parse(&base_url)?;
+        let base = normalize_base_url(&base_url)?;
+        debug!("Spawning new libveezi Client for API base: {base}");
         Ok(Self {
-            http: http_client,
+            transport: match cassette {
+                Some(mode) => mode.wrap(transport),
+                None => transport,
+            },
             base,
             token,
 
@@ -213,6 +466,8 @@ impl Client {
             attribute_list_cache: attribute_cache
                 .map(|(ttl, _)| CacheBuilder::new(1).time_to_live(ttl).build()),
             site_cache: site_cache.map(|ttl| CacheBuilder::new(1).time_to_live(ttl).build()),
+            cache_counters: CacheCounterSet::default(),
+            middleware,
         })
     }
 
@@ -222,6 +477,20 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(
+            name = "request",
+            skip_all,
+            err,
+            fields(
+                endpoint = %endpoint,
+                status = Empty,
+                size = Empty,
+                latency_ms = Empty
+            )
+        )
+    )]
     async fn get_json<T>(&self, endpoint: &str) -> ApiResult<T>
     where
         T: DeserializeOwned + Debug,
@@ -230,15 +499,68 @@ impl Client {
 
         debug!(target: "libveezi-http", "GET {url}");
 
-        let resp = self
-            .http
-            .get(url)
-            .header("VeeziAccessToken", &self.token)
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<T>()
-            .await?;
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCESS_TOKEN_HEADER,
+            HeaderValue::from_str(&self.token)
+                .map_err(|err| LibVeeziError::Transport(err.into()))?,
+        );
+        let mut request = TransportRequest {
+            method: Method::GET,
+            url,
+            headers,
+        };
+        for middleware in &self.middleware {
+            middleware.on_request(&mut request);
+        }
+
+        let started = Instant::now();
+        let response = match self.transport.send(request.clone()).await {
+            Ok(response) => response,
+            Err(err) => {
+                #[cfg(feature = "metrics")]
+                metrics::record_error(endpoint, started.elapsed());
+                for middleware in self.middleware.iter().rev() {
+                    middleware.on_error(&request, &err, started.elapsed());
+                }
+                return Err(err);
+            }
+        };
+        let elapsed = started.elapsed();
+        #[cfg(feature = "tracing")]
+        {
+            let span = Span::current();
+            span.record("status", response.status.as_u16());
+            span.record("size", response.body.len());
+            span.record("latency_ms", elapsed.as_millis());
+        }
+        #[cfg(feature = "metrics")]
+        metrics::record_response(
+            endpoint,
+            response.status,
+            elapsed,
+            u64::try_from(response.body.len()).unwrap_or(u64::MAX),
+        );
+        for middleware in self.middleware.iter().rev() {
+            middleware.on_response(&request, &response, elapsed);
+        }
+
+        let status = response.status;
+        if status.is_client_error() || status.is_server_error() {
+            return Err(LibVeeziError::Status {
+                status,
+                url: request.url,
+            });
+        }
+        let resp = match serde_json::from_slice::<T>(&response.body) {
+            Ok(resp) => resp,
+            Err(err) => {
+                for middleware in self.middleware.iter().rev() {
+                    middleware.on_decode_error(&request, &response, &err);
+                }
+                return Err(err.into());
+            }
+        };
 
         debug!(target: "libveezi-http", "OK: {resp:?}");
 
@@ -256,11 +578,81 @@ impl Client {
         self.invalidate_cached_site();
     }
 
+    /// Get the hit and miss counts and the approximate number of entries of
+    /// each cache
+    #[must_use]
+    pub fn cache_stats(&self) -> ClientCacheStats {
+        let counters = &self.cache_counters;
+        ClientCacheStats {
+            sessions: counters.sessions.stats(self.session_cache.as_ref()),
+            session_list: counters
+                .session_list
+                .stats(self.session_list_cache.as_ref()),
+            web_session_list: counters
+                .web_session_list
+                .stats(self.web_session_list_cache.as_ref()),
+            films: counters.films.stats(self.film_cache.as_ref()),
+            film_list: counters.film_list.stats(self.film_list_cache.as_ref()),
+            film_packages: counters
+                .film_packages
+                .stats(self.film_package_cache.as_ref()),
+            film_package_list: counters
+                .film_package_list
+                .stats(self.film_package_list_cache.as_ref()),
+            screens: counters.screens.stats(self.screen_cache.as_ref()),
+            screen_list: counters.screen_list.stats(self.screen_list_cache.as_ref()),
+            attributes: counters.attributes.stats(self.attribute_cache.as_ref()),
+            attribute_list: counters
+                .attribute_list
+                .stats(self.attribute_list_cache.as_ref()),
+            site: counters.site.stats(self.site_cache.as_ref()),
+        }
+    }
+
+    /// Record the approximate number of entries of each cache as a gauge with
+    /// the `metrics` feature
+    ///
+    /// Cache entry counts are not tracked as requests are made, so this should
+    /// be called before each scrape of the installed recorder.
+    #[cfg(feature = "metrics")]
+    pub async fn record_cache_metrics(&self) {
+        run_pending_tasks(self.session_cache.as_ref()).await;
+        run_pending_tasks(self.session_list_cache.as_ref()).await;
+        run_pending_tasks(self.web_session_list_cache.as_ref()).await;
+        run_pending_tasks(self.film_cache.as_ref()).await;
+        run_pending_tasks(self.film_list_cache.as_ref()).await;
+        run_pending_tasks(self.film_package_cache.as_ref()).await;
+        run_pending_tasks(self.film_package_list_cache.as_ref()).await;
+        run_pending_tasks(self.screen_cache.as_ref()).await;
+        run_pending_tasks(self.screen_list_cache.as_ref()).await;
+        run_pending_tasks(self.attribute_cache.as_ref()).await;
+        run_pending_tasks(self.attribute_list_cache.as_ref()).await;
+        run_pending_tasks(self.site_cache.as_ref()).await;
+        let stats = self.cache_stats();
+        for (name, cache) in [
+            ("sessions", stats.sessions),
+            ("session_list", stats.session_list),
+            ("web_session_list", stats.web_session_list),
+            ("films", stats.films),
+            ("film_list", stats.film_list),
+            ("film_packages", stats.film_packages),
+            ("film_package_list", stats.film_package_list),
+            ("screens", stats.screens),
+            ("screen_list", stats.screen_list),
+            ("attributes", stats.attributes),
+            ("attribute_list", stats.attribute_list),
+            ("site", stats.site),
+        ] {
+            metrics::record_cache_entries(name, cache.entries);
+        }
+    }
+
     /// Get a list of all future [Session]s.
     ///
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(cache = Empty)))]
     pub async fn list_sessions(&self) -> ApiResult<SessionList> {
         let fetch_raw = async {
             Ok(SessionList::from(
@@ -276,10 +668,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&()).await {
             debug!("SessionList cache hit");
+            self.cache_counters.session_list.hit();
             return Ok(cached);
         }
 
         debug!("SessionList cache miss, fetching from API");
+        self.cache_counters.session_list.miss();
         let sessions = fetch_raw.await?;
         cache.insert((), sessions.clone()).await;
         if let Some(session_cache) = &self.session_cache {
@@ -309,6 +703,13 @@ impl Client {
             cache.invalidate_all();
         }
     }
+    /// Internal helper to invalidate only the cached full list of [`Session`]s,
+    /// leaving cached individual items untouched
+    pub(crate) fn invalidate_cached_session_list(&self) {
+        if let Some(cache) = &self.session_list_cache {
+            cache.invalidate_all();
+        }
+    }
 
     /// Get a list of all future [`Session`]s that should be available for
     /// online sales.
@@ -322,6 +723,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(cache = Empty)))]
     pub async fn list_web_sessions(&self) -> ApiResult<SessionList> {
         let fetch_raw = async {
             Ok(SessionList::from(
@@ -337,10 +739,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&()).await {
             debug!("Web SessionList cache hit");
+            self.cache_counters.web_session_list.hit();
             return Ok(cached);
         }
 
         debug!("Web SessionList cache miss, fetching from API");
+        self.cache_counters.web_session_list.miss();
         let sessions = fetch_raw.await?;
         cache.insert((), sessions.clone()).await;
         if let Some(session_cache) = &self.session_cache {
@@ -364,6 +768,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, id), fields(id = %id, cache = Empty)))]
     pub async fn get_session(&self, id: SessionId) -> ApiResult<Session> {
         let fetch_raw = async { self.get_json::<Session>(&format!("v1/session/{id}")).await };
 
@@ -375,10 +780,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&id).await {
             debug!("Session cache hit for ID {id}");
+            self.cache_counters.sessions.hit();
             return Ok(cached);
         }
 
         debug!("Session cache miss for ID {id}, fetching from API");
+        self.cache_counters.sessions.miss();
         let session = fetch_raw.await?;
         cache.insert(id, session.clone()).await;
         Ok(session)
@@ -389,6 +796,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(cache = Empty)))]
     pub async fn list_films(&self) -> ApiResult<Vec<Film>> {
         // v4/film
 
@@ -402,10 +810,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&()).await {
             debug!("Film list cache hit");
+            self.cache_counters.film_list.hit();
             return Ok(cached);
         }
 
         debug!("Film list cache miss, fetching from API");
+        self.cache_counters.film_list.miss();
         let films = fetch_raw.await?;
         cache.insert((), films.clone()).await;
         if let Some(film_cache) = &self.film_cache {
@@ -424,6 +834,13 @@ impl Client {
             cache.invalidate_all();
         }
     }
+    /// Internal helper to invalidate only the cached full list of [`Film`]s,
+    /// leaving cached individual items untouched
+    pub(crate) fn invalidate_cached_film_list(&self) {
+        if let Some(cache) = &self.film_list_cache {
+            cache.invalidate_all();
+        }
+    }
     /// Invalidate a cached [`Film`] by its ID
     ///
     /// As a side effect, this also invalidates the full film list cache
@@ -441,6 +858,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, id), fields(id = %id, cache = Empty)))]
     pub async fn get_film(&self, id: &FilmId) -> ApiResult<Film> {
         let fetch_raw = async {
             self.get_json::<Film>(&format!("v4/film/{}", id.as_str()))
@@ -455,10 +873,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(id).await {
             debug!("Film cache hit for ID {id}");
+            self.cache_counters.films.hit();
             return Ok(cached);
         }
 
         debug!("Film cache miss for ID {id}, fetching from API");
+        self.cache_counters.films.miss();
         let film = fetch_raw.await?;
         cache.insert(id.clone(), film.clone()).await;
         Ok(film)
@@ -471,6 +891,7 @@ impl Client {
     ///
     /// This function will return an error if the API request fails, or None if
     /// no film with the given title is found.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn get_film_by_title(&self, title: &str) -> ApiResult<Option<Film>> {
         let films = self.list_films().await?;
         Ok(films.into_iter().find(|film| film.title == title))
@@ -484,6 +905,7 @@ impl Client {
     ///
     /// This function will return an error if the API request fails, or None if
     /// no film with the given short name is found.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn get_film_by_short_name(&self, short_name: &str) -> ApiResult<Option<Film>> {
         let films = self.list_films().await?;
         Ok(films.into_iter().find(|film| film.short_name == short_name))
@@ -497,6 +919,7 @@ impl Client {
     ///
     /// This function will return an error if the API request fails, or None if
     /// no film with the given signage text is found.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn get_film_by_signage_text(&self, signage_text: &str) -> ApiResult<Option<Film>> {
         let films = self.list_films().await?;
         Ok(films
@@ -509,6 +932,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn list_films_by_genre(&self, genre: &str) -> ApiResult<Vec<Film>> {
         let films = self.list_films().await?;
         Ok(films
@@ -522,6 +946,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn list_films_by_distributor(&self, distributor: &str) -> ApiResult<Vec<Film>> {
         let films = self.list_films().await?;
         Ok(films
@@ -536,6 +961,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn list_films_with_sessions_in_time_range(
         &self,
         start: NaiveDateTime,
@@ -555,6 +981,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn list_films_with_sessions_in_date_range(
         &self,
         start: NaiveDate,
@@ -568,11 +995,178 @@ impl Client {
             .await
     }
 
+    /// Classify every [`Film`] as now playing, on advance sale or coming soon,
+    /// using a window of `window` from now to decide what is now playing.
+    ///
+    /// Films that have opened but have no upcoming sessions are omitted. See
+    /// [`SessionList::classify_film`] for the exact rules. Session times are
+    /// local to the site, so "now" is the current local time at the
+    /// [`Site`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn classify_films(&self, window: TimeDelta) -> ApiResult<Vec<(Film, PlayingStatus)>> {
+        let now = self.get_site().await?.now();
+        let sessions = self.list_sessions().await?;
+        Ok(self
+            .list_films()
+            .await?
+            .into_iter()
+            .filter_map(|film| {
+                sessions
+                    .classify_film(&film, now, window)
+                    .map(|status| (film, status))
+            })
+            .collect())
+    }
+
+    /// Internal helper to list the [`Film`]s classified with a given
+    /// [`PlayingStatus`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    async fn list_films_with_status(
+        &self,
+        window: TimeDelta,
+        status: PlayingStatus,
+    ) -> ApiResult<Vec<Film>> {
+        Ok(self
+            .classify_films(window)
+            .await?
+            .into_iter()
+            .filter(|(_, film_status)| *film_status == status)
+            .map(|(film, _)| film)
+            .collect())
+    }
+
+    /// Get the [`Film`]s that are now playing, i.e. have opened and have a
+    /// session within `window` from now.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn list_now_playing_films(&self, window: TimeDelta) -> ApiResult<Vec<Film>> {
+        self.list_films_with_status(window, PlayingStatus::NowPlaying)
+            .await
+    }
+
+    /// Get the [`Film`]s that are coming soon, i.e. open in the future without
+    /// sessions on sale, or whose first session is beyond `window` from now.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn list_coming_soon_films(&self, window: TimeDelta) -> ApiResult<Vec<Film>> {
+        self.list_films_with_status(window, PlayingStatus::ComingSoon)
+            .await
+    }
+
+    /// Get the [`Film`]s on advance sale, i.e. open in the future but with
+    /// sessions already on sale.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn list_advance_sales_films(&self) -> ApiResult<Vec<Film>> {
+        // The window only distinguishes films that have already opened
+        self.list_films_with_status(TimeDelta::zero(), PlayingStatus::AdvanceSales)
+            .await
+    }
+
+    /// Get the next [`Session`] of a [`Film`] that can be booked online, if
+    /// any, as of the current local time at the [`Site`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, film_id), fields(film_id = %film_id)))]
+    pub async fn get_next_session_for_film(&self, film_id: &FilmId) -> ApiResult<Option<Session>> {
+        let now = self.get_site().await?.now();
+        Ok(self
+            .list_sessions()
+            .await?
+            .next_session_for_film(film_id, now)
+            .cloned())
+    }
+
+    /// Get up to `count` of the next [`Session`]s on a [`Screen`] that can be
+    /// booked online, as of the current local time at the [`Site`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any API request fails.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip(self, screen_id), fields(screen_id = %screen_id))
+    )]
+    pub async fn list_next_sessions_on_screen(
+        &self,
+        screen_id: ScreenId,
+        count: usize,
+    ) -> ApiResult<Vec<Session>> {
+        let now = self.get_site().await?.now();
+        Ok(self
+            .list_sessions()
+            .await?
+            .next_sessions_on_screen(screen_id, now, count)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    /// Build a [`Feed`] of the [`Film`]s that are now playing, on advance sale
+    /// or coming soon, each listing up to `showtimes` of its next sessions that
+    /// can be booked online, as of the current local time at the [`Site`].
+    ///
+    /// See [`Feed::films`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn film_feed(
+        &self,
+        link: &str,
+        window: TimeDelta,
+        showtimes: usize,
+    ) -> ApiResult<Feed> {
+        let site = self.get_site().await?;
+        let now = site.now();
+        let films = self.list_films().await?;
+        let sessions = self.list_web_sessions().await?;
+        Ok(Feed::films(
+            &site, link, &films, &sessions, now, window, showtimes,
+        ))
+    }
+
+    /// Build a [`Feed`] of the web [`Session`]s that are not in `previous`,
+    /// e.g. the [`SessionList`] the last feed was generated from.
+    ///
+    /// See [`Feed::new_sessions`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, previous)))]
+    pub async fn new_session_feed(&self, link: &str, previous: &SessionList) -> ApiResult<Feed> {
+        let site = self.get_site().await?;
+        let films = self.list_films().await?;
+        let sessions = self.list_web_sessions().await?;
+        Ok(Feed::new_sessions(&site, link, &films, previous, &sessions))
+    }
+
     /// Get a list of all [`FilmPackage`]s in the Veezi system.
     ///
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(cache = Empty)))]
     pub async fn list_film_packages(&self) -> ApiResult<Vec<FilmPackage>> {
         let fetch_raw = async { self.get_json::<Vec<FilmPackage>>("v1/filmpackage").await };
 
@@ -584,10 +1178,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&()).await {
             debug!("FilmPackage list cache hit");
+            self.cache_counters.film_package_list.hit();
             return Ok(cached);
         }
 
         debug!("FilmPackage list cache miss, fetching from API");
+        self.cache_counters.film_package_list.miss();
         let packages = fetch_raw.await?;
         cache.insert((), packages.clone()).await;
         if let Some(package_cache) = &self.film_package_cache {
@@ -606,6 +1202,13 @@ impl Client {
             cache.invalidate_all();
         }
     }
+    /// Internal helper to invalidate only the cached full list of
+    /// [`FilmPackage`]s, leaving cached individual items untouched
+    pub(crate) fn invalidate_cached_film_package_list(&self) {
+        if let Some(cache) = &self.film_package_list_cache {
+            cache.invalidate_all();
+        }
+    }
     /// Invalidate a cached [`FilmPackage`] by its ID
     ///
     /// As a side effect, this also invalidates the full film package list cache
@@ -626,6 +1229,7 @@ impl Client {
     ///
     /// This function will return an error if the API request fails, or None if
     /// no package with the given title is found.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn get_film_package_by_title(&self, title: &str) -> ApiResult<Option<FilmPackage>> {
         let packages = self.list_film_packages().await?;
         Ok(packages.into_iter().find(|package| package.title == title))
@@ -636,6 +1240,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, film_id), fields(film_id = %film_id)))]
     pub async fn list_film_packages_by_film_id(
         &self,
         film_id: &FilmId,
@@ -652,6 +1257,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, id), fields(id = %id, cache = Empty)))]
     pub async fn get_film_package(&self, id: FilmPackageId) -> ApiResult<FilmPackage> {
         let fetch_raw = async {
             self.get_json::<FilmPackage>(&format!("v1/filmpackage/{id}"))
@@ -666,10 +1272,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&id).await {
             debug!("FilmPackage cache hit for ID {id}");
+            self.cache_counters.film_packages.hit();
             return Ok(cached);
         }
 
         debug!("FilmPackage cache miss for ID {id}, fetching from API");
+        self.cache_counters.film_packages.miss();
         let package = fetch_raw.await?;
         cache.insert(id, package.clone()).await;
         Ok(package)
@@ -680,6 +1288,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(cache = Empty)))]
     pub async fn list_screens(&self) -> ApiResult<Vec<Screen>> {
         let fetch_raw = async { self.get_json::<Vec<Screen>>("v1/screen").await };
 
@@ -691,10 +1300,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&()).await {
             debug!("Screen list cache hit");
+            self.cache_counters.screen_list.hit();
             return Ok(cached);
         }
 
         debug!("Screen list cache miss, fetching from API");
+        self.cache_counters.screen_list.miss();
         let screens = fetch_raw.await?;
         cache.insert((), screens.clone()).await;
         if let Some(screen_cache) = &self.screen_cache {
@@ -730,6 +1341,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, id), fields(id = %id, cache = Empty)))]
     pub async fn get_screen(&self, id: ScreenId) -> ApiResult<Screen> {
         let fetch_raw = async { self.get_json::<Screen>(&format!("v1/screen/{id}")).await };
 
@@ -741,10 +1353,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&id).await {
             debug!("Screen cache hit for ID {id}");
+            self.cache_counters.screens.hit();
             return Ok(cached);
         }
 
         debug!("Screen cache miss for ID {id}, fetching from API");
+        self.cache_counters.screens.miss();
         let screen = fetch_raw.await?;
         cache.insert(id, screen.clone()).await;
         Ok(screen)
@@ -758,6 +1372,7 @@ impl Client {
     ///
     /// This function will return an error if the API request fails, or None if
     /// no screen with the given screen number is found.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn get_screen_by_number(&self, screen_number: String) -> ApiResult<Option<Screen>> {
         let screens = self.list_screens().await?;
         Ok(screens
@@ -770,6 +1385,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(cache = Empty)))]
     pub async fn get_site(&self) -> ApiResult<Site> {
         let fetch_raw = async { self.get_json::<Site>("v1/site").await };
 
@@ -781,10 +1397,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&()).await {
             debug!("Site cache hit");
+            self.cache_counters.site.hit();
             return Ok(cached);
         }
 
         debug!("Site cache miss, fetching from API");
+        self.cache_counters.site.miss();
         let site = fetch_raw.await?;
         cache.insert((), site.clone()).await;
         Ok(site)
@@ -801,6 +1419,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(cache = Empty)))]
     pub async fn list_attributes(&self) -> ApiResult<Vec<Attribute>> {
         let fetch_raw = async { self.get_json::<Vec<Attribute>>("v1/attribute").await };
 
@@ -812,10 +1431,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(&()).await {
             debug!("Attribute list cache hit");
+            self.cache_counters.attribute_list.hit();
             return Ok(cached);
         }
 
         debug!("Attribute list cache miss, fetching from API");
+        self.cache_counters.attribute_list.miss();
         let attributes = fetch_raw.await?;
         cache.insert((), attributes.clone()).await;
         if let Some(attribute_cache) = &self.attribute_cache {
@@ -836,6 +1457,13 @@ impl Client {
             cache.invalidate_all();
         }
     }
+    /// Internal helper to invalidate only the cached full list of
+    /// [`Attribute`]s, leaving cached individual items untouched
+    pub(crate) fn invalidate_cached_attribute_list(&self) {
+        if let Some(cache) = &self.attribute_list_cache {
+            cache.invalidate_all();
+        }
+    }
     /// Invalidate a cached [`Attribute`] by its ID
     ///
     /// As a side effect, this also invalidates the full attribute list cache
@@ -853,6 +1481,7 @@ impl Client {
     /// # Errors
     ///
     /// This function will return an error if the API request fails.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, id), fields(id = %id, cache = Empty)))]
     pub async fn get_attribute(&self, id: &AttributeId) -> ApiResult<Attribute> {
         let fetch_raw = async {
             self.get_json::<Attribute>(&format!("v1/attribute/{}", id.as_str()))
@@ -867,10 +1496,12 @@ impl Client {
         // Try to get from cache
         if let Some(cached) = cache.get(id).await {
             debug!("Attribute cache hit for ID {id}");
+            self.cache_counters.attributes.hit();
             return Ok(cached);
         }
 
         debug!("Attribute cache miss for ID {id}, fetching from API");
+        self.cache_counters.attributes.miss();
         let attribute = fetch_raw.await?;
         cache.insert(id.clone(), attribute.clone()).await;
         Ok(attribute)
@@ -884,6 +1515,7 @@ impl Client {
     ///
     /// This function will return an error if the API request fails, or None if
     /// no attribute with the given short name is found.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn get_attribute_by_short_name(
         &self,
         short_name: &str,
@@ -902,6 +1534,7 @@ impl Client {
     ///
     /// This function will return an error if the API request fails, or None if
     /// no attribute with the given description is found.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn get_attribute_by_description(
         &self,
         description: &str,
@@ -0,0 +1,742 @@
+//! CSV export of sessions, films, film packages, screens and attributes for
+//! spreadsheets
+//!
+//! Each exportable type has a column enum ([`SessionColumn`], [`FilmColumn`],
+//! [`PackageFilmColumn`], [`ScreenColumn`] and [`AttributeColumn`]) whose
+//! `ALL` constant lists
+//! every column in API order. Pass any subset, in any order, to the matching
+//! `write_*` function to choose which columns are exported. Enums are
+//! flattened to their display names and [`crate::session::SalesVia`] becomes a
+//! comma separated channel list.
+//!
+//! Timestamps are always written as the site's local time in the
+//! `YYYY-MM-DD HH:MM:SS` form spreadsheets parse as dates. When a [`Site`] is
+//! given, [`SessionColumn::UtcOffset`] holds the site's UTC offset at the
+//! start of each session. Fields starting with `=`, `+`, `-`, `@`, a tab or a
+//! carriage return are prefixed with `'`, so that free text such as film
+//! titles cannot be interpreted as a spreadsheet formula.
+
+use std::io::{self, Write};
+
+use chrono::NaiveDateTime;
+
+use crate::{
+    attr::Attribute,
+    film::Film,
+    package::{FilmPackage, PackageFilm},
+    screen::Screen,
+    session::{Session, SessionList},
+    site::Site,
+};
+
+/// A column that can be exported to CSV for rows of type `T`
+pub trait CsvColumn<T>: Copy {
+    /// The header of this column
+    fn header(self) -> &'static str;
+
+    /// The value of this column for the given row
+    ///
+    /// The [`Site`], if given, is used by columns that depend on its time
+    /// zone, such as [`SessionColumn::UtcOffset`].
+    fn value(self, row: &T, site: Option<&Site>) -> String;
+}
+
+/// Internal helper to format a local site time in a form spreadsheets parse
+fn format_time(time: NaiveDateTime) -> String {
+    time.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Internal helper to format the UTC offset of a site at a local site time
+/// (e.g., `UTC-07:00`), or an empty string if the site's time zone is unknown
+fn format_offset(time: NaiveDateTime, site: Option<&Site>) -> String {
+    site.and_then(|site| site.localize(time))
+        .map(|time| time.format("UTC%:z").to_string())
+        .unwrap_or_default()
+}
+
+/// Internal helper to format an optional value as an empty string if missing
+fn format_optional(value: Option<&String>) -> String {
+    value.cloned().unwrap_or_default()
+}
+
+/// Internal helper to prefix a field that a spreadsheet would interpret as a
+/// formula with `'`, so it is shown as text
+fn guard_formula(field: String) -> String {
+    if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{field}")
+    } else {
+        field
+    }
+}
+
+/// Internal helper to escape a single CSV field per RFC 4180
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) || field.trim() != field {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Internal helper to write a single CSV record
+fn write_record<W: Write>(out: &mut W, fields: impl IntoIterator<Item = String>) -> io::Result<()> {
+    let fields: Vec<String> = fields
+        .into_iter()
+        .map(|field| escape_field(&guard_formula(field)))
+        .collect();
+    write!(out, "{}\r\n", fields.join(","))
+}
+
+/// Write rows to CSV with the given columns, including a header record
+///
+/// # Errors
+///
+/// This function will return an error if writing to `out` fails.
+pub fn write_csv<'a, W, T, C>(
+    mut out: W,
+    rows: impl IntoIterator<Item = &'a T>,
+    columns: &[C],
+    site: Option<&Site>,
+) -> io::Result<()>
+where
+    W: Write,
+    T: 'a,
+    C: CsvColumn<T>,
+{
+    write_record(
+        &mut out,
+        columns.iter().map(|column| column.header().to_string()),
+    )?;
+    for row in rows {
+        write_record(
+            &mut out,
+            columns.iter().map(|column| column.value(row, site)),
+        )?;
+    }
+    out.flush()
+}
+
+/// The columns that can be exported for a [`Session`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SessionColumn {
+    /// [`Session::id`]
+    Id,
+    /// [`Session::film_id`]
+    FilmId,
+    /// [`Session::film_package_id`]
+    FilmPackageId,
+    /// [`Session::title`]
+    Title,
+    /// [`Session::screen_id`]
+    ScreenId,
+    /// [`Session::seating`]
+    Seating,
+    /// [`Session::are_complimentaries_allowed`]
+    AreComplimentariesAllowed,
+    /// [`Session::show_type`]
+    ShowType,
+    /// [`Session::sales_via`]
+    SalesVia,
+    /// [`Session::status`]
+    Status,
+    /// [`Session::pre_show_start_time`]
+    PreShowStartTime,
+    /// [`Session::sales_cut_off_time`]
+    SalesCutOffTime,
+    /// [`Session::feature_start_time`]
+    FeatureStartTime,
+    /// [`Session::feature_end_time`]
+    FeatureEndTime,
+    /// [`Session::cleanup_end_time`]
+    CleanupEndTime,
+    /// [`Session::tickets_sold_out`]
+    TicketsSoldOut,
+    /// [`Session::few_tickets_left`]
+    FewTicketsLeft,
+    /// [`Session::seats_available`]
+    SeatsAvailable,
+    /// [`Session::seats_held`]
+    SeatsHeld,
+    /// [`Session::seats_house`]
+    SeatsHouse,
+    /// [`Session::seats_sold`]
+    SeatsSold,
+    /// [`Session::film_format`]
+    FilmFormat,
+    /// [`Session::price_card_name`]
+    PriceCardName,
+    /// [`Session::attributes`]
+    Attributes,
+    /// [`Session::audio_language`]
+    AudioLanguage,
+    /// The UTC offset of the [`Site`] at [`Session::feature_start_time`],
+    /// which is empty unless a site with a known time zone is given
+    UtcOffset,
+}
+impl SessionColumn {
+    /// Every [`SessionColumn`], in API order, followed by
+    /// [`SessionColumn::UtcOffset`]
+    pub const ALL: &[Self] = &[
+        Self::Id,
+        Self::FilmId,
+        Self::FilmPackageId,
+        Self::Title,
+        Self::ScreenId,
+        Self::Seating,
+        Self::AreComplimentariesAllowed,
+        Self::ShowType,
+        Self::SalesVia,
+        Self::Status,
+        Self::PreShowStartTime,
+        Self::SalesCutOffTime,
+        Self::FeatureStartTime,
+        Self::FeatureEndTime,
+        Self::CleanupEndTime,
+        Self::TicketsSoldOut,
+        Self::FewTicketsLeft,
+        Self::SeatsAvailable,
+        Self::SeatsHeld,
+        Self::SeatsHouse,
+        Self::SeatsSold,
+        Self::FilmFormat,
+        Self::PriceCardName,
+        Self::Attributes,
+        Self::AudioLanguage,
+        Self::UtcOffset,
+    ];
+}
+impl CsvColumn<Session> for SessionColumn {
+    fn header(self) -> &'static str {
+        match self {
+            Self::Id => "Id",
+            Self::FilmId => "FilmId",
+            Self::FilmPackageId => "FilmPackageId",
+            Self::Title => "Title",
+            Self::ScreenId => "ScreenId",
+            Self::Seating => "Seating",
+            Self::AreComplimentariesAllowed => "AreComplimentariesAllowed",
+            Self::ShowType => "ShowType",
+            Self::SalesVia => "SalesVia",
+            Self::Status => "Status",
+            Self::PreShowStartTime => "PreShowStartTime",
+            Self::SalesCutOffTime => "SalesCutOffTime",
+            Self::FeatureStartTime => "FeatureStartTime",
+            Self::FeatureEndTime => "FeatureEndTime",
+            Self::CleanupEndTime => "CleanupEndTime",
+            Self::TicketsSoldOut => "TicketsSoldOut",
+            Self::FewTicketsLeft => "FewTicketsLeft",
+            Self::SeatsAvailable => "SeatsAvailable",
+            Self::SeatsHeld => "SeatsHeld",
+            Self::SeatsHouse => "SeatsHouse",
+            Self::SeatsSold => "SeatsSold",
+            Self::FilmFormat => "FilmFormat",
+            Self::PriceCardName => "PriceCardName",
+            Self::Attributes => "Attributes",
+            Self::AudioLanguage => "AudioLanguage",
+            Self::UtcOffset => "UtcOffset",
+        }
+    }
+
+    fn value(self, session: &Session, site: Option<&Site>) -> String {
+        match self {
+            Self::Id => session.id.to_string(),
+            Self::FilmId => session.film_id.to_string(),
+            Self::FilmPackageId => session
+                .film_package_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            Self::Title => session.title.clone(),
+            Self::ScreenId => session.screen_id.to_string(),
+            Self::Seating => session.seating.to_string(),
+            Self::AreComplimentariesAllowed => session.are_complimentaries_allowed.to_string(),
+            Self::ShowType => session.show_type.to_string(),
+            Self::SalesVia => session.sales_via.to_string(),
+            Self::Status => session.status.to_string(),
+            Self::PreShowStartTime => format_time(session.pre_show_start_time),
+            Self::SalesCutOffTime => format_time(session.sales_cut_off_time),
+            Self::FeatureStartTime => format_time(session.feature_start_time),
+            Self::FeatureEndTime => format_time(session.feature_end_time),
+            Self::CleanupEndTime => format_time(session.cleanup_end_time),
+            Self::TicketsSoldOut => session.tickets_sold_out.to_string(),
+            Self::FewTicketsLeft => session.few_tickets_left.to_string(),
+            Self::SeatsAvailable => session.seats_available.to_string(),
+            Self::SeatsHeld => session.seats_held.to_string(),
+            Self::SeatsHouse => session.seats_house.to_string(),
+            Self::SeatsSold => session.seats_sold.to_string(),
+            Self::FilmFormat => session.film_format.to_string(),
+            Self::PriceCardName => session.price_card_name.clone(),
+            Self::Attributes => session
+                .attributes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            Self::AudioLanguage => format_optional(session.audio_language.as_ref()),
+            Self::UtcOffset => format_offset(session.feature_start_time, site),
+        }
+    }
+}
+
+/// The columns that can be exported for a [`Film`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum FilmColumn {
+    /// [`Film::id`]
+    Id,
+    /// [`Film::title`]
+    Title,
+    /// [`Film::short_name`]
+    ShortName,
+    /// [`Film::synopsis`]
+    Synopsis,
+    /// [`Film::genre`]
+    Genre,
+    /// [`Film::signage_text`]
+    SignageText,
+    /// [`Film::distributor`]
+    Distributor,
+    /// [`Film::opening_date`]
+    OpeningDate,
+    /// [`Film::rating`]
+    Rating,
+    /// [`Film::status`]
+    Status,
+    /// [`Film::content`]
+    Content,
+    /// [`Film::duration`]
+    Duration,
+    /// [`Film::display_sequence`]
+    DisplaySequence,
+    /// [`Film::national_code`]
+    NationalCode,
+    /// [`Film::format`]
+    Format,
+    /// [`Film::is_restricted`]
+    IsRestricted,
+    /// [`Film::people`], formatted as `Role: First Last` separated by
+    /// semicolons
+    People,
+    /// [`Film::audio_language`]
+    AudioLanguage,
+    /// [`Film::government_film_title`]
+    GovernmentFilmTitle,
+    /// [`Film::film_poster_url`]
+    FilmPosterUrl,
+    /// [`Film::film_poster_thumbnail_url`]
+    FilmPosterThumbnailUrl,
+    /// [`Film::backdrop_image_url`]
+    BackdropImageUrl,
+    /// [`Film::film_trailer_url`]
+    FilmTrailerUrl,
+}
+impl FilmColumn {
+    /// Every [`FilmColumn`], in API order
+    pub const ALL: &[Self] = &[
+        Self::Id,
+        Self::Title,
+        Self::ShortName,
+        Self::Synopsis,
+        Self::Genre,
+        Self::SignageText,
+        Self::Distributor,
+        Self::OpeningDate,
+        Self::Rating,
+        Self::Status,
+        Self::Content,
+        Self::Duration,
+        Self::DisplaySequence,
+        Self::NationalCode,
+        Self::Format,
+        Self::IsRestricted,
+        Self::People,
+        Self::AudioLanguage,
+        Self::GovernmentFilmTitle,
+        Self::FilmPosterUrl,
+        Self::FilmPosterThumbnailUrl,
+        Self::BackdropImageUrl,
+        Self::FilmTrailerUrl,
+    ];
+}
+impl CsvColumn<Film> for FilmColumn {
+    fn header(self) -> &'static str {
+        match self {
+            Self::Id => "Id",
+            Self::Title => "Title",
+            Self::ShortName => "ShortName",
+            Self::Synopsis => "Synopsis",
+            Self::Genre => "Genre",
+            Self::SignageText => "SignageText",
+            Self::Distributor => "Distributor",
+            Self::OpeningDate => "OpeningDate",
+            Self::Rating => "Rating",
+            Self::Status => "Status",
+            Self::Content => "Content",
+            Self::Duration => "Duration",
+            Self::DisplaySequence => "DisplaySequence",
+            Self::NationalCode => "NationalCode",
+            Self::Format => "Format",
+            Self::IsRestricted => "IsRestricted",
+            Self::People => "People",
+            Self::AudioLanguage => "AudioLanguage",
+            Self::GovernmentFilmTitle => "GovernmentFilmTitle",
+            Self::FilmPosterUrl => "FilmPosterUrl",
+            Self::FilmPosterThumbnailUrl => "FilmPosterThumbnailUrl",
+            Self::BackdropImageUrl => "BackdropImageUrl",
+            Self::FilmTrailerUrl => "FilmTrailerUrl",
+        }
+    }
+
+    fn value(self, film: &Film, _site: Option<&Site>) -> String {
+        match self {
+            Self::Id => film.id.to_string(),
+            Self::Title => film.title.clone(),
+            Self::ShortName => film.short_name.clone(),
+            Self::Synopsis => format_optional(film.synopsis.as_ref()),
+            Self::Genre => film.genre.clone(),
+            Self::SignageText => film.signage_text.clone(),
+            Self::Distributor => film.distributor.clone(),
+            Self::OpeningDate => format_time(film.opening_date),
+            Self::Rating => format_optional(film.rating.as_ref()),
+            Self::Status => film.status.to_string(),
+            Self::Content => format_optional(film.content.as_ref()),
+            Self::Duration => film.duration.to_string(),
+            Self::DisplaySequence => film.display_sequence.to_string(),
+            Self::NationalCode => format_optional(film.national_code.as_ref()),
+            Self::Format => film.format.to_string(),
+            Self::IsRestricted => film.is_restricted.to_string(),
+            Self::People => film
+                .people
+                .iter()
+                .map(|person| {
+                    format!(
+                        "{}: {} {}",
+                        person.role, person.first_name, person.last_name
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+            Self::AudioLanguage => format_optional(film.audio_language.as_ref()),
+            Self::GovernmentFilmTitle => format_optional(film.government_film_title.as_ref()),
+            Self::FilmPosterUrl => format_optional(film.film_poster_url.as_ref()),
+            Self::FilmPosterThumbnailUrl => film.film_poster_thumbnail_url.clone(),
+            Self::BackdropImageUrl => format_optional(film.backdrop_image_url.as_ref()),
+            Self::FilmTrailerUrl => format_optional(film.film_trailer_url.as_ref()),
+        }
+    }
+}
+
+/// A single row of a film package export: one [`PackageFilm`] within its
+/// [`FilmPackage`]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PackageFilmRow<'a> {
+    /// The package containing the film
+    pub package: &'a FilmPackage,
+    /// The film within the package
+    pub film: &'a PackageFilm,
+}
+impl<'a> PackageFilmRow<'a> {
+    /// Flatten a list of [`FilmPackage`]s into one row per [`PackageFilm`],
+    /// ordered by package and then by [`PackageFilm::order`]
+    #[must_use]
+    pub fn from_packages(packages: &'a [FilmPackage]) -> Vec<Self> {
+        packages
+            .iter()
+            .flat_map(|package| {
+                let mut films: Vec<&PackageFilm> = package.films.iter().collect();
+                films.sort_by_key(|film| film.order);
+                films.into_iter().map(move |film| Self { package, film })
+            })
+            .collect()
+    }
+}
+
+/// The columns that can be exported for a [`PackageFilmRow`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PackageFilmColumn {
+    /// [`FilmPackage::id`]
+    PackageId,
+    /// [`FilmPackage::title`]
+    PackageTitle,
+    /// [`FilmPackage::status`]
+    PackageStatus,
+    /// [`PackageFilm::film_id`]
+    FilmId,
+    /// [`PackageFilm::title`]
+    FilmTitle,
+    /// [`PackageFilm::split_percent`]
+    SplitPercent,
+    /// [`PackageFilm::trailer_duration`]
+    TrailerDuration,
+    /// [`PackageFilm::clean_up_duration`]
+    CleanUpDuration,
+    /// [`PackageFilm::order`]
+    Order,
+}
+impl PackageFilmColumn {
+    /// Every [`PackageFilmColumn`], in API order
+    pub const ALL: &[Self] = &[
+        Self::PackageId,
+        Self::PackageTitle,
+        Self::PackageStatus,
+        Self::FilmId,
+        Self::FilmTitle,
+        Self::SplitPercent,
+        Self::TrailerDuration,
+        Self::CleanUpDuration,
+        Self::Order,
+    ];
+}
+impl<'a> CsvColumn<PackageFilmRow<'a>> for PackageFilmColumn {
+    fn header(self) -> &'static str {
+        match self {
+            Self::PackageId => "PackageId",
+            Self::PackageTitle => "PackageTitle",
+            Self::PackageStatus => "PackageStatus",
+            Self::FilmId => "FilmId",
+            Self::FilmTitle => "FilmTitle",
+            Self::SplitPercent => "SplitPercent",
+            Self::TrailerDuration => "TrailerDuration",
+            Self::CleanUpDuration => "CleanUpDuration",
+            Self::Order => "Order",
+        }
+    }
+
+    fn value(self, row: &PackageFilmRow<'a>, _site: Option<&Site>) -> String {
+        match self {
+            Self::PackageId => row.package.id.to_string(),
+            Self::PackageTitle => row.package.title.clone(),
+            Self::PackageStatus => row.package.status.to_string(),
+            Self::FilmId => row.film.film_id.to_string(),
+            Self::FilmTitle => row.film.title.clone(),
+            Self::SplitPercent => row.film.split_percent.to_string(),
+            Self::TrailerDuration => row.film.trailer_duration.to_string(),
+            Self::CleanUpDuration => row.film.clean_up_duration.to_string(),
+            Self::Order => row.film.order.to_string(),
+        }
+    }
+}
+
+/// The columns that can be exported for a [`Screen`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ScreenColumn {
+    /// [`Screen::id`]
+    Id,
+    /// [`Screen::name`]
+    Name,
+    /// [`Screen::screen_number`]
+    ScreenNumber,
+    /// [`Screen::has_custom_layout`]
+    HasCustomLayout,
+    /// [`Screen::total_seats`]
+    TotalSeats,
+    /// [`Screen::house_seats`]
+    HouseSeats,
+}
+impl ScreenColumn {
+    /// Every [`ScreenColumn`], in API order
+    pub const ALL: &[Self] = &[
+        Self::Id,
+        Self::Name,
+        Self::ScreenNumber,
+        Self::HasCustomLayout,
+        Self::TotalSeats,
+        Self::HouseSeats,
+    ];
+}
+impl CsvColumn<Screen> for ScreenColumn {
+    fn header(self) -> &'static str {
+        match self {
+            Self::Id => "Id",
+            Self::Name => "Name",
+            Self::ScreenNumber => "ScreenNumber",
+            Self::HasCustomLayout => "HasCustomLayout",
+            Self::TotalSeats => "TotalSeats",
+            Self::HouseSeats => "HouseSeats",
+        }
+    }
+
+    fn value(self, screen: &Screen, _site: Option<&Site>) -> String {
+        match self {
+            Self::Id => screen.id.to_string(),
+            Self::Name => screen.name.clone(),
+            Self::ScreenNumber => screen.screen_number.clone(),
+            Self::HasCustomLayout => screen.has_custom_layout.to_string(),
+            Self::TotalSeats => screen.total_seats.to_string(),
+            Self::HouseSeats => screen.house_seats.to_string(),
+        }
+    }
+}
+
+/// The columns that can be exported for an [`Attribute`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum AttributeColumn {
+    /// [`Attribute::id`]
+    Id,
+    /// [`Attribute::description`]
+    Description,
+    /// [`Attribute::short_name`]
+    ShortName,
+    /// [`Attribute::font_color`]
+    FontColor,
+    /// [`Attribute::background_color`]
+    BackgroundColor,
+    /// [`Attribute::show_on_sessions_with_no_comps`]
+    ShowOnSessionsWithNoComps,
+}
+impl AttributeColumn {
+    /// Every [`AttributeColumn`], in API order
+    pub const ALL: &[Self] = &[
+        Self::Id,
+        Self::Description,
+        Self::ShortName,
+        Self::FontColor,
+        Self::BackgroundColor,
+        Self::ShowOnSessionsWithNoComps,
+    ];
+}
+impl CsvColumn<Attribute> for AttributeColumn {
+    fn header(self) -> &'static str {
+        match self {
+            Self::Id => "Id",
+            Self::Description => "Description",
+            Self::ShortName => "ShortName",
+            Self::FontColor => "FontColor",
+            Self::BackgroundColor => "BackgroundColor",
+            Self::ShowOnSessionsWithNoComps => "ShowOnSessionsWithNoComps",
+        }
+    }
+
+    fn value(self, attribute: &Attribute, _site: Option<&Site>) -> String {
+        match self {
+            Self::Id => attribute.id.to_string(),
+            Self::Description => attribute.description.clone(),
+            Self::ShortName => attribute.short_name.clone(),
+            Self::FontColor => attribute.font_color.clone(),
+            Self::BackgroundColor => attribute.background_color.clone(),
+            Self::ShowOnSessionsWithNoComps => attribute.show_on_sessions_with_no_comps.to_string(),
+        }
+    }
+}
+
+/// Write a [`SessionList`] to CSV, one row per [`Session`]
+///
+/// # Errors
+///
+/// This function will return an error if writing to `out` fails.
+pub fn write_sessions<W: Write>(
+    out: W,
+    sessions: &SessionList,
+    columns: &[SessionColumn],
+    site: Option<&Site>,
+) -> io::Result<()> {
+    write_csv(out, sessions.iter(), columns, site)
+}
+
+/// Write a list of [`Film`]s to CSV, one row per [`Film`]
+///
+/// # Errors
+///
+/// This function will return an error if writing to `out` fails.
+pub fn write_films<W: Write>(
+    out: W,
+    films: &[Film],
+    columns: &[FilmColumn],
+    site: Option<&Site>,
+) -> io::Result<()> {
+    write_csv(out, films, columns, site)
+}
+
+/// Write a list of [`FilmPackage`]s to CSV, one row per [`PackageFilm`]
+///
+/// # Errors
+///
+/// This function will return an error if writing to `out` fails.
+pub fn write_film_packages<W: Write>(
+    out: W,
+    packages: &[FilmPackage],
+    columns: &[PackageFilmColumn],
+) -> io::Result<()> {
+    write_csv(out, &PackageFilmRow::from_packages(packages), columns, None)
+}
+
+/// Write a list of [`Screen`]s to CSV, one row per [`Screen`]
+///
+/// # Errors
+///
+/// This function will return an error if writing to `out` fails.
+pub fn write_screens<W: Write>(
+    out: W,
+    screens: &[Screen],
+    columns: &[ScreenColumn],
+) -> io::Result<()> {
+    write_csv(out, screens, columns, None)
+}
+
+/// Write a list of [`Attribute`]s to CSV, one row per [`Attribute`]
+///
+/// # Errors
+///
+/// This function will return an error if writing to `out` fails.
+pub fn write_attributes<W: Write>(
+    out: W,
+    attributes: &[Attribute],
+    columns: &[AttributeColumn],
+) -> io::Result<()> {
+    write_csv(out, attributes, columns, None)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for CSV export
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder, SiteBuilder};
+
+    /// Internal helper to export a single session with the given columns
+    fn export(session: &Session, columns: &[SessionColumn], site: Option<&Site>) -> String {
+        let mut out = Vec::new();
+        write_csv(&mut out, [session], columns, site).expect("writing to a Vec cannot fail");
+        String::from_utf8(out).expect("CSV output should be UTF-8")
+    }
+
+    #[test]
+    fn timestamps_use_one_format_with_a_separate_offset() {
+        let film = FilmBuilder::new("ST00000001").build();
+        let screen = ScreenBuilder::new(1).build();
+        let start = NaiveDateTime::parse_from_str("2025-07-01 19:00:00", "%Y-%m-%d %H:%M:%S")
+            .expect("the start time should parse");
+        let session = SessionBuilder::new(1, &film, &screen, start).build();
+        let site = SiteBuilder::new()
+            .with_time_zone("America/Los_Angeles")
+            .build();
+        let columns = [SessionColumn::PreShowStartTime, SessionColumn::UtcOffset];
+
+        assert_eq!(
+            export(&session, &columns, Some(&site)),
+            "PreShowStartTime,UtcOffset\r\n2025-07-01 19:00:00,UTC-07:00\r\n"
+        );
+        assert_eq!(
+            export(&session, &columns, None),
+            "PreShowStartTime,UtcOffset\r\n2025-07-01 19:00:00,\r\n"
+        );
+    }
+
+    #[test]
+    fn formulas_are_guarded() {
+        let film = FilmBuilder::new("ST00000001")
+            .with_title("=HYPERLINK(\"http://example.com\")")
+            .build();
+        let screen = ScreenBuilder::new(1).build();
+        let session = SessionBuilder::new(1, &film, &screen, film.opening_date)
+            .with_price_card_name("@Member")
+            .build();
+
+        assert_eq!(
+            export(
+                &session,
+                &[SessionColumn::Title, SessionColumn::PriceCardName],
+                None
+            ),
+            "Title,PriceCardName\r\n\"'=HYPERLINK(\"\"http://example.com\"\")\",'@Member\r\n"
+        );
+    }
+}
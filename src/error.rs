@@ -6,8 +6,14 @@
 use std::{
     error::Error,
     fmt::{self, Debug, Display},
+    io,
 };
 
+use reqwest::StatusCode;
+use url::Url;
+
+use crate::{cassette::CassetteError, region::BaseUrlError};
+
 /// The list of errors that can occur when using the libveezi library
 #[derive(Debug)]
 pub enum LibVeeziError {
@@ -15,12 +21,35 @@ pub enum LibVeeziError {
     Http(reqwest::Error),
     /// An error occurred while parsing a URL
     UrlParse(url::ParseError),
+    /// The base URL of a client is not valid
+    BaseUrl(BaseUrlError),
+    /// The Veezi API responded with an error status code
+    Status {
+        /// The status code of the response
+        status: StatusCode,
+        /// The URL of the request
+        url: Url,
+    },
+    /// A response body could not be deserialized into the expected type
+    Decode(serde_json::Error),
+    /// An error occurred while recording or replaying a cassette
+    Cassette(CassetteError),
+    /// A custom [`crate::transport::Transport`] failed to send a request
+    Transport(Box<dyn Error + Send + Sync>),
+    /// The runtime of a blocking client could not be started
+    Runtime(io::Error),
 }
 impl Display for LibVeeziError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Http(err) => write!(f, "HTTP error: {err}"),
             Self::UrlParse(err) => write!(f, "URL parse error: {err}"),
+            Self::BaseUrl(err) => write!(f, "Configuration error: {err}"),
+            Self::Status { status, url } => write!(f, "HTTP status {status} for {url}"),
+            Self::Decode(err) => write!(f, "Decode error: {err}"),
+            Self::Cassette(err) => write!(f, "Cassette error: {err}"),
+            Self::Transport(err) => write!(f, "Transport error: {err}"),
+            Self::Runtime(err) => write!(f, "Runtime error: {err}"),
         }
     }
 }
@@ -29,6 +58,12 @@ impl Error for LibVeeziError {
         match self {
             Self::Http(err) => Some(err),
             Self::UrlParse(err) => Some(err),
+            Self::BaseUrl(err) => Some(err),
+            Self::Status { .. } => None,
+            Self::Decode(err) => Some(err),
+            Self::Cassette(err) => Some(err),
+            Self::Transport(err) => Some(err.as_ref()),
+            Self::Runtime(err) => Some(err),
         }
     }
 }
@@ -42,6 +77,21 @@ impl From<url::ParseError> for LibVeeziError {
         Self::UrlParse(err)
     }
 }
+impl From<BaseUrlError> for LibVeeziError {
+    fn from(err: BaseUrlError) -> Self {
+        Self::BaseUrl(err)
+    }
+}
+impl From<serde_json::Error> for LibVeeziError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Decode(err)
+    }
+}
+impl From<CassetteError> for LibVeeziError {
+    fn from(err: CassetteError) -> Self {
+        Self::Cassette(err)
+    }
+}
 
 /// A result type for the libveezi library
 pub type ApiResult<T> = Result<T, LibVeeziError>;
@@ -0,0 +1,451 @@
+//! A Prometheus exporter for live occupancy and sales state
+//!
+//! The primary type is [`OccupancyExporter`], which serves `GET /metrics` in
+//! the Prometheus text format. Every scrape lists the sessions starting within
+//! the exporter's window (24 hours by default) through a shared [`Client`], so
+//! its caches decide how often the Veezi API is actually polled.
+//!
+//! Each session is exported with `session`, `film`, `screen`, `attributes`
+//! and `start` labels; `attributes` holds the comma separated short names of
+//! its [`Attribute`]s. Each screen is exported with `screen` and `screen_id`
+//! labels, summed over its sessions in the window.
+//!
+//! | Metric | Description |
+//! |--------|-------------|
+//! | `veezi_session_seats_sold` | Seats sold |
+//! | `veezi_session_seats_available` | Seats still available |
+//! | `veezi_session_seats_held` | Seats held |
+//! | `veezi_session_seats_house` | House seats |
+//! | `veezi_session_capacity` | Capacity of the session |
+//! | `veezi_session_occupancy_ratio` | Seats sold over capacity |
+//! | `veezi_session_sold_out` | 1 if the session is sold out, otherwise 0 |
+//! | `veezi_session_sales_cut_off_minutes` | Minutes until sales close |
+//! | `veezi_screen_sessions` | Sessions on the screen |
+//! | `veezi_screen_sold_out_sessions` | Sold out sessions on the screen |
+//! | `veezi_screen_seats_sold` | Seats sold |
+//! | `veezi_screen_seats_available` | Seats still available |
+//! | `veezi_screen_seats_held` | Seats held |
+//! | `veezi_screen_seats_house` | House seats |
+//! | `veezi_screen_capacity` | Capacity of the sessions |
+//! | `veezi_screen_occupancy_ratio` | Seats sold over capacity |
+//!
+//! The metrics can also be rendered without a server with
+//! [`render_occupancy`].
+//!
+//! This module is only available with the `exporter` feature.
+
+use std::{collections::HashMap, io, sync::Arc};
+
+use axum::{
+    Router,
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::{NaiveDateTime, TimeDelta};
+use log::debug;
+use tokio::net::TcpListener;
+
+use crate::{
+    analytics::{OccupancySummary, SessionOccupancy},
+    attr::{Attribute, AttributeId},
+    client::Client,
+    error::ApiResult,
+    screen::{Screen, ScreenId},
+    session::{Session, SessionList},
+};
+
+/// The content type of the Prometheus text format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The window of sessions exported if none is given
+pub const DEFAULT_WINDOW: TimeDelta = TimeDelta::hours(24);
+
+/// A single gauge and its samples, in the Prometheus text format
+struct Gauge {
+    /// The name of the metric
+    name: &'static str,
+    /// The help text of the metric
+    help: &'static str,
+    /// The label sets and values of each sample
+    samples: Vec<(String, String)>,
+}
+impl Gauge {
+    /// Create a new [`Gauge`] with no samples
+    const fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Add a sample with the given labels
+    fn sample<T: ToString>(&mut self, labels: &str, value: &T) {
+        self.samples.push((labels.to_string(), value.to_string()));
+    }
+
+    /// Render the metric, including its `HELP` and `TYPE` lines
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("# HELP {} {}", self.name, self.help),
+            format!("# TYPE {} gauge", self.name),
+        ];
+        lines.extend(
+            self.samples
+                .iter()
+                .map(|(labels, value)| format!("{}{labels} {value}", self.name)),
+        );
+        lines
+    }
+}
+
+/// Internal helper to escape a label value in the Prometheus text format
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Internal helper to format a set of labels in the Prometheus text format
+fn labels(pairs: &[(&str, &str)]) -> String {
+    let pairs: Vec<String> = pairs
+        .iter()
+        .map(|(name, value)| format!("{name}=\"{}\"", escape_label(value)))
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+/// Internal helper to convert a percentage to a ratio
+fn ratio(percent: f64) -> f64 {
+    percent / 100.0
+}
+
+/// The screens and attributes used to label sessions
+struct Lookup<'a> {
+    /// The known screens, by ID
+    screens: HashMap<ScreenId, &'a Screen>,
+    /// The short names of the known attributes, by ID
+    attributes: HashMap<&'a AttributeId, &'a str>,
+}
+impl<'a> Lookup<'a> {
+    /// Index the given screens and attributes
+    fn new(screens: &'a [Screen], attributes: &'a [Attribute]) -> Self {
+        Self {
+            screens: screens.iter().map(|screen| (screen.id, screen)).collect(),
+            attributes: attributes
+                .iter()
+                .map(|attribute| (&attribute.id, attribute.short_name.as_str()))
+                .collect(),
+        }
+    }
+
+    /// Compute the occupancy of a session against its screen, if known
+    fn occupancy(&self, session: &Session) -> SessionOccupancy {
+        SessionOccupancy::new(session, self.screens.get(&session.screen_id).copied())
+    }
+
+    /// Get the name of a screen, or its ID if it is unknown
+    fn screen_name(&self, id: ScreenId) -> String {
+        self.screens
+            .get(&id)
+            .map_or_else(|| id.to_string(), |screen| screen.name.clone())
+    }
+
+    /// Get the labels of a session
+    fn session(&self, session: &Session) -> String {
+        let attributes: Vec<&str> = session
+            .attributes
+            .iter()
+            .map(|id| self.attributes.get(id).copied().unwrap_or(id.as_str()))
+            .collect();
+        labels(&[
+            ("session", &session.id.to_string()),
+            ("film", &session.title),
+            ("screen", &self.screen_name(session.screen_id)),
+            ("attributes", &attributes.join(",")),
+            ("start", &session.feature_start_time.to_string()),
+        ])
+    }
+}
+
+/// Internal helper to build the per-session gauges
+fn session_gauges(sessions: &SessionList, lookup: &Lookup<'_>, now: NaiveDateTime) -> [Gauge; 8] {
+    let mut gauges = [
+        Gauge::new("veezi_session_seats_sold", "Seats sold for a session"),
+        Gauge::new(
+            "veezi_session_seats_available",
+            "Seats still available for a session",
+        ),
+        Gauge::new("veezi_session_seats_held", "Seats held for a session"),
+        Gauge::new("veezi_session_seats_house", "House seats for a session"),
+        Gauge::new("veezi_session_capacity", "Capacity of a session"),
+        Gauge::new(
+            "veezi_session_occupancy_ratio",
+            "Seats sold over the capacity of a session",
+        ),
+        Gauge::new(
+            "veezi_session_sold_out",
+            "Whether a session is sold out (1) or not (0)",
+        ),
+        Gauge::new(
+            "veezi_session_sales_cut_off_minutes",
+            "Minutes until sales close for a session",
+        ),
+    ];
+    for session in sessions.iter() {
+        let occupancy = lookup.occupancy(session);
+        let session_labels = lookup.session(session);
+        let [
+            sold,
+            available,
+            held,
+            house,
+            capacity,
+            occupancy_ratio,
+            sold_out,
+            cut_off,
+        ] = &mut gauges;
+        sold.sample(&session_labels, &occupancy.seats_sold);
+        available.sample(&session_labels, &occupancy.seats_available);
+        held.sample(&session_labels, &occupancy.seats_held);
+        house.sample(&session_labels, &occupancy.seats_house);
+        capacity.sample(&session_labels, &occupancy.capacity);
+        occupancy_ratio.sample(&session_labels, &ratio(occupancy.occupancy_percent));
+        sold_out.sample(&session_labels, &u8::from(occupancy.sold_out));
+        cut_off.sample(
+            &session_labels,
+            &(session.sales_cut_off_time - now).num_minutes(),
+        );
+    }
+    gauges
+}
+
+/// Internal helper to build the per-screen gauges, ordered by screen name and
+/// ID
+fn screen_gauges(sessions: &SessionList, lookup: &Lookup<'_>) -> [Gauge; 8] {
+    let mut by_screen: HashMap<ScreenId, OccupancySummary> = HashMap::new();
+    for session in sessions.iter() {
+        by_screen
+            .entry(session.screen_id)
+            .or_default()
+            .add(&lookup.occupancy(session));
+    }
+    let mut by_screen: Vec<(String, ScreenId, OccupancySummary)> = by_screen
+        .into_iter()
+        .map(|(id, summary)| (lookup.screen_name(id), id, summary))
+        .collect();
+    by_screen.sort_by(|a, b| (&a.0, a.1.into_u32()).cmp(&(&b.0, b.1.into_u32())));
+
+    let mut gauges = [
+        Gauge::new("veezi_screen_sessions", "Sessions on a screen"),
+        Gauge::new(
+            "veezi_screen_sold_out_sessions",
+            "Sold out sessions on a screen",
+        ),
+        Gauge::new("veezi_screen_seats_sold", "Seats sold on a screen"),
+        Gauge::new(
+            "veezi_screen_seats_available",
+            "Seats still available on a screen",
+        ),
+        Gauge::new("veezi_screen_seats_held", "Seats held on a screen"),
+        Gauge::new("veezi_screen_seats_house", "House seats on a screen"),
+        Gauge::new(
+            "veezi_screen_capacity",
+            "Capacity of the sessions on a screen",
+        ),
+        Gauge::new(
+            "veezi_screen_occupancy_ratio",
+            "Seats sold over the capacity of the sessions on a screen",
+        ),
+    ];
+    for (name, id, summary) in &by_screen {
+        let screen_labels = labels(&[("screen", name), ("screen_id", &id.to_string())]);
+        let [
+            sessions,
+            sold_out,
+            sold,
+            available,
+            held,
+            house,
+            capacity,
+            occupancy_ratio,
+        ] = &mut gauges;
+        sessions.sample(&screen_labels, &summary.sessions);
+        sold_out.sample(&screen_labels, &summary.sold_out_sessions);
+        sold.sample(&screen_labels, &summary.seats_sold);
+        available.sample(&screen_labels, &summary.seats_available);
+        held.sample(&screen_labels, &summary.seats_held);
+        house.sample(&screen_labels, &summary.seats_house);
+        capacity.sample(&screen_labels, &summary.capacity);
+        occupancy_ratio.sample(&screen_labels, &ratio(summary.occupancy_percent));
+    }
+    gauges
+}
+
+/// Render the occupancy and sales state of sessions in the Prometheus text
+/// format
+///
+/// `screens` and `attributes` provide the screen names, capacities and
+/// attribute short names; unknown screens and attributes are labelled by
+/// their ID. `now` is the current local time at the site, used for the
+/// minutes until sales cut-off.
+#[must_use]
+pub fn render_occupancy(
+    sessions: &SessionList,
+    screens: &[Screen],
+    attributes: &[Attribute],
+    now: NaiveDateTime,
+) -> String {
+    let lookup = Lookup::new(screens, attributes);
+    let mut lines: Vec<String> = session_gauges(sessions, &lookup, now)
+        .iter()
+        .chain(&screen_gauges(sessions, &lookup))
+        .flat_map(Gauge::lines)
+        .collect();
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// A Prometheus exporter for the occupancy of upcoming sessions
+#[derive(Clone)]
+pub struct OccupancyExporter {
+    /// The client used to reach the Veezi API
+    client: Arc<Client>,
+    /// How far ahead of the current time sessions are exported
+    pub window: TimeDelta,
+}
+impl OccupancyExporter {
+    /// Create a new [`OccupancyExporter`] for the sessions starting within
+    /// [`DEFAULT_WINDOW`]
+    #[must_use]
+    pub const fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            window: DEFAULT_WINDOW,
+        }
+    }
+
+    /// Export the sessions starting within the given window instead
+    #[must_use]
+    pub const fn with_window(mut self, window: TimeDelta) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Fetch the current sessions and render their metrics in the Prometheus
+    /// text format
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any of the API requests fail.
+    pub async fn render(&self) -> ApiResult<String> {
+        let now = self.client.get_site().await?.now();
+        let end = now
+            .checked_add_signed(self.window)
+            .unwrap_or(NaiveDateTime::MAX);
+        let sessions = self
+            .client
+            .list_sessions()
+            .await?
+            .filter_by_time_range(NaiveDateTime::MIN, end);
+        let screens = self.client.list_screens().await?;
+        let attributes = self.client.list_attributes().await?;
+        Ok(render_occupancy(&sessions, &screens, &attributes, now))
+    }
+
+    /// Build the [`Router`] serving `GET /metrics`
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/metrics", get(metrics))
+            .with_state(self.clone())
+    }
+
+    /// Serve `GET /metrics` on a listener until the server fails
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the server fails.
+    pub async fn serve(self, listener: TcpListener) -> io::Result<()> {
+        debug!("Serving libveezi exporter on {:?}", listener.local_addr());
+        axum::serve(listener, self.router()).await
+    }
+}
+
+/// Internal handler for `GET /metrics`
+async fn metrics(State(exporter): State<OccupancyExporter>) -> Response {
+    match exporter.render().await {
+        Ok(body) => ([(header::CONTENT_TYPE, CONTENT_TYPE)], body).into_response(),
+        Err(err) => {
+            debug!("Exporter scrape failed: {err}");
+            (StatusCode::BAD_GATEWAY, err.to_string()).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for rendering occupancy in the Prometheus text format
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder};
+
+    /// Internal helper to get a time on the first of June 2025
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 1)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .expect("the time should exist")
+    }
+
+    #[test]
+    fn sessions_are_rendered_with_their_labels() {
+        let film = FilmBuilder::new("ST00000001").with_title("Heat").build();
+        let screen = ScreenBuilder::new(1).with_seats(200, 0).build();
+        let session = SessionBuilder::new(7, &film, &screen, at(18))
+            .with_seats_sold(50)
+            .build();
+
+        let exposition =
+            render_occupancy(&SessionList::from(vec![session]), &[screen], &[], at(17));
+        assert!(exposition.contains("# HELP veezi_session_seats_sold Seats sold for a session\n"));
+        assert!(exposition.contains("# TYPE veezi_session_seats_sold gauge\n"));
+        assert!(exposition.contains(
+            "veezi_session_seats_sold{session=\"7\",film=\"Heat\",screen=\"Screen 1\",\
+             attributes=\"\",start=\"2025-06-01 18:15:00\"} 50\n"
+        ));
+        assert!(
+            exposition.contains("veezi_screen_capacity{screen=\"Screen 1\",screen_id=\"1\"} 200\n")
+        );
+        assert!(exposition.ends_with('\n'));
+    }
+
+    #[test]
+    fn screens_sharing_a_name_have_distinct_labels() {
+        let film = FilmBuilder::new("ST00000001").build();
+        let screens = [
+            ScreenBuilder::new(2).with_name("Studio").build(),
+            ScreenBuilder::new(1).with_name("Studio").build(),
+        ];
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screens[0], at(18)).build(),
+            SessionBuilder::new(2, &film, &screens[1], at(18)).build(),
+        ]);
+
+        let exposition = render_occupancy(&sessions, &screens, &[], at(17));
+        let samples: Vec<&str> = exposition
+            .lines()
+            .filter(|line| line.starts_with("veezi_screen_sessions{"))
+            .collect();
+        assert_eq!(
+            samples,
+            [
+                "veezi_screen_sessions{screen=\"Studio\",screen_id=\"1\"} 1",
+                "veezi_screen_sessions{screen=\"Studio\",screen_id=\"2\"} 1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,479 @@
+//! RSS 2.0 and Atom feeds of films and sessions
+//!
+//! A [`Feed`] can be built in two modes:
+//!
+//! - [`Feed::films`] produces one item per now playing, advance sales or coming
+//!   soon [`Film`], carrying its synopsis, poster, rating and next showtimes.
+//! - [`Feed::sessions`] produces one item per [`Session`], and is intended for
+//!   sessions that were newly added since the feed was last generated, e.g.
+//!   from [`Feed::new_sessions`] or from
+//!   [`crate::watch::WatchEvent::SessionAdded`] events.
+//!
+//! Render the feed with [`Feed::to_rss`] or [`Feed::to_atom`].
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, SubsecRound, TimeDelta, Utc};
+
+use crate::{
+    film::{Film, PlayingStatus},
+    session::{Session, SessionId, SessionList},
+    site::Site,
+};
+
+/// The format used to render the showtimes of a film or session
+const SHOWTIME_FORMAT: &str = "%a %-d %b %H:%M";
+
+/// The XML declaration at the start of every rendered feed
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>";
+
+/// Internal helper to escape text for use in XML content and attributes
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Internal helper to format an XML element with escaped text content
+fn element(name: &str, text: &str) -> String {
+    format!("<{name}>{}</{name}>", escape_xml(text))
+}
+
+/// Internal helper to guess the MIME type of an image from the extension of
+/// its URL, if it is a common image format
+fn image_type(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let file = path.rsplit('/').next().unwrap_or_default();
+    let (_, extension) = file.rsplit_once('.')?;
+    match extension.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "avif" => Some("image/avif"),
+        _ => None,
+    }
+}
+
+/// Internal helper to format the `type` attribute of an Atom image link,
+/// which is left out if the image's MIME type is unknown
+fn image_type_attribute(url: &str) -> String {
+    image_type(url)
+        .map(|mime| format!(" type=\"{mime}\""))
+        .unwrap_or_default()
+}
+
+/// Internal helper to resolve a local site time to an instant, treating it
+/// as UTC if the site's time zone is unknown
+fn resolve_time(site: &Site, time: NaiveDateTime) -> DateTime<FixedOffset> {
+    site.localize(time)
+        .map_or_else(|| time.and_utc().fixed_offset(), |time| time.fixed_offset())
+}
+
+/// Internal helper to describe a [`PlayingStatus`] for readers of a feed
+const fn status_label(status: PlayingStatus) -> &'static str {
+    match status {
+        PlayingStatus::NowPlaying => "Now Playing",
+        PlayingStatus::AdvanceSales => "Advance Sales",
+        PlayingStatus::ComingSoon => "Coming Soon",
+    }
+}
+
+/// A single item (RSS) or entry (Atom) of a [`Feed`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FeedItem {
+    /// A stable, unique identifier for the item
+    pub id: String,
+    /// The title of the item
+    pub title: String,
+    /// A link to a page about the item, if any
+    pub link: Option<String>,
+    /// The HTML summary of the item
+    pub summary: String,
+    /// The URL of an image for the item, if any
+    pub image: Option<String>,
+    /// The category of the item, if any
+    pub category: Option<String>,
+    /// The time the item was published
+    pub published: DateTime<FixedOffset>,
+}
+impl FeedItem {
+    /// Create a [`FeedItem`] for a [`Film`] with its [`PlayingStatus`] and its
+    /// upcoming showtimes
+    #[must_use]
+    pub fn film(film: &Film, status: PlayingStatus, showtimes: &[&Session], site: &Site) -> Self {
+        let mut summary = Vec::new();
+        if let Some(synopsis) = &film.synopsis {
+            summary.push(element("p", synopsis));
+        }
+        if let Some(rating) = &film.rating {
+            summary.push(element("p", &format!("Rated {rating}")));
+        }
+        if showtimes.is_empty() {
+            summary.push(format!(
+                "<p>Opens {}</p>",
+                film.opening_date.format("%a %-d %b %Y")
+            ));
+        } else {
+            summary.push("<p>Next showtimes:</p><ul>".to_string());
+            summary.extend(showtimes.iter().map(|session| {
+                format!(
+                    "<li>{}</li>",
+                    session.feature_start_time.format(SHOWTIME_FORMAT)
+                )
+            }));
+            summary.push("</ul>".to_string());
+        }
+
+        Self {
+            id: format!("urn:libveezi:film:{}", film.id),
+            title: film.title.clone(),
+            link: None,
+            summary: summary.concat(),
+            image: film
+                .film_poster_url
+                .clone()
+                .or_else(|| Some(film.film_poster_thumbnail_url.clone()))
+                .filter(|url| !url.is_empty()),
+            category: Some(status_label(status).to_string()),
+            published: resolve_time(site, film.opening_date),
+        }
+    }
+
+    /// Create a [`FeedItem`] for a [`Session`], with details from its
+    /// [`Film`] if known
+    #[must_use]
+    pub fn session(
+        session: &Session,
+        film: Option<&Film>,
+        published: DateTime<FixedOffset>,
+        site: &Site,
+    ) -> Self {
+        let mut summary = vec![format!(
+            "<p>{}</p>",
+            session.feature_start_time.format(SHOWTIME_FORMAT)
+        )];
+        if let Some(rating) = film.and_then(|film| film.rating.as_ref()) {
+            summary.push(element("p", &format!("Rated {rating}")));
+        }
+        if let Some(synopsis) = film.and_then(|film| film.synopsis.as_ref()) {
+            summary.push(element("p", synopsis));
+        }
+
+        Self {
+            id: format!("urn:libveezi:session:{}", session.id),
+            title: format!(
+                "{} - {}",
+                session.title,
+                session.feature_start_time.format(SHOWTIME_FORMAT)
+            ),
+            link: None,
+            summary: summary.concat(),
+            image: film
+                .and_then(|film| {
+                    film.film_poster_url
+                        .clone()
+                        .or_else(|| Some(film.film_poster_thumbnail_url.clone()))
+                })
+                .filter(|url| !url.is_empty()),
+            category: Some(site.name.clone()),
+            published,
+        }
+    }
+}
+
+/// An RSS 2.0 or Atom feed of [`FeedItem`]s
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Feed {
+    /// A stable, unique identifier for the feed
+    pub id: String,
+    /// The title of the feed
+    pub title: String,
+    /// A link to the website the feed belongs to
+    pub link: String,
+    /// A description of the feed
+    pub description: String,
+    /// The name of the author of the feed, which defaults to the site's name
+    pub author: String,
+    /// The time the feed was generated
+    pub updated: DateTime<FixedOffset>,
+    /// The items of the feed
+    pub items: Vec<FeedItem>,
+}
+impl Feed {
+    /// Create an empty [`Feed`] for a [`Site`]
+    #[must_use]
+    pub fn new(site: &Site, link: &str, title: String, description: String) -> Self {
+        Self {
+            id: format!("urn:libveezi:site:{}", site.short_name),
+            title,
+            link: link.to_string(),
+            description,
+            author: site.name.clone(),
+            updated: Utc::now().trunc_subsecs(0).fixed_offset(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Set the name of the author of the feed
+    #[must_use]
+    pub fn with_author(mut self, author: String) -> Self {
+        self.author = author;
+        self
+    }
+
+    /// Create a [`Feed`] with one item per now playing, advance sales or
+    /// coming soon [`Film`], classified by [`SessionList::classify_film`]
+    ///
+    /// Each item lists up to `showtimes` of the film's next sessions that can
+    /// be booked online. Items are ordered by [`Film::display_sequence`].
+    #[must_use]
+    pub fn films(
+        site: &Site,
+        link: &str,
+        films: &[Film],
+        sessions: &SessionList,
+        now: NaiveDateTime,
+        window: TimeDelta,
+        showtimes: usize,
+    ) -> Self {
+        let mut films: Vec<(&Film, PlayingStatus)> = films
+            .iter()
+            .filter_map(|film| {
+                sessions
+                    .classify_film(film, now, window)
+                    .map(|status| (film, status))
+            })
+            .collect();
+        films.sort_by_key(|(film, _)| film.display_sequence);
+
+        let mut feed = Self::new(
+            site,
+            link,
+            format!("{} - Films", site.name),
+            format!("Films now playing and coming soon at {}", site.name),
+        );
+        feed.items = films
+            .into_iter()
+            .map(|(film, status)| {
+                let mut next: Vec<&Session> = sessions
+                    .iter()
+                    .filter(|session| {
+                        session.film_id == film.id && session.is_open_for_web_sales_at(now)
+                    })
+                    .collect();
+                next.sort_by_key(|session| session.feature_start_time);
+                next.truncate(showtimes);
+                FeedItem::film(film, status, &next, site)
+            })
+            .collect();
+        feed
+    }
+
+    /// Create a [`Feed`] with one item per [`Session`], ordered by feature
+    /// start time
+    ///
+    /// Every item is marked as published at the time the feed is generated.
+    #[must_use]
+    pub fn sessions<'a>(
+        site: &Site,
+        link: &str,
+        films: &[Film],
+        sessions: impl IntoIterator<Item = &'a Session>,
+    ) -> Self {
+        let mut sessions: Vec<&Session> = sessions.into_iter().collect();
+        sessions.sort_by_key(|session| session.feature_start_time);
+
+        let mut feed = Self::new(
+            site,
+            link,
+            format!("{} - New Sessions", site.name),
+            format!("Newly added sessions at {}", site.name),
+        );
+        let published = feed.updated;
+        feed.items = sessions
+            .into_iter()
+            .map(|session| {
+                let film = films.iter().find(|film| film.id == session.film_id);
+                FeedItem::session(session, film, published, site)
+            })
+            .collect();
+        feed
+    }
+
+    /// Create a [`Feed`] with one item per [`Session`] in `current` that is
+    /// not in `previous`
+    #[must_use]
+    pub fn new_sessions(
+        site: &Site,
+        link: &str,
+        films: &[Film],
+        previous: &SessionList,
+        current: &SessionList,
+    ) -> Self {
+        let known: HashSet<SessionId> = previous.iter().map(|session| session.id).collect();
+        Self::sessions(
+            site,
+            link,
+            films,
+            current
+                .iter()
+                .filter(|session| !known.contains(&session.id)),
+        )
+    }
+
+    /// Render this [`Feed`] as an RSS 2.0 document
+    #[must_use]
+    pub fn to_rss(&self) -> String {
+        let mut lines = vec![
+            XML_DECLARATION.to_string(),
+            "<rss version=\"2.0\">".to_string(),
+            "<channel>".to_string(),
+            element("title", &self.title),
+            element("link", &self.link),
+            element("description", &self.description),
+            format!(
+                "<lastBuildDate>{}</lastBuildDate>",
+                self.updated.to_rfc2822()
+            ),
+            element("generator", "libveezi"),
+        ];
+        for item in &self.items {
+            lines.push("<item>".to_string());
+            lines.push(format!(
+                "<guid isPermaLink=\"false\">{}</guid>",
+                escape_xml(&item.id)
+            ));
+            lines.push(element("title", &item.title));
+            if let Some(link) = &item.link {
+                lines.push(element("link", link));
+            }
+            lines.push(element("description", &item.summary));
+            if let Some(image) = &item.image {
+                // RSS 2.0 requires a type, so fall back to a generic one
+                lines.push(format!(
+                    "<enclosure url=\"{}\" length=\"0\" type=\"{}\"/>",
+                    escape_xml(image),
+                    image_type(image).unwrap_or("application/octet-stream")
+                ));
+            }
+            if let Some(category) = &item.category {
+                lines.push(element("category", category));
+            }
+            lines.push(format!(
+                "<pubDate>{}</pubDate>",
+                item.published.to_rfc2822()
+            ));
+            lines.push("</item>".to_string());
+        }
+        lines.push("</channel>".to_string());
+        lines.push("</rss>".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    /// Render this [`Feed`] as an Atom document
+    #[must_use]
+    pub fn to_atom(&self) -> String {
+        let mut lines = vec![
+            XML_DECLARATION.to_string(),
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\">".to_string(),
+            element("id", &self.id),
+            element("title", &self.title),
+            element("subtitle", &self.description),
+            format!(
+                "<link rel=\"alternate\" href=\"{}\"/>",
+                escape_xml(&self.link)
+            ),
+            element("updated", &self.updated.to_rfc3339()),
+            format!("<author>{}</author>", element("name", &self.author)),
+            element("generator", "libveezi"),
+        ];
+        for item in &self.items {
+            lines.push("<entry>".to_string());
+            lines.push(element("id", &item.id));
+            lines.push(element("title", &item.title));
+            lines.push(format!(
+                "<link rel=\"alternate\" href=\"{}\"/>",
+                escape_xml(item.link.as_ref().unwrap_or(&self.link))
+            ));
+            if let Some(image) = &item.image {
+                lines.push(format!(
+                    "<link rel=\"enclosure\"{} href=\"{}\"/>",
+                    image_type_attribute(image),
+                    escape_xml(image)
+                ));
+            }
+            if let Some(category) = &item.category {
+                lines.push(format!("<category term=\"{}\"/>", escape_xml(category)));
+            }
+            lines.push(element("published", &item.published.to_rfc3339()));
+            lines.push(element("updated", &item.published.to_rfc3339()));
+            lines.push(format!(
+                "<summary type=\"html\">{}</summary>",
+                escape_xml(&item.summary)
+            ));
+            lines.push("</entry>".to_string());
+        }
+        lines.push("</feed>".to_string());
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for rendering [`Feed`]s
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder, SiteBuilder};
+
+    /// Internal helper to build a session feed with a single poster URL
+    fn feed_with_poster(poster_url: &str) -> Feed {
+        let site = SiteBuilder::new().with_name("Noyo Theater", "NOYO").build();
+        let film = FilmBuilder::new("ST00000001")
+            .with_poster_url(Some(poster_url))
+            .build();
+        let screen = ScreenBuilder::new(1).build();
+        let session = SessionBuilder::new(1, &film, &screen, film.opening_date).build();
+        Feed::sessions(&site, "https://example.com/", &[film], [&session])
+    }
+
+    #[test]
+    fn atom_feed_has_author() {
+        let atom = feed_with_poster("https://example.com/poster.jpg").to_atom();
+        assert!(atom.contains("<author><name>Noyo Theater</name></author>"));
+
+        let atom = feed_with_poster("https://example.com/poster.jpg")
+            .with_author("Front of House".to_string())
+            .to_atom();
+        assert!(atom.contains("<author><name>Front of House</name></author>"));
+    }
+
+    #[test]
+    fn enclosure_type_follows_poster_url_or_falls_back() {
+        let feed = feed_with_poster("https://example.com/poster.PNG?size=large");
+        assert!(
+            feed.to_atom()
+                .contains("<link rel=\"enclosure\" type=\"image/png\" href=\"https://example.com/")
+        );
+        assert!(feed.to_rss().contains("length=\"0\" type=\"image/png\"/>"));
+
+        let feed = feed_with_poster("https://example.com/posters/12345");
+        assert!(
+            feed.to_atom()
+                .contains("<link rel=\"enclosure\" href=\"https://example.com/posters/12345\"/>")
+        );
+        assert!(feed.to_rss().contains(
+            "<enclosure url=\"https://example.com/posters/12345\" length=\"0\" \
+             type=\"application/octet-stream\"/>"
+        ));
+    }
+}
@@ -5,14 +5,17 @@
 use std::fmt::{self, Debug, Display, Formatter};
 
 use chrono::NaiveDateTime;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "blocking")]
+use crate::blocking;
 #[allow(unused_imports)] // for docs
 use crate::session::{SalesVia, Session, SessionStatus, ShowType};
 use crate::{client::Client, error::ApiResult, session::SessionList};
 
 /// The status of a particular [`Film`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "PascalCase")]
 pub enum FilmStatus {
     /// Film is active and can be scheduled
@@ -22,9 +25,19 @@ pub enum FilmStatus {
     /// Film has been deleted
     Deleted,
 }
+impl Display for FilmStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Active => "Active",
+            Self::Inactive => "Inactive",
+            Self::Deleted => "Deleted",
+        })
+    }
+}
 
 /// The format of a particular [`Film`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum FilmFormat {
     /// A 2D film
     #[serde(rename = "2D Film")]
@@ -42,11 +55,37 @@ pub enum FilmFormat {
     #[serde(rename = "Not a Film")]
     NotAFilm,
 }
+impl Display for FilmFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Film2D => "2D Film",
+            Self::Digital2D => "2D Digital",
+            Self::Digital3D => "3D Digital",
+            Self::Digital3DHFR => "3D HFR",
+            Self::NotAFilm => "Not a Film",
+        })
+    }
+}
+
+/// How a [`Film`] is currently being programmed, as classified by
+/// [`SessionList::classify_film`]
+#[derive(Serialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PlayingStatus {
+    /// The film has opened and has public sessions within the classification
+    /// window
+    NowPlaying,
+    /// The film has not opened yet, but tickets for some of its sessions are
+    /// already on sale
+    AdvanceSales,
+    /// The film opens in the future, or its first public session is beyond
+    /// the classification window
+    ComingSoon,
+}
 
 /// The unique ID of a [`Person`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Hash)]
 #[serde(transparent)]
-pub struct PersonId(String);
+pub struct PersonId(pub(crate) String);
 impl PersonId {
     /// Get the string representation of this [`PersonId`]
     #[must_use]
@@ -61,7 +100,7 @@ impl Display for PersonId {
 }
 
 /// A particular person associated with a [`Film`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Hash)]
 #[serde(rename_all = "PascalCase")]
 pub struct Person {
     /// The unique ID of the person
@@ -75,9 +114,9 @@ pub struct Person {
 }
 
 /// The unique ID of a [`Film`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Hash)]
 #[serde(transparent)]
-pub struct FilmId(String);
+pub struct FilmId(pub(crate) String);
 impl FilmId {
     /// Get the string representation of this [`FilmId`]
     #[must_use]
@@ -93,6 +132,16 @@ impl FilmId {
     pub async fn fetch(&self, client: &Client) -> ApiResult<Film> {
         client.get_film(self).await
     }
+
+    /// Blocking version of [`Self::fetch`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(&self, client: &blocking::Client) -> ApiResult<Film> {
+        client.block_on(self.fetch(client.inner()))
+    }
 }
 impl Display for FilmId {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -101,7 +150,7 @@ impl Display for FilmId {
 }
 
 /// A particular film in the Veezi system
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Film {
     /// The unique ID of the film
@@ -163,6 +212,16 @@ impl Film {
         Ok(client.list_sessions().await?.filter_by_film(&self.id))
     }
 
+    /// Blocking version of [`Self::sessions`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn sessions_blocking(&self, client: &blocking::Client) -> ApiResult<SessionList> {
+        client.block_on(self.sessions(client.inner()))
+    }
+
     /// Get a list of all future [Session]s for this [`Film`] that should be
     /// available for online sales.
     ///
@@ -179,6 +238,16 @@ impl Film {
         Ok(client.list_web_sessions().await?.filter_by_film(&self.id))
     }
 
+    /// Blocking version of [`Self::web_sessions`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn web_sessions_blocking(&self, client: &blocking::Client) -> ApiResult<SessionList> {
+        client.block_on(self.web_sessions(client.inner()))
+    }
+
     /// Format the duration of the film as "Xh Ym" or "Xh"
     #[must_use]
     pub fn formatted_duration(&self) -> String {
@@ -0,0 +1,989 @@
+//! A GraphQL schema over the Veezi data model
+//!
+//! The primary function is [`build_schema`], which creates a [`VeeziSchema`]
+//! that resolves queries with a shared [`Client`]. [`Session`], [`Film`],
+//! [`FilmPackage`], [`PackageFilm`], [`Screen`], [`Site`], [`Attribute`] and
+//! [`Person`] are exposed as GraphQL types, along with their relationships:
+//!
+//! - `Session.film`, `Session.filmPackage`, `Session.screen` and
+//!   `Session.attributes`
+//! - `Film.sessions`, `Screen.sessions` and `Attribute.sessions`
+//! - `FilmPackage.films` and `PackageFilm.film`
+//! - `Site.screens`
+//!
+//! Films, film packages, screens and attributes referenced by ID are fetched
+//! through a [`DataLoader`], so resolving them for a list of sessions takes a
+//! single list request rather than one request per session. Likewise the
+//! [`Site`], whose local time `Session.isOpenForSales` and
+//! `Session.isOpenForWebSales` are checked against, is fetched once per batch.
+//! The sessions behind `Film.sessions`, `Screen.sessions` and
+//! `Attribute.sessions` are batched by a [`SessionLoader`], so listing the
+//! sessions of every film takes a single session list request whether or not
+//! the [`Client`] has caching enabled.
+//!
+//! This module is only available with the `graphql` feature.
+
+use std::{collections::HashMap, hash::Hash, sync::Arc};
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Error, ID, Object, Result, Schema,
+    dataloader::{DataLoader, Loader},
+};
+use chrono::NaiveDateTime;
+use serde::de::{DeserializeOwned, IntoDeserializer, value};
+
+use crate::{
+    attr::{Attribute, AttributeId},
+    client::Client,
+    error::LibVeeziError,
+    film::{Film, FilmFormat, FilmId, FilmStatus, Person},
+    package::{FilmPackage, FilmPackageId, PackageFilm},
+    screen::{Screen, ScreenId},
+    session::{Seating, Session, SessionId, SessionList, SessionStatus, ShowType},
+    site::Site,
+};
+
+/// The GraphQL schema created by [`build_schema`]
+pub type VeeziSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Create a [`VeeziSchema`] resolving queries with a [`Client`]
+///
+/// The schema's [`DataLoader`] spawns its batches with [`tokio::spawn`], so it
+/// must be executed within a Tokio runtime.
+#[must_use]
+pub fn build_schema(client: Arc<Client>) -> VeeziSchema {
+    let loader = DataLoader::new(
+        VeeziLoader {
+            client: Arc::clone(&client),
+        },
+        tokio::spawn,
+    );
+    let session_loader = DataLoader::new(
+        SessionLoader {
+            client: Arc::clone(&client),
+        },
+        tokio::spawn,
+    );
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(client)
+        .data(loader)
+        .data(session_loader)
+        .finish()
+}
+
+/// Batches lookups of Veezi objects by ID into list requests
+pub struct VeeziLoader {
+    /// The client used to fetch the lists
+    client: Arc<Client>,
+}
+impl Loader<FilmId> for VeeziLoader {
+    type Value = Film;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(&self, keys: &[FilmId]) -> Result<HashMap<FilmId, Film>, Self::Error> {
+        Ok(self
+            .client
+            .list_films()
+            .await?
+            .into_iter()
+            .filter(|film| keys.contains(&film.id))
+            .map(|film| (film.id.clone(), film))
+            .collect())
+    }
+}
+impl Loader<FilmPackageId> for VeeziLoader {
+    type Value = FilmPackage;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(
+        &self,
+        keys: &[FilmPackageId],
+    ) -> Result<HashMap<FilmPackageId, FilmPackage>, Self::Error> {
+        Ok(self
+            .client
+            .list_film_packages()
+            .await?
+            .into_iter()
+            .filter(|package| keys.contains(&package.id))
+            .map(|package| (package.id, package))
+            .collect())
+    }
+}
+impl Loader<ScreenId> for VeeziLoader {
+    type Value = Screen;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(&self, keys: &[ScreenId]) -> Result<HashMap<ScreenId, Screen>, Self::Error> {
+        Ok(self
+            .client
+            .list_screens()
+            .await?
+            .into_iter()
+            .filter(|screen| keys.contains(&screen.id))
+            .map(|screen| (screen.id, screen))
+            .collect())
+    }
+}
+impl Loader<AttributeId> for VeeziLoader {
+    type Value = Attribute;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(
+        &self,
+        keys: &[AttributeId],
+    ) -> Result<HashMap<AttributeId, Attribute>, Self::Error> {
+        Ok(self
+            .client
+            .list_attributes()
+            .await?
+            .into_iter()
+            .filter(|attribute| keys.contains(&attribute.id))
+            .map(|attribute| (attribute.id.clone(), attribute))
+            .collect())
+    }
+}
+
+/// The key under which the [`VeeziLoader`] loads the current [`Site`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CurrentSite;
+impl Loader<CurrentSite> for VeeziLoader {
+    type Value = Site;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(&self, _keys: &[CurrentSite]) -> Result<HashMap<CurrentSite, Site>, Self::Error> {
+        Ok(HashMap::from([(
+            CurrentSite,
+            self.client.get_site().await?,
+        )]))
+    }
+}
+
+/// Batches lookups of the sessions of films, screens and attributes into a
+/// single session list request
+pub struct SessionLoader {
+    /// The client used to fetch the session lists
+    client: Arc<Client>,
+}
+
+/// The key under which the [`SessionLoader`] loads the sessions of a film that
+/// are available for web sales
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct WebSessionsOf(pub FilmId);
+
+/// Internal helper to group sessions by the keys they match, including an
+/// empty group for every key without sessions
+fn group_sessions<K: Eq + Hash + Clone>(
+    sessions: SessionList,
+    keys: &[K],
+    matches: impl Fn(&Session, &K) -> bool,
+) -> HashMap<K, Vec<Session>> {
+    let mut groups: HashMap<K, Vec<Session>> =
+        keys.iter().map(|key| (key.clone(), Vec::new())).collect();
+    for session in sessions {
+        for (key, group) in &mut groups {
+            if matches(&session, key) {
+                group.push(session.clone());
+            }
+        }
+    }
+    groups
+}
+
+impl Loader<FilmId> for SessionLoader {
+    type Value = Vec<Session>;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(&self, keys: &[FilmId]) -> Result<HashMap<FilmId, Vec<Session>>, Self::Error> {
+        let sessions = self.client.list_sessions().await?;
+        Ok(group_sessions(sessions, keys, |session, id| {
+            session.film_id == *id
+        }))
+    }
+}
+impl Loader<WebSessionsOf> for SessionLoader {
+    type Value = Vec<Session>;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(
+        &self,
+        keys: &[WebSessionsOf],
+    ) -> Result<HashMap<WebSessionsOf, Vec<Session>>, Self::Error> {
+        let sessions = self.client.list_web_sessions().await?;
+        Ok(group_sessions(
+            sessions,
+            keys,
+            |session, WebSessionsOf(id)| session.film_id == *id,
+        ))
+    }
+}
+impl Loader<ScreenId> for SessionLoader {
+    type Value = Vec<Session>;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(
+        &self,
+        keys: &[ScreenId],
+    ) -> Result<HashMap<ScreenId, Vec<Session>>, Self::Error> {
+        let sessions = self.client.list_sessions().await?;
+        Ok(group_sessions(sessions, keys, |session, id| {
+            session.screen_id == *id
+        }))
+    }
+}
+impl Loader<AttributeId> for SessionLoader {
+    type Value = Vec<Session>;
+    type Error = Arc<LibVeeziError>;
+
+    async fn load(
+        &self,
+        keys: &[AttributeId],
+    ) -> Result<HashMap<AttributeId, Vec<Session>>, Self::Error> {
+        let sessions = self.client.list_sessions().await?;
+        Ok(group_sessions(sessions, keys, |session, id| {
+            session.attributes.contains(id)
+        }))
+    }
+}
+
+/// Internal helper to get the [`Client`] of a query
+fn client<'a>(ctx: &Context<'a>) -> Result<&'a Arc<Client>> {
+    ctx.data::<Arc<Client>>()
+}
+
+/// Internal helper to get the [`VeeziLoader`] of a query
+fn loader<'a>(ctx: &Context<'a>) -> Result<&'a DataLoader<VeeziLoader>> {
+    ctx.data::<DataLoader<VeeziLoader>>()
+}
+
+/// Internal helper to load the sessions of a film, screen or attribute through
+/// the [`SessionLoader`] of a query
+async fn load_sessions<K>(ctx: &Context<'_>, key: K) -> Result<Vec<SessionObject>>
+where
+    K: Send + Sync + Hash + Eq + Clone + 'static,
+    SessionLoader: Loader<K, Value = Vec<Session>, Error = Arc<LibVeeziError>>,
+{
+    Ok(ctx
+        .data::<DataLoader<SessionLoader>>()?
+        .load_one(key)
+        .await?
+        .unwrap_or_default()
+        .into_iter()
+        .map(SessionObject)
+        .collect())
+}
+
+/// Internal helper to get the current local time at the [`Site`], which is
+/// what session times are compared against
+async fn site_now(ctx: &Context<'_>) -> Result<NaiveDateTime> {
+    loader(ctx)?
+        .load_one(CurrentSite)
+        .await?
+        .map(|site| site.now())
+        .ok_or_else(|| Error::new("site not found"))
+}
+
+/// Internal helper to parse a GraphQL [`ID`] into a numeric Veezi ID
+fn numeric_id<T: DeserializeOwned>(id: &ID) -> Result<T> {
+    let number: u32 = id
+        .parse()
+        .map_err(|_| Error::new(format!("invalid ID {:?}", id.as_str())))?;
+    T::deserialize(IntoDeserializer::<value::Error>::into_deserializer(number))
+        .map_err(|err| Error::new(err.to_string()))
+}
+
+/// Internal helper to parse a GraphQL [`ID`] into a string Veezi ID
+fn string_id<T: DeserializeOwned>(id: &ID) -> Result<T> {
+    T::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+        id.as_str(),
+    ))
+    .map_err(|err| Error::new(err.to_string()))
+}
+
+/// Internal helper to convert a [`SessionList`] into GraphQL objects
+fn session_objects(sessions: SessionList) -> Vec<SessionObject> {
+    sessions.into_vec().into_iter().map(SessionObject).collect()
+}
+
+/// The root of every GraphQL query
+pub struct QueryRoot;
+#[Object]
+impl QueryRoot {
+    /// List future sessions, optionally filtered by film, screen, attribute
+    /// and time range, or only those available for web sales
+    #[allow(clippy::too_many_arguments)]
+    async fn sessions(
+        &self,
+        ctx: &Context<'_>,
+        film: Option<ID>,
+        screen: Option<ID>,
+        attribute: Option<ID>,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+        #[graphql(default = false)] web: bool,
+    ) -> Result<Vec<SessionObject>> {
+        let client = client(ctx)?;
+        let mut sessions = if web {
+            client.list_web_sessions().await?
+        } else {
+            client.list_sessions().await?
+        };
+        if let Some(film) = film {
+            sessions = sessions.filter_by_film(&string_id(&film)?);
+        }
+        if let Some(screen) = screen {
+            sessions = sessions.filter_by_screen(numeric_id(&screen)?);
+        }
+        if let Some(attribute) = attribute {
+            sessions = sessions.filter_containing_attribute(&string_id(&attribute)?);
+        }
+        if from.is_some() || to.is_some() {
+            sessions = sessions.filter_by_time_range(
+                from.unwrap_or(NaiveDateTime::MIN),
+                to.unwrap_or(NaiveDateTime::MAX),
+            );
+        }
+        Ok(session_objects(sessions))
+    }
+
+    /// Get a session by ID
+    async fn session(&self, ctx: &Context<'_>, id: ID) -> Result<SessionObject> {
+        let id: SessionId = numeric_id(&id)?;
+        Ok(SessionObject(client(ctx)?.get_session(id).await?))
+    }
+
+    /// List all films
+    async fn films(&self, ctx: &Context<'_>) -> Result<Vec<FilmObject>> {
+        Ok(client(ctx)?
+            .list_films()
+            .await?
+            .into_iter()
+            .map(FilmObject)
+            .collect())
+    }
+
+    /// Get a film by ID
+    async fn film(&self, ctx: &Context<'_>, id: ID) -> Result<FilmObject> {
+        let id: FilmId = string_id(&id)?;
+        Ok(FilmObject(client(ctx)?.get_film(&id).await?))
+    }
+
+    /// List all film packages
+    async fn film_packages(&self, ctx: &Context<'_>) -> Result<Vec<FilmPackageObject>> {
+        Ok(client(ctx)?
+            .list_film_packages()
+            .await?
+            .into_iter()
+            .map(FilmPackageObject)
+            .collect())
+    }
+
+    /// Get a film package by ID
+    async fn film_package(&self, ctx: &Context<'_>, id: ID) -> Result<FilmPackageObject> {
+        let id: FilmPackageId = numeric_id(&id)?;
+        Ok(FilmPackageObject(client(ctx)?.get_film_package(id).await?))
+    }
+
+    /// List all screens
+    async fn screens(&self, ctx: &Context<'_>) -> Result<Vec<ScreenObject>> {
+        Ok(client(ctx)?
+            .list_screens()
+            .await?
+            .into_iter()
+            .map(ScreenObject)
+            .collect())
+    }
+
+    /// Get a screen by ID
+    async fn screen(&self, ctx: &Context<'_>, id: ID) -> Result<ScreenObject> {
+        let id: ScreenId = numeric_id(&id)?;
+        Ok(ScreenObject(client(ctx)?.get_screen(id).await?))
+    }
+
+    /// List all attributes
+    async fn attributes(&self, ctx: &Context<'_>) -> Result<Vec<AttributeObject>> {
+        Ok(client(ctx)?
+            .list_attributes()
+            .await?
+            .into_iter()
+            .map(AttributeObject)
+            .collect())
+    }
+
+    /// Get an attribute by ID
+    async fn attribute(&self, ctx: &Context<'_>, id: ID) -> Result<AttributeObject> {
+        let id: AttributeId = string_id(&id)?;
+        Ok(AttributeObject(client(ctx)?.get_attribute(&id).await?))
+    }
+
+    /// Get the site
+    async fn site(&self, ctx: &Context<'_>) -> Result<SiteObject> {
+        Ok(SiteObject(client(ctx)?.get_site().await?))
+    }
+}
+
+/// A [`Session`] exposed as the GraphQL `Session` type
+pub struct SessionObject(pub Session);
+#[Object(name = "Session")]
+impl SessionObject {
+    /// The unique ID of the session
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The ID of the film being shown in this session
+    async fn film_id(&self) -> ID {
+        ID(self.0.film_id.to_string())
+    }
+
+    /// The film being shown in this session
+    async fn film(&self, ctx: &Context<'_>) -> Result<Option<FilmObject>> {
+        Ok(loader(ctx)?
+            .load_one(self.0.film_id.clone())
+            .await?
+            .map(FilmObject))
+    }
+
+    /// The ID of the film package (if any) associated with this session
+    async fn film_package_id(&self) -> Option<ID> {
+        self.0.film_package_id.map(|id| ID(id.to_string()))
+    }
+
+    /// The film package (if any) associated with this session
+    async fn film_package(&self, ctx: &Context<'_>) -> Result<Option<FilmPackageObject>> {
+        let Some(id) = self.0.film_package_id else {
+            return Ok(None);
+        };
+        Ok(loader(ctx)?.load_one(id).await?.map(FilmPackageObject))
+    }
+
+    /// The title of the film being shown in this session
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    /// The screen ID where this session is being shown
+    async fn screen_id(&self) -> ID {
+        ID(self.0.screen_id.to_string())
+    }
+
+    /// The screen where this session is being shown
+    async fn screen(&self, ctx: &Context<'_>) -> Result<Option<ScreenObject>> {
+        Ok(loader(ctx)?
+            .load_one(self.0.screen_id)
+            .await?
+            .map(ScreenObject))
+    }
+
+    /// The seating type for this session
+    async fn seating(&self) -> Seating {
+        self.0.seating
+    }
+
+    /// Whether complimentary tickets are allowed for this session
+    async fn are_complimentaries_allowed(&self) -> bool {
+        self.0.are_complimentaries_allowed
+    }
+
+    /// The show type for this session
+    async fn show_type(&self) -> ShowType {
+        self.0.show_type
+    }
+
+    /// The sales channels via which tickets for this session can be sold
+    async fn sales_via(&self) -> Vec<&'static str> {
+        self.0.sales_via.channels()
+    }
+
+    /// The status of this session
+    async fn status(&self) -> SessionStatus {
+        self.0.status
+    }
+
+    /// The time this session starts
+    async fn pre_show_start_time(&self) -> NaiveDateTime {
+        self.0.pre_show_start_time
+    }
+
+    /// The time this session ends sales
+    async fn sales_cut_off_time(&self) -> NaiveDateTime {
+        self.0.sales_cut_off_time
+    }
+
+    /// The time this session's feature starts
+    async fn feature_start_time(&self) -> NaiveDateTime {
+        self.0.feature_start_time
+    }
+
+    /// The time this session's feature ends
+    async fn feature_end_time(&self) -> NaiveDateTime {
+        self.0.feature_end_time
+    }
+
+    /// The time this session's cleanup ends
+    async fn cleanup_end_time(&self) -> NaiveDateTime {
+        self.0.cleanup_end_time
+    }
+
+    /// Whether tickets for this session are sold out
+    async fn tickets_sold_out(&self) -> bool {
+        self.0.tickets_sold_out
+    }
+
+    /// Whether there are few tickets left for this session
+    async fn few_tickets_left(&self) -> bool {
+        self.0.few_tickets_left
+    }
+
+    /// The number of seats available for this session
+    async fn seats_available(&self) -> u32 {
+        self.0.seats_available
+    }
+
+    /// The number of seats held for this session
+    async fn seats_held(&self) -> u32 {
+        self.0.seats_held
+    }
+
+    /// The number of house seats for this session
+    async fn seats_house(&self) -> u32 {
+        self.0.seats_house
+    }
+
+    /// The number of seats sold for this session
+    async fn seats_sold(&self) -> u32 {
+        self.0.seats_sold
+    }
+
+    /// The format of the film being shown in this session
+    async fn film_format(&self) -> FilmFormat {
+        self.0.film_format
+    }
+
+    /// The price card name associated with this session
+    async fn price_card_name(&self) -> &str {
+        &self.0.price_card_name
+    }
+
+    /// The list of attribute IDs associated with this session
+    async fn attribute_ids(&self) -> Vec<ID> {
+        self.0
+            .attributes
+            .iter()
+            .map(|id| ID(id.to_string()))
+            .collect()
+    }
+
+    /// The attributes associated with this session
+    async fn attributes(&self, ctx: &Context<'_>) -> Result<Vec<AttributeObject>> {
+        let mut attributes = loader(ctx)?
+            .load_many(self.0.attributes.iter().cloned())
+            .await?;
+        Ok(self
+            .0
+            .attributes
+            .iter()
+            .filter_map(|id| attributes.remove(id))
+            .map(AttributeObject)
+            .collect())
+    }
+
+    /// The audio language of the film being shown in this session
+    async fn audio_language(&self) -> Option<&str> {
+        self.0.audio_language.as_deref()
+    }
+
+    /// Whether tickets can currently be sold for this session
+    async fn is_open_for_sales(&self, ctx: &Context<'_>) -> Result<bool> {
+        Ok(self.0.is_open_for_sales_at(site_now(ctx).await?))
+    }
+
+    /// Whether tickets can currently be sold online for this session
+    async fn is_open_for_web_sales(&self, ctx: &Context<'_>) -> Result<bool> {
+        Ok(self.0.is_open_for_web_sales_at(site_now(ctx).await?))
+    }
+}
+
+/// A [`Person`] exposed as the GraphQL `Person` type
+pub struct PersonObject(pub Person);
+#[Object(name = "Person")]
+impl PersonObject {
+    /// The unique ID of the person
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The first name of the person
+    async fn first_name(&self) -> &str {
+        &self.0.first_name
+    }
+
+    /// The last name of the person
+    async fn last_name(&self) -> &str {
+        &self.0.last_name
+    }
+
+    /// The role of the person in the film (e.g., "Actor", "Director")
+    async fn role(&self) -> &str {
+        &self.0.role
+    }
+}
+
+/// A [`Film`] exposed as the GraphQL `Film` type
+pub struct FilmObject(pub Film);
+#[Object(name = "Film")]
+impl FilmObject {
+    /// The unique ID of the film
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The title of the film
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    /// The short name of the film
+    async fn short_name(&self) -> &str {
+        &self.0.short_name
+    }
+
+    /// The synopsis of the film
+    async fn synopsis(&self) -> Option<&str> {
+        self.0.synopsis.as_deref()
+    }
+
+    /// The genre of the film
+    async fn genre(&self) -> &str {
+        &self.0.genre
+    }
+
+    /// The signage text of the film
+    async fn signage_text(&self) -> &str {
+        &self.0.signage_text
+    }
+
+    /// The distributor of the film
+    async fn distributor(&self) -> &str {
+        &self.0.distributor
+    }
+
+    /// The opening date of the film
+    async fn opening_date(&self) -> NaiveDateTime {
+        self.0.opening_date
+    }
+
+    /// The rating of the film
+    async fn rating(&self) -> Option<&str> {
+        self.0.rating.as_deref()
+    }
+
+    /// The status of the film
+    async fn status(&self) -> FilmStatus {
+        self.0.status
+    }
+
+    /// The content advice of the film
+    async fn content(&self) -> Option<&str> {
+        self.0.content.as_deref()
+    }
+
+    /// The duration of the film in minutes
+    async fn duration(&self) -> u32 {
+        self.0.duration
+    }
+
+    /// The duration of the film as "Xh Ym" or "Xh"
+    async fn formatted_duration(&self) -> String {
+        self.0.formatted_duration()
+    }
+
+    /// The display sequence of the film
+    async fn display_sequence(&self) -> u32 {
+        self.0.display_sequence
+    }
+
+    /// The national code of the film
+    async fn national_code(&self) -> Option<&str> {
+        self.0.national_code.as_deref()
+    }
+
+    /// The format of the film
+    async fn format(&self) -> FilmFormat {
+        self.0.format
+    }
+
+    /// Whether the film is restricted
+    async fn is_restricted(&self) -> bool {
+        self.0.is_restricted
+    }
+
+    /// The people involved in the film
+    async fn people(&self) -> Vec<PersonObject> {
+        self.0.people.iter().cloned().map(PersonObject).collect()
+    }
+
+    /// The audio language of the film
+    async fn audio_language(&self) -> Option<&str> {
+        self.0.audio_language.as_deref()
+    }
+
+    /// The government title of the film
+    async fn government_film_title(&self) -> Option<&str> {
+        self.0.government_film_title.as_deref()
+    }
+
+    /// The URL of the film's poster
+    async fn film_poster_url(&self) -> Option<&str> {
+        self.0.film_poster_url.as_deref()
+    }
+
+    /// The URL of the film's poster thumbnail
+    async fn film_poster_thumbnail_url(&self) -> &str {
+        &self.0.film_poster_thumbnail_url
+    }
+
+    /// The URL of the film's backdrop image
+    async fn backdrop_image_url(&self) -> Option<&str> {
+        self.0.backdrop_image_url.as_deref()
+    }
+
+    /// The URL of the film's trailer
+    async fn film_trailer_url(&self) -> Option<&str> {
+        self.0.film_trailer_url.as_deref()
+    }
+
+    /// The future sessions of the film, or only those available for web
+    /// sales
+    async fn sessions(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] web: bool,
+    ) -> Result<Vec<SessionObject>> {
+        if web {
+            load_sessions(ctx, WebSessionsOf(self.0.id.clone())).await
+        } else {
+            load_sessions(ctx, self.0.id.clone()).await
+        }
+    }
+}
+
+/// A [`PackageFilm`] exposed as the GraphQL `PackageFilm` type
+pub struct PackageFilmObject(pub PackageFilm);
+#[Object(name = "PackageFilm")]
+impl PackageFilmObject {
+    /// The unique ID of the film
+    async fn film_id(&self) -> ID {
+        ID(self.0.film_id.to_string())
+    }
+
+    /// The film
+    async fn film(&self, ctx: &Context<'_>) -> Result<Option<FilmObject>> {
+        Ok(loader(ctx)?
+            .load_one(self.0.film_id.clone())
+            .await?
+            .map(FilmObject))
+    }
+
+    /// The title of the film
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    /// What percent of the box office this film receives within the package
+    async fn split_percent(&self) -> f32 {
+        self.0.split_percent
+    }
+
+    /// The duration of the trailers for this film in minutes
+    async fn trailer_duration(&self) -> u32 {
+        self.0.trailer_duration
+    }
+
+    /// The duration of the cleaning up after this film in minutes
+    async fn clean_up_duration(&self) -> u32 {
+        self.0.clean_up_duration
+    }
+
+    /// The order of this film within the package
+    async fn order(&self) -> u32 {
+        self.0.order
+    }
+}
+
+/// A [`FilmPackage`] exposed as the GraphQL `FilmPackage` type
+pub struct FilmPackageObject(pub FilmPackage);
+#[Object(name = "FilmPackage")]
+impl FilmPackageObject {
+    /// The unique ID of the film package
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The title of the film package
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    /// The current status of the film package
+    async fn status(&self) -> FilmStatus {
+        self.0.status
+    }
+
+    /// The films within this package
+    async fn films(&self) -> Vec<PackageFilmObject> {
+        self.0
+            .films
+            .iter()
+            .cloned()
+            .map(PackageFilmObject)
+            .collect()
+    }
+}
+
+/// A [`Screen`] exposed as the GraphQL `Screen` type
+pub struct ScreenObject(pub Screen);
+#[Object(name = "Screen")]
+impl ScreenObject {
+    /// The unique ID of the screen
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The name of the screen
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// The screen number as a string
+    async fn screen_number(&self) -> &str {
+        &self.0.screen_number
+    }
+
+    /// Whether the screen has a custom layout
+    async fn has_custom_layout(&self) -> bool {
+        self.0.has_custom_layout
+    }
+
+    /// The total number of seats in the screen
+    async fn total_seats(&self) -> u32 {
+        self.0.total_seats
+    }
+
+    /// The number of house seats in the screen
+    async fn house_seats(&self) -> u32 {
+        self.0.house_seats
+    }
+
+    /// The future sessions on the screen
+    async fn sessions(&self, ctx: &Context<'_>) -> Result<Vec<SessionObject>> {
+        load_sessions(ctx, self.0.id).await
+    }
+}
+
+/// An [`Attribute`] exposed as the GraphQL `Attribute` type
+pub struct AttributeObject(pub Attribute);
+#[Object(name = "Attribute")]
+impl AttributeObject {
+    /// The unique ID of the attribute
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The name of the attribute
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    /// The short name of the attribute
+    async fn short_name(&self) -> &str {
+        &self.0.short_name
+    }
+
+    /// The font color associated with the attribute (hex code)
+    async fn font_color(&self) -> &str {
+        &self.0.font_color
+    }
+
+    /// The background color associated with the attribute (hex code)
+    async fn background_color(&self) -> &str {
+        &self.0.background_color
+    }
+
+    /// Whether to show this attribute on sessions that have no complimentary
+    /// tickets
+    async fn show_on_sessions_with_no_comps(&self) -> bool {
+        self.0.show_on_sessions_with_no_comps
+    }
+
+    /// The future sessions with the attribute
+    async fn sessions(&self, ctx: &Context<'_>) -> Result<Vec<SessionObject>> {
+        load_sessions(ctx, self.0.id.clone()).await
+    }
+}
+
+/// A [`Site`] exposed as the GraphQL `Site` type
+pub struct SiteObject(pub Site);
+#[Object(name = "Site")]
+impl SiteObject {
+    /// The name of the site
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// The short name of the site
+    async fn short_name(&self) -> &str {
+        &self.0.short_name
+    }
+
+    /// The legal name of the site
+    async fn legal_name(&self) -> &str {
+        &self.0.legal_name
+    }
+
+    /// The national code of the site
+    async fn national_code(&self) -> Option<&str> {
+        self.0.national_code.as_deref()
+    }
+
+    /// The lines of the site's address
+    async fn address_lines(&self) -> Vec<&str> {
+        self.0.address_lines()
+    }
+
+    /// The site's postal code
+    async fn post_code(&self) -> Option<&str> {
+        self.0.post_code.as_deref()
+    }
+
+    /// The site's primary phone number
+    async fn phone_1(&self) -> Option<&str> {
+        self.0.phone_1.as_deref()
+    }
+
+    /// The site's secondary phone number
+    async fn phone_2(&self) -> Option<&str> {
+        self.0.phone_2.as_deref()
+    }
+
+    /// The time zone identifier for the site
+    async fn time_zone_identifier(&self) -> &str {
+        &self.0.time_zone_identifier
+    }
+
+    /// The current local time at the site
+    async fn now(&self) -> NaiveDateTime {
+        self.0.now()
+    }
+
+    /// The country where the site is located
+    async fn country(&self) -> &str {
+        &self.0.country
+    }
+
+    /// The screens at the site
+    async fn screens(&self, ctx: &Context<'_>) -> Result<Vec<ScreenObject>> {
+        let mut screens = client(ctx)?.list_screens().await?;
+        screens.retain(|screen| self.0.screens.contains(&screen.id.into_u32()));
+        Ok(screens.into_iter().map(ScreenObject).collect())
+    }
+}
@@ -0,0 +1,340 @@
+//! iCalendar ([RFC 5545](https://www.rfc-editor.org/rfc/rfc5545)) export of
+//! [`Session`]s
+//!
+//! Each session becomes one `VEVENT` spanning its feature, titled with the film
+//! title and located at its [`Screen`] within the [`Site`]. The
+//! [`crate::session::SessionId`] and the site make up the stable `UID` (see
+//! [`session_uid`]), so calendar clients update existing events rather than
+//! duplicating them when a subscribed calendar is refreshed.
+//!
+//! Session times are local to the site. If [`Site::time_zone_identifier`] is a
+//! known IANA time zone, times are emitted with a `TZID` parameter and a
+//! matching `VTIMEZONE` component covering every transition of the zone
+//! between the first and last event; otherwise they are emitted as floating
+//! local times.
+
+use chrono::{DateTime, NaiveDateTime, Offset, TimeDelta, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+
+use crate::{screen::Screen, session::Session, site::Site};
+
+/// The `PRODID` of calendars generated by this module
+const PRODUCT_ID: &str = concat!("-//libveezi//libveezi ", env!("CARGO_PKG_VERSION"), "//EN");
+
+/// The maximum length of a content line in octets, excluding the line break
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Internal helper to escape a `TEXT` property value
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Internal helper to append a content line, folding it at
+/// [`MAX_LINE_OCTETS`] without splitting UTF-8 characters
+fn push_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space of a continuation line counts towards its length
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Internal helper to format a local `DATE-TIME` value
+fn format_local(time: NaiveDateTime) -> String {
+    time.format("%Y%m%dT%H%M%S").to_string()
+}
+
+/// Internal helper to format a `UTC-OFFSET` value
+fn format_offset(offset_seconds: i32) -> String {
+    let sign = if offset_seconds < 0 { '-' } else { '+' };
+    let seconds = offset_seconds.unsigned_abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if seconds == 0 {
+        format!("{sign}{hours:02}{minutes:02}")
+    } else {
+        format!("{sign}{hours:02}{minutes:02}{seconds:02}")
+    }
+}
+
+/// Internal helper to get the UTC offset in effect at an instant, in seconds
+fn offset_at(tz: Tz, instant: DateTime<Utc>) -> i32 {
+    tz.offset_from_utc_datetime(&instant.naive_utc())
+        .fix()
+        .local_minus_utc()
+}
+
+/// Internal helper to find the first instant in `(before, after]` at which the
+/// UTC offset of `tz` differs from the offset at `before`
+fn find_transition(tz: Tz, mut before: DateTime<Utc>, mut after: DateTime<Utc>) -> DateTime<Utc> {
+    let offset = offset_at(tz, before);
+    while after - before > TimeDelta::seconds(1) {
+        let middle = before + (after - before) / 2;
+        if offset_at(tz, middle) == offset {
+            before = middle;
+        } else {
+            after = middle;
+        }
+    }
+    after
+}
+
+/// Internal helper to append a `STANDARD` or `DAYLIGHT` observance that starts
+/// at the given instant
+fn push_observance(out: &mut String, tz: Tz, start: DateTime<Utc>, offset_from: i32) {
+    let offset = tz.offset_from_utc_datetime(&start.naive_utc());
+    let kind = if offset.dst_offset().is_zero() {
+        "STANDARD"
+    } else {
+        "DAYLIGHT"
+    };
+    let offset_to = offset.fix().local_minus_utc();
+    let local_start = start.naive_utc() + TimeDelta::seconds(i64::from(offset_from));
+
+    push_line(out, &format!("BEGIN:{kind}"));
+    push_line(out, &format!("DTSTART:{}", format_local(local_start)));
+    push_line(out, &format!("TZOFFSETFROM:{}", format_offset(offset_from)));
+    push_line(out, &format!("TZOFFSETTO:{}", format_offset(offset_to)));
+    if let Some(name) = offset.abbreviation() {
+        push_line(out, &format!("TZNAME:{}", escape_text(name)));
+    }
+    push_line(out, &format!("END:{kind}"));
+}
+
+/// The interval at which [`push_time_zone`] samples the UTC offset of a time
+/// zone to find its transitions
+const TRANSITION_SCAN_STEP: TimeDelta = TimeDelta::hours(1);
+
+/// Internal helper to append a `VTIMEZONE` component describing `tz` over the
+/// span of the given local times
+///
+/// Every transition of `tz` between the earliest and latest time gets its own
+/// observance, whether or not an event falls on each side of it.
+fn push_time_zone(out: &mut String, tz: Tz, times: &[NaiveDateTime]) {
+    let instants: Vec<DateTime<Utc>> = times
+        .iter()
+        .filter_map(|time| tz.from_local_datetime(time).earliest())
+        .map(|time| time.with_timezone(&Utc))
+        .collect();
+    let (Some(first), Some(last)) = (
+        instants.iter().min().copied(),
+        instants.iter().max().copied(),
+    ) else {
+        return;
+    };
+
+    push_line(out, "BEGIN:VTIMEZONE");
+    push_line(out, &format!("TZID:{}", tz.name()));
+    // The first observance covers everything up to the first event
+    push_observance(out, tz, first, offset_at(tz, first));
+    let mut before = first;
+    while before < last {
+        let after = (before + TRANSITION_SCAN_STEP).min(last);
+        let offset_from = offset_at(tz, before);
+        if offset_from == offset_at(tz, after) {
+            before = after;
+        } else {
+            let transition = find_transition(tz, before, after);
+            push_observance(out, tz, transition, offset_from);
+            before = transition;
+        }
+    }
+    push_line(out, "END:VTIMEZONE");
+}
+
+/// Internal helper to append a `VEVENT` for a single session
+fn push_event(
+    out: &mut String,
+    session: &Session,
+    screen: Option<&Screen>,
+    site: &Site,
+    tz: Option<Tz>,
+    stamp: &str,
+) {
+    let tzid = tz
+        .map(|tz| format!(";TZID={}", tz.name()))
+        .unwrap_or_default();
+    let time_property =
+        |name: &str, time: NaiveDateTime| format!("{name}{tzid}:{}", format_local(time));
+
+    let mut location = Vec::new();
+    if let Some(screen) = screen {
+        location.push(screen.name.as_str());
+    }
+    location.push(site.name.as_str());
+    location.extend(site.address_lines());
+
+    push_line(out, "BEGIN:VEVENT");
+    push_line(out, &format!("UID:{}", session_uid(session, site)));
+    push_line(out, &format!("DTSTAMP:{stamp}"));
+    push_line(out, &time_property("DTSTART", session.feature_start_time));
+    push_line(out, &time_property("DTEND", session.feature_end_time));
+    push_line(out, &format!("SUMMARY:{}", escape_text(&session.title)));
+    push_line(
+        out,
+        &format!("LOCATION:{}", escape_text(&location.join(", "))),
+    );
+    push_line(out, "END:VEVENT");
+}
+
+/// Get the stable iCalendar `UID` used for a [`Session`] at a [`Site`]
+///
+/// Session IDs are only unique within a site, so the UID also includes the
+/// site's [`Site::short_name`] (or [`Site::name`] if it has none), lowercased
+/// with anything other than ASCII letters and digits replaced by `-`.
+#[must_use]
+pub fn session_uid(session: &Session, site: &Site) -> String {
+    let name = if site.short_name.trim().is_empty() {
+        &site.name
+    } else {
+        &site.short_name
+    };
+    let site_id: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("session-{}.{site_id}@libveezi", session.id)
+}
+
+/// Render an iCalendar document with one `VEVENT` per session
+///
+/// `screens` provides the screen names used in each event's `LOCATION`;
+/// sessions on screens not in the list are located at the site only.
+#[must_use]
+pub fn to_ical<'a>(
+    sessions: impl IntoIterator<Item = &'a Session>,
+    screens: &[Screen],
+    site: &Site,
+) -> String {
+    let sessions: Vec<&Session> = sessions.into_iter().collect();
+    let tz = site.time_zone();
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:{PRODUCT_ID}"));
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "METHOD:PUBLISH");
+    push_line(
+        &mut out,
+        &format!("X-WR-CALNAME:{}", escape_text(&site.name)),
+    );
+    if let Some(tz) = tz {
+        push_line(&mut out, &format!("X-WR-TIMEZONE:{}", tz.name()));
+        let times: Vec<NaiveDateTime> = sessions
+            .iter()
+            .flat_map(|session| [session.feature_start_time, session.feature_end_time])
+            .collect();
+        push_time_zone(&mut out, tz, &times);
+    }
+    for session in sessions {
+        let screen = screens.iter().find(|screen| screen.id == session.screen_id);
+        push_event(&mut out, session, screen, site, tz, &stamp);
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for rendering iCalendar documents
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder, SiteBuilder};
+
+    /// Internal helper to build a session starting at 7pm on a day of 2025
+    fn session_on(id: u32, month: u32, day: u32) -> Session {
+        let film = FilmBuilder::new("ST00000001")
+            .with_title("Fire, Ice; and \\Back")
+            .build();
+        let screen = ScreenBuilder::new(1).build();
+        let start = NaiveDate::from_ymd_opt(2025, month, day)
+            .and_then(|date| date.and_hms_opt(19, 0, 0))
+            .expect("the start time should exist");
+        SessionBuilder::new(id, &film, &screen, start).build()
+    }
+
+    #[test]
+    fn events_are_escaped_and_located() {
+        let site = SiteBuilder::new().build();
+        let session = session_on(1, 6, 1);
+        let ical = to_ical([&session], &[ScreenBuilder::new(1).build()], &site);
+
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ical.ends_with("END:VCALENDAR\r\n"));
+        assert!(ical.contains("SUMMARY:Fire\\, Ice\\; and \\\\Back\r\n"));
+        assert!(ical.contains("DTSTART;TZID=America/Los_Angeles:20250601T"));
+        assert!(ical.contains("LOCATION:Screen 1\\, Libveezi Cinema"));
+        assert!(ical.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS));
+    }
+
+    #[test]
+    fn unknown_time_zones_use_floating_times() {
+        let site = SiteBuilder::new().with_time_zone("Nowhere/Special").build();
+        let ical = to_ical([&session_on(1, 6, 1)], &[], &site);
+
+        assert!(!ical.contains("VTIMEZONE"));
+        assert!(ical.contains("DTSTART:20250601T"));
+    }
+
+    #[test]
+    fn time_zones_cover_transitions_between_events() {
+        let site = SiteBuilder::new().build();
+        let (january, december) = (session_on(1, 1, 10), session_on(2, 12, 10));
+        let ical = to_ical([&december, &january], &[], &site);
+
+        assert!(ical.contains(
+            "BEGIN:DAYLIGHT\r\nDTSTART:20250309T020000\r\nTZOFFSETFROM:-0800\r\n\
+             TZOFFSETTO:-0700\r\n"
+        ));
+        assert!(ical.contains(
+            "BEGIN:STANDARD\r\nDTSTART:20251102T020000\r\nTZOFFSETFROM:-0700\r\n\
+             TZOFFSETTO:-0800\r\n"
+        ));
+        assert_eq!(ical.matches("BEGIN:STANDARD").count(), 2);
+    }
+
+    #[test]
+    fn uids_include_the_site() {
+        let session = session_on(42, 6, 1);
+        let downtown = SiteBuilder::new()
+            .with_name("Downtown", "Down Town")
+            .build();
+        let riverside = SiteBuilder::new().with_name("Riverside", "").build();
+
+        assert_eq!(
+            session_uid(&session, &downtown),
+            "session-42.down-town@libveezi"
+        );
+        assert_eq!(
+            session_uid(&session, &riverside),
+            "session-42.riverside@libveezi"
+        );
+    }
+}
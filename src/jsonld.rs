@@ -0,0 +1,366 @@
+//! [Schema.org](https://schema.org) JSON-LD markup for showtime pages
+//!
+//! The primary type is [`ScreeningEvent`], which combines a [`Session`] with
+//! its [`Film`], [`Screen`] and [`Site`] into a schema.org `ScreeningEvent`
+//! whose `workPresented` is a schema.org `Movie`. Serialize it with
+//! [`ScreeningEvent::to_json_ld`], which escapes `<` so the output is safe to
+//! embed in a `<script type="application/ld+json">` element.
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::{
+    film::{Film, FilmFormat},
+    screen::Screen,
+    session::{Session, SessionList},
+    site::Site,
+};
+
+/// The JSON-LD context used by every top-level object in this module
+const SCHEMA_CONTEXT: &str = "https://schema.org";
+
+/// Internal helper to format a local site time as an ISO 8601 date-time,
+/// including the UTC offset if the site's time zone is known
+fn format_time(site: &Site, time: NaiveDateTime) -> String {
+    site.localize(time).map_or_else(
+        || time.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        |time| time.to_rfc3339(),
+    )
+}
+
+/// Internal helper to format a number of minutes as an ISO 8601 duration
+fn format_duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("PT{minutes}M"),
+        (hours, 0) => format!("PT{hours}H"),
+        (hours, minutes) => format!("PT{hours}H{minutes}M"),
+    }
+}
+
+/// Internal helper to map a [`FilmFormat`] to a schema.org `videoFormat`
+const fn video_format(format: FilmFormat) -> Option<&'static str> {
+    match format {
+        FilmFormat::Film2D | FilmFormat::Digital2D => Some("2D"),
+        FilmFormat::Digital3D => Some("3D"),
+        FilmFormat::Digital3DHFR => Some("3D HFR"),
+        FilmFormat::NotAFilm => None,
+    }
+}
+
+/// A schema.org `Person`
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct Person {
+    /// The schema.org type, always `Person`
+    #[serde(rename = "@type")]
+    pub kind: &'static str,
+    /// The full name of the person
+    pub name: String,
+}
+
+/// A schema.org `Movie`
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Movie {
+    /// The schema.org type, always `Movie`
+    #[serde(rename = "@type")]
+    pub kind: &'static str,
+    /// The title of the film
+    pub name: String,
+    /// The synopsis of the film
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The poster of the film
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// The runtime of the film as an ISO 8601 duration
+    pub duration: String,
+    /// The rating of the film (e.g., "PG-13")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_rating: Option<String>,
+    /// The genre of the film
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub genre: String,
+    /// The opening date of the film
+    pub date_published: String,
+    /// The trailer of the film
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trailer: Option<String>,
+    /// The directors of the film
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub director: Vec<Person>,
+    /// The actors in the film
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actor: Vec<Person>,
+}
+impl Movie {
+    /// Create a schema.org `Movie` from a [`Film`]
+    #[must_use]
+    pub fn new(film: &Film) -> Self {
+        let people_with_role = |role: &str| {
+            film.people
+                .iter()
+                .filter(|person| person.role.eq_ignore_ascii_case(role))
+                .map(|person| Person {
+                    kind: "Person",
+                    name: format!("{} {}", person.first_name, person.last_name)
+                        .trim()
+                        .to_string(),
+                })
+                .collect()
+        };
+
+        Self {
+            kind: "Movie",
+            name: film.title.clone(),
+            description: film.synopsis.clone(),
+            image: Some(
+                film.film_poster_url
+                    .clone()
+                    .unwrap_or_else(|| film.film_poster_thumbnail_url.clone()),
+            )
+            .filter(|url| !url.is_empty()),
+            duration: format_duration(film.duration),
+            content_rating: film.rating.clone(),
+            genre: film.genre.clone(),
+            date_published: film.opening_date.date().to_string(),
+            trailer: film.film_trailer_url.clone(),
+            director: people_with_role("Director"),
+            actor: people_with_role("Actor"),
+        }
+    }
+}
+
+/// A schema.org `PostalAddress`
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PostalAddress {
+    /// The schema.org type, always `PostalAddress`
+    #[serde(rename = "@type")]
+    pub kind: &'static str,
+    /// The street address lines, joined with commas
+    pub street_address: String,
+    /// The postal code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    /// The country
+    pub address_country: String,
+}
+
+/// A schema.org `Place`, used for the screen within a [`MovieTheater`]
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct Place {
+    /// The schema.org type, always `Place`
+    #[serde(rename = "@type")]
+    pub kind: &'static str,
+    /// The name of the screen
+    pub name: String,
+}
+
+/// A schema.org `MovieTheater`
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MovieTheater {
+    /// The schema.org type, always `MovieTheater`
+    #[serde(rename = "@type")]
+    pub kind: &'static str,
+    /// The name of the site
+    pub name: String,
+    /// The address of the site
+    pub address: PostalAddress,
+    /// The primary phone number of the site
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telephone: Option<String>,
+    /// The number of screens at the site
+    pub screen_count: usize,
+    /// The screen the event takes place on
+    pub contains_place: Place,
+}
+impl MovieTheater {
+    /// Create a schema.org `MovieTheater` for a [`Site`], containing the given
+    /// [`Screen`]
+    #[must_use]
+    pub fn new(site: &Site, screen: &Screen) -> Self {
+        Self {
+            kind: "MovieTheater",
+            name: site.name.clone(),
+            address: PostalAddress {
+                kind: "PostalAddress",
+                street_address: site.street_address_lines().join(", "),
+                postal_code: site.post_code.clone(),
+                address_country: site.country.clone(),
+            },
+            telephone: site.phone_1.clone(),
+            screen_count: site.screens.len(),
+            contains_place: Place {
+                kind: "Place",
+                name: screen.name.clone(),
+            },
+        }
+    }
+}
+
+/// A schema.org `Offer` describing ticket availability
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Offer {
+    /// The schema.org type, always `Offer`
+    #[serde(rename = "@type")]
+    pub kind: &'static str,
+    /// The schema.org `ItemAvailability` of tickets
+    pub availability: &'static str,
+    /// The time ticket sales close
+    pub valid_through: String,
+}
+
+/// A schema.org `ScreeningEvent` for a single [`Session`]
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreeningEvent {
+    /// The JSON-LD context, always `https://schema.org`
+    #[serde(rename = "@context")]
+    pub context: &'static str,
+    /// The schema.org type, always `ScreeningEvent`
+    #[serde(rename = "@type")]
+    pub kind: &'static str,
+    /// The title of the session
+    pub name: String,
+    /// The time the feature starts
+    pub start_date: String,
+    /// The time the feature ends
+    pub end_date: String,
+    /// The time the pre-show starts
+    pub door_time: String,
+    /// The theater and screen the session is shown in
+    pub location: MovieTheater,
+    /// The film being shown
+    pub work_presented: Movie,
+    /// The format the film is shown in (e.g., "3D")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_format: Option<&'static str>,
+    /// The audio language of the session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_language: Option<String>,
+    /// The ticket availability of the session
+    pub offers: Offer,
+}
+impl ScreeningEvent {
+    /// Create a schema.org `ScreeningEvent` from a [`Session`] and its
+    /// [`Film`], [`Screen`] and [`Site`]
+    #[must_use]
+    pub fn new(session: &Session, film: &Film, screen: &Screen, site: &Site) -> Self {
+        let availability = if session.tickets_sold_out {
+            "https://schema.org/SoldOut"
+        } else if session.few_tickets_left {
+            "https://schema.org/LimitedAvailability"
+        } else {
+            "https://schema.org/InStock"
+        };
+
+        Self {
+            context: SCHEMA_CONTEXT,
+            kind: "ScreeningEvent",
+            name: session.title.clone(),
+            start_date: format_time(site, session.feature_start_time),
+            end_date: format_time(site, session.feature_end_time),
+            door_time: format_time(site, session.pre_show_start_time),
+            location: MovieTheater::new(site, screen),
+            work_presented: Movie::new(film),
+            video_format: video_format(session.film_format),
+            in_language: session
+                .audio_language
+                .clone()
+                .or_else(|| film.audio_language.clone()),
+            offers: Offer {
+                kind: "Offer",
+                availability,
+                valid_through: format_time(site, session.sales_cut_off_time),
+            },
+        }
+    }
+
+    /// Create a schema.org `ScreeningEvent` for every session in a
+    /// [`SessionList`]
+    ///
+    /// Sessions whose film or screen is not in the given lists are skipped.
+    #[must_use]
+    pub fn for_sessions(
+        sessions: &SessionList,
+        films: &[Film],
+        screens: &[Screen],
+        site: &Site,
+    ) -> Vec<Self> {
+        sessions
+            .iter()
+            .filter_map(|session| {
+                let film = films.iter().find(|film| film.id == session.film_id)?;
+                let screen = screens
+                    .iter()
+                    .find(|screen| screen.id == session.screen_id)?;
+                Some(Self::new(session, film, screen, site))
+            })
+            .collect()
+    }
+
+    /// Serialize this [`ScreeningEvent`] as a JSON-LD document
+    ///
+    /// Every `<` is escaped as `\u003c`, so text such as a film title cannot
+    /// close the surrounding `<script>` element.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the event cannot be serialized.
+    pub fn to_json_ld(&self) -> serde_json::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?.replace('<', "\\u003c"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for rendering [`ScreeningEvent`]s
+
+    use chrono::NaiveDate;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder, SiteBuilder};
+
+    /// Internal helper to render the JSON-LD of a session of a film with the
+    /// given title
+    fn json_ld_for(title: &str) -> String {
+        let site = SiteBuilder::new().build();
+        let film = FilmBuilder::new("ST00000001")
+            .with_title(title)
+            .with_duration(135)
+            .build();
+        let screen = ScreenBuilder::new(1).build();
+        let start = NaiveDate::from_ymd_opt(2025, 6, 1)
+            .and_then(|date| date.and_hms_opt(19, 0, 0))
+            .expect("the start time should exist");
+        let session = SessionBuilder::new(1, &film, &screen, start).build();
+        ScreeningEvent::new(&session, &film, &screen, &site)
+            .to_json_ld()
+            .expect("the event should serialize")
+    }
+
+    #[test]
+    fn screening_events_describe_the_session() {
+        let json: Value = serde_json::from_str(&json_ld_for("The Long Projection"))
+            .expect("JSON-LD should parse");
+        assert_eq!(json["@context"], SCHEMA_CONTEXT);
+        assert_eq!(json["@type"], "ScreeningEvent");
+        assert_eq!(json["workPresented"]["name"], "The Long Projection");
+        assert_eq!(json["workPresented"]["duration"], "PT2H15M");
+        assert_eq!(json["doorTime"], "2025-06-01T19:00:00-07:00");
+    }
+
+    #[test]
+    fn script_tags_cannot_be_closed() {
+        let title = "</script><script>alert(1)</script>";
+        let json_ld = json_ld_for(title);
+        assert!(!json_ld.contains('<'));
+
+        let json: Value = serde_json::from_str(&json_ld).expect("JSON-LD should parse");
+        assert_eq!(json["name"], title);
+        assert_eq!(json["workPresented"]["name"], title);
+    }
+}
@@ -44,12 +44,41 @@
     clippy::doc_comment_double_space_linebreaks
 )]
 
+pub mod analytics;
 pub mod attr;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cassette;
 pub mod client;
+pub mod csv;
 pub mod error;
+#[cfg(feature = "exporter")]
+pub mod exporter;
+pub mod feed;
 pub mod film;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod ical;
+pub mod jsonld;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod middleware;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod multisite;
 pub mod package;
+pub mod region;
+pub mod schedule;
 pub mod screen;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod session;
 pub mod site;
+#[cfg(feature = "sitegen")]
+pub mod sitegen;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod timeline;
+pub mod transport;
 mod utils;
+pub mod watch;
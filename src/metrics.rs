@@ -0,0 +1,150 @@
+//! Metrics recorded by the [`Client`] with the `metrics` feature
+//!
+//! Every request made by a [`Client`] is recorded with the [`metrics`] facade,
+//! so any installed recorder (such as a Prometheus exporter) picks them up.
+//! Requests are labelled with their `endpoint`, with any entity ID replaced by
+//! `{id}` (e.g., `v4/film/{id}`), so the number of series stays bounded.
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | [`REQUESTS_TOTAL`] | counter | `endpoint`, `status` |
+//! | [`REQUEST_DURATION_SECONDS`] | histogram | `endpoint` |
+//! | [`RESPONSE_SIZE_BYTES`] | histogram | `endpoint` |
+//! | [`RATE_LIMITED_TOTAL`] | counter | `endpoint` |
+//! | [`RETRIES_TOTAL`] | counter | `endpoint` |
+//! | [`CACHE_HITS_TOTAL`] | counter | `cache` |
+//! | [`CACHE_MISSES_TOTAL`] | counter | `cache` |
+//! | [`CACHE_ENTRIES`] | gauge | `cache` |
+//!
+//! Requests that fail without a response are counted with a `status` of
+//! `error`. The client does not retry or wait out rate limits itself, so
+//! `429 Too Many Requests` responses are counted in [`RATE_LIMITED_TOTAL`] for
+//! callers that do, and callers that retry a request should count it with
+//! [`record_retry`] so retried requests can be told apart from first attempts.
+//! Cache entry counts are only sampled when
+//! [`Client::record_cache_metrics`] is awaited, which should be done before
+//! each scrape.
+//!
+//! [`Client`]: crate::client::Client
+//! [`Client::record_cache_metrics`]: crate::client::Client::record_cache_metrics
+
+use std::time::Duration;
+
+use metrics::{
+    Unit, counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram,
+};
+use reqwest::StatusCode;
+
+/// The number of requests made, by endpoint and status code
+pub const REQUESTS_TOTAL: &str = "veezi_requests_total";
+/// The time taken by requests, by endpoint
+pub const REQUEST_DURATION_SECONDS: &str = "veezi_request_duration_seconds";
+/// The size of response bodies, by endpoint
+pub const RESPONSE_SIZE_BYTES: &str = "veezi_response_size_bytes";
+/// The number of `429 Too Many Requests` responses, by endpoint
+pub const RATE_LIMITED_TOTAL: &str = "veezi_rate_limited_total";
+/// The number of retried requests, by endpoint
+pub const RETRIES_TOTAL: &str = "veezi_retries_total";
+/// The number of requests served from a cache, by cache
+pub const CACHE_HITS_TOTAL: &str = "veezi_cache_hits_total";
+/// The number of requests that missed a cache, by cache
+pub const CACHE_MISSES_TOTAL: &str = "veezi_cache_misses_total";
+/// The approximate number of entries in a cache, by cache
+pub const CACHE_ENTRIES: &str = "veezi_cache_entries";
+
+/// Register the descriptions and units of every metric with the installed
+/// recorder
+///
+/// This is optional, but gives exporters such as Prometheus help text for
+/// each metric.
+pub fn describe() {
+    describe_counter!(REQUESTS_TOTAL, Unit::Count, "Veezi API requests made");
+    describe_histogram!(
+        REQUEST_DURATION_SECONDS,
+        Unit::Seconds,
+        "Time taken by Veezi API requests"
+    );
+    describe_histogram!(
+        RESPONSE_SIZE_BYTES,
+        Unit::Bytes,
+        "Size of Veezi API response bodies"
+    );
+    describe_counter!(
+        RATE_LIMITED_TOTAL,
+        Unit::Count,
+        "Veezi API requests rejected by rate limiting"
+    );
+    describe_counter!(RETRIES_TOTAL, Unit::Count, "Veezi API requests retried");
+    describe_counter!(
+        CACHE_HITS_TOTAL,
+        Unit::Count,
+        "Requests served from a cache"
+    );
+    describe_counter!(
+        CACHE_MISSES_TOTAL,
+        Unit::Count,
+        "Requests that missed a cache"
+    );
+    describe_gauge!(CACHE_ENTRIES, Unit::Count, "Approximate entries in a cache");
+}
+
+/// Internal helper to convert a count to the `f64` recorded by [`metrics`]
+#[allow(clippy::cast_precision_loss, clippy::as_conversions)]
+const fn to_f64(value: u64) -> f64 {
+    value as f64
+}
+
+/// Internal helper to get the `endpoint` label of an endpoint, replacing any
+/// entity ID after the resource name with `{id}`
+fn endpoint_label(endpoint: &str) -> String {
+    let path = endpoint.split('?').next().unwrap_or_default();
+    let mut segments = path.splitn(3, '/');
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some(version), Some(resource), Some(_)) => format!("{version}/{resource}/{{id}}"),
+        _ => path.to_string(),
+    }
+}
+
+/// Internal helper to record a request that received a response
+pub(crate) fn record_response(endpoint: &str, status: StatusCode, elapsed: Duration, size: u64) {
+    let endpoint = endpoint_label(endpoint);
+    let code = status.as_str().to_string();
+    counter!(REQUESTS_TOTAL, "endpoint" => endpoint.clone(), "status" => code).increment(1);
+    histogram!(REQUEST_DURATION_SECONDS, "endpoint" => endpoint.clone()).record(elapsed);
+    histogram!(RESPONSE_SIZE_BYTES, "endpoint" => endpoint.clone()).record(to_f64(size));
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        counter!(RATE_LIMITED_TOTAL, "endpoint" => endpoint).increment(1);
+    }
+}
+
+/// Internal helper to record a request that failed without a response
+pub(crate) fn record_error(endpoint: &str, elapsed: Duration) {
+    let endpoint = endpoint_label(endpoint);
+    counter!(REQUESTS_TOTAL, "endpoint" => endpoint.clone(), "status" => "error").increment(1);
+    histogram!(REQUEST_DURATION_SECONDS, "endpoint" => endpoint).record(elapsed);
+}
+
+/// Record that a request to an endpoint is being retried
+///
+/// The client makes a single attempt per request, so this is for callers that
+/// retry failed or rate limited requests themselves. `endpoint` is the path
+/// requested (e.g., `v4/film/ST00000001`); any entity ID is replaced with
+/// `{id}` as for the client's own metrics.
+pub fn record_retry(endpoint: &str) {
+    counter!(RETRIES_TOTAL, "endpoint" => endpoint_label(endpoint)).increment(1);
+}
+
+/// Internal helper to record a cache hit
+pub(crate) fn record_cache_hit(cache: &'static str) {
+    counter!(CACHE_HITS_TOTAL, "cache" => cache).increment(1);
+}
+
+/// Internal helper to record a cache miss
+pub(crate) fn record_cache_miss(cache: &'static str) {
+    counter!(CACHE_MISSES_TOTAL, "cache" => cache).increment(1);
+}
+
+/// Internal helper to record the number of entries in a cache
+pub(crate) fn record_cache_entries(cache: &'static str, entries: u64) {
+    gauge!(CACHE_ENTRIES, "cache" => cache).set(to_f64(entries));
+}
@@ -0,0 +1,82 @@
+//! Request/response middleware for the [`Client`]
+//!
+//! A [`Middleware`] is added to a [`ClientBuilder`] with
+//! [`ClientBuilder::with_middleware`]. Every request the [`Client`] makes
+//! passes through [`Middleware::on_request`] of each middleware in the order
+//! they were added, so they can add headers or rewrite the URL. The response
+//! then passes through [`Middleware::on_response`] in reverse order, before it
+//! is deserialized, along with the time the request took. If the body cannot
+//! be deserialized, [`Middleware::on_decode_error`] is called with the raw
+//! body.
+//!
+//! Every method has a default implementation that does nothing, so a
+//! middleware only needs to implement the hooks it cares about:
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use libveezi::{
+//!     middleware::Middleware,
+//!     transport::{TransportRequest, TransportResponse},
+//! };
+//! use log::info;
+//!
+//! struct AuditLog;
+//! impl Middleware for AuditLog {
+//!     fn on_response(
+//!         &self,
+//!         request: &TransportRequest,
+//!         response: &TransportResponse,
+//!         elapsed: Duration,
+//!     ) {
+//!         info!("{} {} took {elapsed:?}", request.url, response.status);
+//!     }
+//! }
+//! ```
+//!
+//! [`Client`]: crate::client::Client
+//! [`ClientBuilder`]: crate::client::ClientBuilder
+//! [`ClientBuilder::with_middleware`]: crate::client::ClientBuilder::with_middleware
+
+use std::time::Duration;
+
+use crate::{
+    error::LibVeeziError,
+    transport::{TransportRequest, TransportResponse},
+};
+
+/// Hooks called around every request made by a [`crate::client::Client`]
+pub trait Middleware: Send + Sync {
+    /// Inspect or modify a request before it is sent
+    fn on_request(&self, request: &mut TransportRequest) {
+        let _ = request;
+    }
+
+    /// Inspect a response before it is deserialized
+    ///
+    /// This is called for every response, including those with an error
+    /// status code.
+    fn on_response(
+        &self,
+        request: &TransportRequest,
+        response: &TransportResponse,
+        elapsed: Duration,
+    ) {
+        let _ = (request, response, elapsed);
+    }
+
+    /// Inspect a request that failed without a response
+    fn on_error(&self, request: &TransportRequest, error: &LibVeeziError, elapsed: Duration) {
+        let _ = (request, error, elapsed);
+    }
+
+    /// Inspect a response whose body could not be deserialized
+    fn on_decode_error(
+        &self,
+        request: &TransportRequest,
+        response: &TransportResponse,
+        error: &serde_json::Error,
+    ) {
+        let _ = (request, response, error);
+    }
+}
@@ -0,0 +1,592 @@
+//! A local mock Veezi server for integration testing
+//!
+//! The primary type is [`MockVeezi`], which is seeded with [`Session`]s,
+//! [`Film`]s, [`FilmPackage`]s, [`Screen`]s, [`Attribute`]s and a [`Site`],
+//! then started as an in-process HTTP server with [`MockVeezi::start`]. The
+//! resulting [`MockServer`] serves the same endpoints as the Veezi API, checks
+//! the `VeeziAccessToken` header, and records every request it receives, so the
+//! full [`Client`] stack can be tested offline.
+//!
+//! Failures can be injected for any endpoint path with [`MockFailure`], either
+//! before starting the server or while it is running:
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> std::io::Result<()> {
+//! use libveezi::mock::{MockFailure, MockVeezi};
+//!
+//! let server = MockVeezi::new()
+//!     .with_failure("/v4/film", MockFailure::TooManyRequests)
+//!     .start()
+//!     .await?;
+//! let client = server.client();
+//! assert!(client.list_films().await.is_err());
+//! assert_eq!(server.requests().len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! This module is only available with the `mock` feature.
+
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use axum::{
+    Json, Router,
+    extract::{Path, Request, State},
+    http::{Method, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::Utc;
+use log::debug;
+use serde::Serialize;
+use tokio::{net::TcpListener, task::JoinHandle, time};
+
+use crate::{
+    attr::{Attribute, AttributeId},
+    client::{Client, ClientBuilder},
+    film::{Film, FilmId},
+    package::{FilmPackage, FilmPackageId},
+    screen::{Screen, ScreenId},
+    session::{Session, SessionId},
+    site::Site,
+};
+
+/// The access token a [`MockVeezi`] expects by default
+pub const DEFAULT_MOCK_TOKEN: &str = "mock-token";
+
+/// A failure injected into a [`MockServer`]'s responses
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MockFailure {
+    /// Respond with `404 Not Found`
+    NotFound,
+    /// Respond with `429 Too Many Requests` and a `Retry-After` of 1 second
+    TooManyRequests,
+    /// Respond with `500 Internal Server Error`
+    ServerError,
+    /// Wait for the given duration before responding normally
+    Slow(Duration),
+    /// Respond with `200 OK` and a body that is not valid JSON
+    MalformedJson,
+}
+
+/// A request received by a [`MockServer`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RecordedRequest {
+    /// The HTTP method of the request
+    pub method: String,
+    /// The path of the request (e.g., `/v1/session`)
+    pub path: String,
+    /// The query string of the request, if any
+    pub query: Option<String>,
+    /// The `VeeziAccessToken` header of the request, if any
+    pub access_token: Option<String>,
+}
+
+/// A [`MockFailure`] injected for a particular path
+#[derive(Debug, Clone)]
+struct FailureRule {
+    /// The path the failure applies to
+    path: String,
+    /// The failure to inject
+    failure: MockFailure,
+    /// The number of requests left to fail, or `None` to fail every request
+    remaining: Option<usize>,
+}
+
+/// The data served by a [`MockServer`]
+#[derive(Debug, Clone, Default)]
+struct MockData {
+    /// The sessions served at `/v1/session` and `/v1/websession`
+    sessions: Vec<Session>,
+    /// The films served at `/v4/film`
+    films: Vec<Film>,
+    /// The film packages served at `/v1/filmpackage`
+    film_packages: Vec<FilmPackage>,
+    /// The screens served at `/v1/screen`
+    screens: Vec<Screen>,
+    /// The attributes served at `/v1/attribute`
+    attributes: Vec<Attribute>,
+    /// The site served at `/v1/site`
+    site: Option<Site>,
+}
+
+/// The state shared by every request to a [`MockServer`]
+#[derive(Debug)]
+struct MockState {
+    /// The access token requests must send, or `None` to allow any request
+    token: Option<String>,
+    /// The data being served
+    data: Mutex<MockData>,
+    /// The injected failures
+    failures: Mutex<Vec<FailureRule>>,
+    /// The requests received so far
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+/// Internal helper to lock a [`Mutex`] of the mock state
+#[allow(clippy::missing_panics_doc)]
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .expect("mock state lock should not be poisoned")
+}
+
+/// Internal helper to normalize a path to start with a slash
+fn normalize_path(path: &str) -> String {
+    format!("/{}", path.trim_start_matches('/'))
+}
+
+/// A builder for a mock Veezi server
+#[derive(Debug, Clone)]
+pub struct MockVeezi {
+    /// The data to serve
+    data: MockData,
+    /// The failures to inject
+    failures: Vec<FailureRule>,
+    /// The access token requests must send, or `None` to allow any request
+    pub token: Option<String>,
+}
+impl Default for MockVeezi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl MockVeezi {
+    /// Create a new [`MockVeezi`] with no data, expecting
+    /// [`DEFAULT_MOCK_TOKEN`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            data: MockData::default(),
+            failures: Vec::new(),
+            token: Some(DEFAULT_MOCK_TOKEN.to_string()),
+        }
+    }
+
+    /// Expect a particular access token in the `VeeziAccessToken` header
+    #[must_use]
+    pub fn with_token(mut self, token: String) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Accept requests regardless of their `VeeziAccessToken` header
+    #[must_use]
+    pub fn without_token(mut self) -> Self {
+        self.token = None;
+        self
+    }
+
+    /// Serve the given [`Session`]s
+    #[must_use]
+    pub fn with_sessions(mut self, sessions: Vec<Session>) -> Self {
+        self.data.sessions = sessions;
+        self
+    }
+
+    /// Serve the given [`Film`]s
+    #[must_use]
+    pub fn with_films(mut self, films: Vec<Film>) -> Self {
+        self.data.films = films;
+        self
+    }
+
+    /// Serve the given [`FilmPackage`]s
+    #[must_use]
+    pub fn with_film_packages(mut self, film_packages: Vec<FilmPackage>) -> Self {
+        self.data.film_packages = film_packages;
+        self
+    }
+
+    /// Serve the given [`Screen`]s
+    #[must_use]
+    pub fn with_screens(mut self, screens: Vec<Screen>) -> Self {
+        self.data.screens = screens;
+        self
+    }
+
+    /// Serve the given [`Attribute`]s
+    #[must_use]
+    pub fn with_attributes(mut self, attributes: Vec<Attribute>) -> Self {
+        self.data.attributes = attributes;
+        self
+    }
+
+    /// Serve the given [`Site`]
+    #[must_use]
+    pub fn with_site(mut self, site: Site) -> Self {
+        self.data.site = Some(site);
+        self
+    }
+
+    /// Inject a [`MockFailure`] into every request to a path
+    #[must_use]
+    pub fn with_failure(mut self, path: &str, failure: MockFailure) -> Self {
+        self.failures.push(FailureRule {
+            path: normalize_path(path),
+            failure,
+            remaining: None,
+        });
+        self
+    }
+
+    /// Inject a [`MockFailure`] into the next `times` requests to a path
+    #[must_use]
+    pub fn with_failure_times(mut self, path: &str, failure: MockFailure, times: usize) -> Self {
+        self.failures.push(FailureRule {
+            path: normalize_path(path),
+            failure,
+            remaining: Some(times),
+        });
+        self
+    }
+
+    /// Start serving on a random local port
+    ///
+    /// The server runs on the current Tokio runtime until the returned
+    /// [`MockServer`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the listener cannot be bound.
+    pub async fn start(self) -> io::Result<MockServer> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(MockState {
+            token: self.token,
+            data: Mutex::new(self.data),
+            failures: Mutex::new(self.failures),
+            requests: Mutex::new(Vec::new()),
+        });
+        let router = Router::new()
+            .route("/v1/session", get(list_sessions))
+            .route("/v1/session/{id}", get(get_session))
+            .route("/v1/websession", get(list_web_sessions))
+            .route("/v4/film", get(list_films))
+            .route("/v4/film/{id}", get(get_film))
+            .route("/v1/filmpackage", get(list_film_packages))
+            .route("/v1/filmpackage/{id}", get(get_film_package))
+            .route("/v1/screen", get(list_screens))
+            .route("/v1/screen/{id}", get(get_screen))
+            .route("/v1/attribute", get(list_attributes))
+            .route("/v1/attribute/{id}", get(get_attribute))
+            .route("/v1/site", get(get_site))
+            .layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                intercept,
+            ))
+            .with_state(Arc::clone(&state));
+        debug!("Starting mock Veezi server on {addr}");
+        let task = tokio::spawn(async move {
+            if let Err(err) = axum::serve(listener, router).await {
+                debug!("Mock Veezi server failed: {err}");
+            }
+        });
+        Ok(MockServer { addr, state, task })
+    }
+}
+
+/// A running mock Veezi server, started with [`MockVeezi::start`]
+///
+/// The server stops when this is dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    /// The address the server is listening on
+    addr: SocketAddr,
+    /// The state shared with the server
+    state: Arc<MockState>,
+    /// The task running the server
+    task: JoinHandle<()>,
+}
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+impl MockServer {
+    /// Get the address the server is listening on
+    #[must_use]
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Get the base URL to build a [`Client`] with
+    #[must_use]
+    pub fn base_url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Create a [`ClientBuilder`] for this server, using the expected access
+    /// token
+    #[must_use]
+    pub fn client_builder(&self) -> ClientBuilder {
+        ClientBuilder::new(
+            &self.base_url(),
+            self.state
+                .token
+                .clone()
+                .unwrap_or_else(|| DEFAULT_MOCK_TOKEN.to_string()),
+        )
+    }
+
+    /// Create a [`Client`] for this server, using the expected access token
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn client(&self) -> Client {
+        self.client_builder()
+            .build()
+            .expect("mock server base URL should be valid")
+    }
+
+    /// Get every request received so far, in order
+    #[must_use]
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        lock(&self.state.requests).clone()
+    }
+
+    /// Forget every request received so far
+    pub fn clear_requests(&self) {
+        lock(&self.state.requests).clear();
+    }
+
+    /// Inject a [`MockFailure`] into every request to a path
+    pub fn inject_failure(&self, path: &str, failure: MockFailure) {
+        lock(&self.state.failures).push(FailureRule {
+            path: normalize_path(path),
+            failure,
+            remaining: None,
+        });
+    }
+
+    /// Inject a [`MockFailure`] into the next `times` requests to a path
+    pub fn inject_failure_times(&self, path: &str, failure: MockFailure, times: usize) {
+        lock(&self.state.failures).push(FailureRule {
+            path: normalize_path(path),
+            failure,
+            remaining: Some(times),
+        });
+    }
+
+    /// Remove every injected failure
+    pub fn clear_failures(&self) {
+        lock(&self.state.failures).clear();
+    }
+
+    /// Replace the served [`Session`]s
+    pub fn set_sessions(&self, sessions: Vec<Session>) {
+        lock(&self.state.data).sessions = sessions;
+    }
+
+    /// Replace the served [`Film`]s
+    pub fn set_films(&self, films: Vec<Film>) {
+        lock(&self.state.data).films = films;
+    }
+
+    /// Replace the served [`FilmPackage`]s
+    pub fn set_film_packages(&self, film_packages: Vec<FilmPackage>) {
+        lock(&self.state.data).film_packages = film_packages;
+    }
+
+    /// Replace the served [`Screen`]s
+    pub fn set_screens(&self, screens: Vec<Screen>) {
+        lock(&self.state.data).screens = screens;
+    }
+
+    /// Replace the served [`Attribute`]s
+    pub fn set_attributes(&self, attributes: Vec<Attribute>) {
+        lock(&self.state.data).attributes = attributes;
+    }
+
+    /// Replace the served [`Site`]
+    pub fn set_site(&self, site: Option<Site>) {
+        lock(&self.state.data).site = site;
+    }
+}
+
+/// Internal helper to take the next injected failure for a path
+fn take_failure(state: &MockState, path: &str) -> Option<MockFailure> {
+    let mut failures = lock(&state.failures);
+    let index = failures
+        .iter()
+        .position(|rule| rule.path == path && rule.remaining != Some(0))?;
+    let rule = &mut failures[index];
+    let failure = rule.failure;
+    if let Some(remaining) = &mut rule.remaining {
+        *remaining -= 1;
+        if *remaining == 0 {
+            failures.remove(index);
+        }
+    }
+    drop(failures);
+    Some(failure)
+}
+
+/// Internal middleware to record requests, check the access token and inject
+/// failures
+async fn intercept(State(state): State<Arc<MockState>>, request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let access_token = request
+        .headers()
+        .get("VeeziAccessToken")
+        .and_then(|token| token.to_str().ok())
+        .map(str::to_string);
+    lock(&state.requests).push(RecordedRequest {
+        method: request.method().to_string(),
+        path: path.clone(),
+        query: request.uri().query().map(str::to_string),
+        access_token: access_token.clone(),
+    });
+
+    if request.method() != Method::GET {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    }
+    if let Some(token) = &state.token
+        && access_token.as_ref() != Some(token)
+    {
+        return (StatusCode::UNAUTHORIZED, "Invalid or missing access token").into_response();
+    }
+
+    match take_failure(&state, &path) {
+        Some(MockFailure::NotFound) => StatusCode::NOT_FOUND.into_response(),
+        Some(MockFailure::TooManyRequests) => {
+            (StatusCode::TOO_MANY_REQUESTS, [(header::RETRY_AFTER, "1")]).into_response()
+        }
+        Some(MockFailure::ServerError) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        Some(MockFailure::MalformedJson) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/json")],
+            "{\"Id\": ",
+        )
+            .into_response(),
+        Some(MockFailure::Slow(delay)) => {
+            time::sleep(delay).await;
+            next.run(request).await
+        }
+        None => next.run(request).await,
+    }
+}
+
+/// Internal helper to respond with an item, or `404 Not Found`
+fn found<T: Serialize>(item: Option<T>) -> Response {
+    item.map_or_else(
+        || StatusCode::NOT_FOUND.into_response(),
+        |item| Json(item).into_response(),
+    )
+}
+
+/// Internal handler for `GET /v1/session`
+async fn list_sessions(State(state): State<Arc<MockState>>) -> Json<Vec<Session>> {
+    Json(lock(&state.data).sessions.clone())
+}
+
+/// Internal handler for `GET /v1/session/{id}`
+async fn get_session(State(state): State<Arc<MockState>>, Path(id): Path<SessionId>) -> Response {
+    found(
+        lock(&state.data)
+            .sessions
+            .iter()
+            .find(|session| session.id == id)
+            .cloned(),
+    )
+}
+
+/// Internal handler for `GET /v1/websession`
+///
+/// Sessions are checked against the current local time at the served site, or
+/// the current UTC time if no site is set.
+async fn list_web_sessions(State(state): State<Arc<MockState>>) -> Json<Vec<Session>> {
+    let data = lock(&state.data);
+    let now = data
+        .site
+        .as_ref()
+        .map_or_else(|| Utc::now().naive_utc(), Site::now);
+    Json(
+        data.sessions
+            .iter()
+            .filter(|session| session.is_open_for_web_sales_at(now))
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Internal handler for `GET /v4/film`
+async fn list_films(State(state): State<Arc<MockState>>) -> Json<Vec<Film>> {
+    Json(lock(&state.data).films.clone())
+}
+
+/// Internal handler for `GET /v4/film/{id}`
+async fn get_film(State(state): State<Arc<MockState>>, Path(id): Path<FilmId>) -> Response {
+    found(
+        lock(&state.data)
+            .films
+            .iter()
+            .find(|film| film.id == id)
+            .cloned(),
+    )
+}
+
+/// Internal handler for `GET /v1/filmpackage`
+async fn list_film_packages(State(state): State<Arc<MockState>>) -> Json<Vec<FilmPackage>> {
+    Json(lock(&state.data).film_packages.clone())
+}
+
+/// Internal handler for `GET /v1/filmpackage/{id}`
+async fn get_film_package(
+    State(state): State<Arc<MockState>>,
+    Path(id): Path<FilmPackageId>,
+) -> Response {
+    found(
+        lock(&state.data)
+            .film_packages
+            .iter()
+            .find(|package| package.id == id)
+            .cloned(),
+    )
+}
+
+/// Internal handler for `GET /v1/screen`
+async fn list_screens(State(state): State<Arc<MockState>>) -> Json<Vec<Screen>> {
+    Json(lock(&state.data).screens.clone())
+}
+
+/// Internal handler for `GET /v1/screen/{id}`
+async fn get_screen(State(state): State<Arc<MockState>>, Path(id): Path<ScreenId>) -> Response {
+    found(
+        lock(&state.data)
+            .screens
+            .iter()
+            .find(|screen| screen.id == id)
+            .cloned(),
+    )
+}
+
+/// Internal handler for `GET /v1/attribute`
+async fn list_attributes(State(state): State<Arc<MockState>>) -> Json<Vec<Attribute>> {
+    Json(lock(&state.data).attributes.clone())
+}
+
+/// Internal handler for `GET /v1/attribute/{id}`
+async fn get_attribute(
+    State(state): State<Arc<MockState>>,
+    Path(id): Path<AttributeId>,
+) -> Response {
+    found(
+        lock(&state.data)
+            .attributes
+            .iter()
+            .find(|attribute| attribute.id == id)
+            .cloned(),
+    )
+}
+
+/// Internal handler for `GET /v1/site`
+async fn get_site(State(state): State<Arc<MockState>>) -> Response {
+    found(lock(&state.data).site.clone())
+}
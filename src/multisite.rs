@@ -0,0 +1,475 @@
+//! A pool of [`Client`]s for circuits running several Veezi sites
+//!
+//! The primary type is [`MultiSiteClient`], which holds one [`Client`] per
+//! site, keyed by a [`SiteHandle`] of your choosing. Queries are fanned out to
+//! every site in parallel and the results are merged, with each item tagged
+//! with the site it came from (see [`SiteTagged`] and [`SiteSessionList`]).
+//!
+//! Sessions from different sites are ordered by the instant they start, so
+//! session queries also fetch each site's [`Site`] for its time zone.
+//!
+//! A site that fails does not fail the whole query: every query returns a
+//! [`MultiSiteResult`] holding the merged results of the sites that succeeded
+//! and the error of each site that did not.
+//!
+//! ```no_run
+//! use libveezi::{client::ClientBuilder, multisite::MultiSiteClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let sites = MultiSiteClient::new()
+//!     .with_site(
+//!         "downtown",
+//!         ClientBuilder::new("https://api.us.veezi.com/", "token-1".into()).build()?,
+//!     )
+//!     .with_site(
+//!         "riverside",
+//!         ClientBuilder::new("https://api.us.veezi.com/", "token-2".into()).build()?,
+//!     );
+//!
+//! let result = sites.list_sessions_by_film_title("The Long Projection").await;
+//! for tagged in result.value.iter() {
+//!     println!("{}: {}", tagged.site, tagged.item.feature_start_time);
+//! }
+//! for (site, err) in &result.errors {
+//!     eprintln!("{site} failed: {err}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug, Display, Formatter},
+    future::Future,
+    sync::Arc,
+    vec::IntoIter,
+};
+
+use chrono::{TimeDelta, Utc};
+use futures_util::future::{join_all, try_join};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::Client,
+    error::{ApiResult, LibVeeziError},
+    film::Film,
+    session::{Session, SessionList},
+    site::Site,
+};
+
+/// The handle identifying a site in a [`MultiSiteClient`]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+#[serde(transparent)]
+pub struct SiteHandle(String);
+impl SiteHandle {
+    /// Create a new [`SiteHandle`]
+    #[must_use]
+    pub fn new(handle: impl Into<String>) -> Self {
+        Self(handle.into())
+    }
+
+    /// Get the string representation of this [`SiteHandle`]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+impl Display for SiteHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl From<&str> for SiteHandle {
+    fn from(handle: &str) -> Self {
+        Self::new(handle)
+    }
+}
+impl From<String> for SiteHandle {
+    fn from(handle: String) -> Self {
+        Self(handle)
+    }
+}
+
+/// An item returned by one of the sites of a [`MultiSiteClient`]
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct SiteTagged<T> {
+    /// The site the item came from
+    pub site: SiteHandle,
+    /// The item
+    #[serde(flatten)]
+    pub item: T,
+}
+
+/// A list of [`Session`]s from several sites, each tagged with its site
+///
+/// Sessions are ordered by the instant their pre-show starts, interpreting
+/// each session's local times in the time zone of its site. As film, screen
+/// and attribute IDs differ between sites, the [`SessionList`] filters are not
+/// offered here; use [`SiteSessionList::for_site`] or
+/// [`SiteSessionList::group_by_site`] to filter the sessions of each site.
+#[derive(Serialize, Debug, PartialEq, Eq, Clone, Default)]
+pub struct SiteSessionList(Vec<SiteTagged<Session>>);
+impl SiteSessionList {
+    /// Create a new [`SiteSessionList`], ordering the sessions by the instant
+    /// their pre-show starts in the time zone of their site
+    ///
+    /// The times of a site missing from `sites`, or whose time zone is unknown,
+    /// are taken to be in UTC.
+    #[must_use]
+    pub fn new(mut sessions: Vec<SiteTagged<Session>>, sites: &BTreeMap<SiteHandle, Site>) -> Self {
+        sessions.sort_by_cached_key(|tagged| {
+            let start = tagged.item.pre_show_start_time;
+            let instant = sites
+                .get(&tagged.site)
+                .and_then(|site| site.localize(start))
+                .map_or_else(|| start.and_utc(), |time| time.with_timezone(&Utc));
+            (instant, tagged.site.clone())
+        });
+        Self(sessions)
+    }
+
+    /// Obtain the [`Vec`] of tagged sessions contained within this
+    /// [`SiteSessionList`]
+    #[must_use]
+    pub fn into_vec(self) -> Vec<SiteTagged<Session>> {
+        self.0
+    }
+
+    /// Get an iterator over the tagged sessions in this [`SiteSessionList`]
+    pub fn iter(&self) -> impl Iterator<Item = &SiteTagged<Session>> {
+        self.0.iter()
+    }
+
+    /// Get the number of sessions in this [`SiteSessionList`]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check whether this [`SiteSessionList`] has no sessions
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the [`SessionList`] of a single site
+    #[must_use]
+    pub fn for_site(&self, site: &SiteHandle) -> SessionList {
+        self.0
+            .iter()
+            .filter(|tagged| tagged.site == *site)
+            .map(|tagged| tagged.item.clone())
+            .collect::<Vec<Session>>()
+            .into()
+    }
+
+    /// Split this [`SiteSessionList`] into a [`SessionList`] per site
+    #[must_use]
+    pub fn group_by_site(self) -> BTreeMap<SiteHandle, SessionList> {
+        let mut groups: BTreeMap<SiteHandle, Vec<Session>> = BTreeMap::new();
+        for tagged in self.0 {
+            groups.entry(tagged.site).or_default().push(tagged.item);
+        }
+        groups
+            .into_iter()
+            .map(|(site, sessions)| (site, sessions.into()))
+            .collect()
+    }
+}
+impl IntoIterator for SiteSessionList {
+    type Item = SiteTagged<Session>;
+    type IntoIter = IntoIter<SiteTagged<Session>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// The merged result of a query fanned out to every site of a
+/// [`MultiSiteClient`]
+#[derive(Debug)]
+pub struct MultiSiteResult<T> {
+    /// The merged results of every site that succeeded
+    pub value: T,
+    /// The error of every site that failed
+    pub errors: BTreeMap<SiteHandle, LibVeeziError>,
+}
+impl<T> MultiSiteResult<T> {
+    /// Check whether every site succeeded
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Get the merged results, or the errors if any site failed
+    ///
+    /// # Errors
+    ///
+    /// This function will return the errors of every failed site if any site
+    /// failed.
+    pub fn into_complete(self) -> Result<T, BTreeMap<SiteHandle, LibVeeziError>> {
+        if self.errors.is_empty() {
+            Ok(self.value)
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+/// A pool of [`Client`]s, one per site, queried in parallel
+#[derive(Default, Clone)]
+pub struct MultiSiteClient {
+    /// The client of each site
+    clients: BTreeMap<SiteHandle, Arc<Client>>,
+}
+impl Debug for MultiSiteClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiSiteClient")
+            .field("sites", &self.clients.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+impl MultiSiteClient {
+    /// Create a new [`MultiSiteClient`] with no sites
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a site with its [`Client`], replacing any site with the same handle
+    #[must_use]
+    pub fn with_site(mut self, site: impl Into<SiteHandle>, client: Client) -> Self {
+        self.insert(site, Arc::new(client));
+        self
+    }
+
+    /// Add a site with a shared [`Client`], returning the client it replaced,
+    /// if any
+    pub fn insert(
+        &mut self,
+        site: impl Into<SiteHandle>,
+        client: Arc<Client>,
+    ) -> Option<Arc<Client>> {
+        self.clients.insert(site.into(), client)
+    }
+
+    /// Remove a site, returning its client, if any
+    pub fn remove(&mut self, site: &SiteHandle) -> Option<Arc<Client>> {
+        self.clients.remove(site)
+    }
+
+    /// Get the [`Client`] of a site
+    #[must_use]
+    pub fn client(&self, site: &SiteHandle) -> Option<&Client> {
+        self.clients.get(site).map(AsRef::as_ref)
+    }
+
+    /// Get an iterator over the handles of every site, in order
+    pub fn sites(&self) -> impl Iterator<Item = &SiteHandle> {
+        self.clients.keys()
+    }
+
+    /// Run a query against every site in parallel, collecting the result of
+    /// each site that succeeded and the error of each site that failed
+    pub async fn fan_out<'a, T, F, Fut>(
+        &'a self,
+        query: F,
+    ) -> MultiSiteResult<BTreeMap<SiteHandle, T>>
+    where
+        F: Fn(&'a Client) -> Fut,
+        Fut: Future<Output = ApiResult<T>>,
+    {
+        let results = join_all(self.clients.iter().map(|(site, client)| {
+            let query = query(client);
+            async move { (site.clone(), query.await) }
+        }))
+        .await;
+
+        let mut value = BTreeMap::new();
+        let mut errors = BTreeMap::new();
+        for (site, result) in results {
+            match result {
+                Ok(item) => {
+                    value.insert(site, item);
+                }
+                Err(err) => {
+                    errors.insert(site, err);
+                }
+            }
+        }
+        MultiSiteResult { value, errors }
+    }
+
+    /// Internal helper to fan out a query returning a list and tag every item
+    /// with its site
+    async fn fan_out_tagged<'a, T, I, F, Fut>(
+        &'a self,
+        query: F,
+    ) -> MultiSiteResult<Vec<SiteTagged<T>>>
+    where
+        I: IntoIterator<Item = T>,
+        F: Fn(&'a Client) -> Fut,
+        Fut: Future<Output = ApiResult<I>>,
+    {
+        let MultiSiteResult { value, errors } = self.fan_out(query).await;
+        MultiSiteResult {
+            value: value
+                .into_iter()
+                .flat_map(|(site, items)| {
+                    items.into_iter().map(move |item| SiteTagged {
+                        site: site.clone(),
+                        item,
+                    })
+                })
+                .collect(),
+            errors,
+        }
+    }
+
+    /// Internal helper to fan out a query returning sessions, fetching the
+    /// [`Site`] of every site alongside to order the merged sessions
+    async fn fan_out_sessions<'a, I, F, Fut>(&'a self, query: F) -> MultiSiteResult<SiteSessionList>
+    where
+        I: IntoIterator<Item = Session>,
+        F: Fn(&'a Client) -> Fut,
+        Fut: Future<Output = ApiResult<I>>,
+    {
+        let MultiSiteResult { value, errors } = self
+            .fan_out(move |client| try_join(client.get_site(), query(client)))
+            .await;
+        let mut sites = BTreeMap::new();
+        let mut sessions = Vec::new();
+        for (handle, (site, items)) in value {
+            sessions.extend(items.into_iter().map(|item| SiteTagged {
+                site: handle.clone(),
+                item,
+            }));
+            sites.insert(handle, site);
+        }
+        MultiSiteResult {
+            value: SiteSessionList::new(sessions, &sites),
+            errors,
+        }
+    }
+
+    /// Get the [`Site`] of every site
+    pub async fn get_sites(&self) -> MultiSiteResult<Vec<SiteTagged<Site>>> {
+        self.fan_out_tagged(|client| async { Ok([client.get_site().await?]) })
+            .await
+    }
+
+    /// Get every future [`Session`] across all sites
+    pub async fn list_sessions(&self) -> MultiSiteResult<SiteSessionList> {
+        self.fan_out_sessions(Client::list_sessions).await
+    }
+
+    /// Get every future [`Session`] available for online sales across all
+    /// sites
+    ///
+    /// See [`Client::list_web_sessions`] for the criteria.
+    pub async fn list_web_sessions(&self) -> MultiSiteResult<SiteSessionList> {
+        self.fan_out_sessions(Client::list_web_sessions).await
+    }
+
+    /// Get every future [`Session`] of a film across all sites, matching the
+    /// film by its exact [`Session::title`]
+    ///
+    /// Films are matched by title as each site has its own film IDs.
+    pub async fn list_sessions_by_film_title(
+        &self,
+        title: &str,
+    ) -> MultiSiteResult<SiteSessionList> {
+        self.fan_out_sessions(|client| async move {
+            Ok(client
+                .list_sessions()
+                .await?
+                .into_iter()
+                .filter(|session| session.title == title)
+                .collect::<Vec<Session>>())
+        })
+        .await
+    }
+
+    /// Get the [`Film`]s now playing at every site
+    ///
+    /// See [`Client::list_now_playing_films`] for the meaning of `window`.
+    pub async fn list_now_playing_films(
+        &self,
+        window: TimeDelta,
+    ) -> MultiSiteResult<Vec<SiteTagged<Film>>> {
+        self.fan_out_tagged(|client| client.list_now_playing_films(window))
+            .await
+    }
+
+    /// Get the [`Film`]s coming soon to every site
+    ///
+    /// See [`Client::list_coming_soon_films`] for the meaning of `window`.
+    pub async fn list_coming_soon_films(
+        &self,
+        window: TimeDelta,
+    ) -> MultiSiteResult<Vec<SiteTagged<Film>>> {
+        self.fan_out_tagged(|client| client.list_coming_soon_films(window))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for ordering [`SiteSessionList`]s
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder, SiteBuilder};
+
+    /// Internal helper to tag a session starting at a local hour with its site
+    fn session_at(site: &str, hour: u32) -> SiteTagged<Session> {
+        let film = FilmBuilder::new("ST00000001").build();
+        let screen = ScreenBuilder::new(1).build();
+        let start = NaiveDate::from_ymd_opt(2025, 6, 1)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .expect("the start time should exist");
+        SiteTagged {
+            site: SiteHandle::new(site),
+            item: SessionBuilder::new(1, &film, &screen, start).build(),
+        }
+    }
+
+    #[test]
+    fn sessions_are_ordered_by_instant() {
+        let sites = BTreeMap::from([
+            (
+                SiteHandle::new("east"),
+                SiteBuilder::new()
+                    .with_time_zone("America/New_York")
+                    .build(),
+            ),
+            (
+                SiteHandle::new("west"),
+                SiteBuilder::new()
+                    .with_time_zone("America/Los_Angeles")
+                    .build(),
+            ),
+        ]);
+        // 17:00 in Los Angeles is 20:00 in New York, after 19:00 there
+        let sessions = vec![session_at("west", 17), session_at("east", 19)];
+        let list = SiteSessionList::new(sessions, &sites);
+        let order: Vec<&str> = list.iter().map(|tagged| tagged.site.as_str()).collect();
+        assert_eq!(order, ["east", "west"]);
+    }
+
+    #[test]
+    fn unknown_time_zones_are_taken_as_utc() {
+        let sites = BTreeMap::from([(
+            SiteHandle::new("east"),
+            SiteBuilder::new()
+                .with_time_zone("America/New_York")
+                .build(),
+        )]);
+        // 19:00 in New York is 23:00 UTC, after 22:00 at the unknown site
+        let sessions = vec![session_at("east", 19), session_at("unknown", 22)];
+        let list = SiteSessionList::new(sessions, &sites);
+        let order: Vec<&str> = list.iter().map(|tagged| tagged.site.as_str()).collect();
+        assert_eq!(order, ["unknown", "east"]);
+    }
+}
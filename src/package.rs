@@ -6,8 +6,10 @@
 
 use std::fmt::{self, Debug, Display, Formatter};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "blocking")]
+use crate::blocking;
 use crate::{
     client::Client,
     error::ApiResult,
@@ -15,7 +17,7 @@ use crate::{
 };
 
 /// A particular film within a [`FilmPackage`]
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct PackageFilm {
     /// The unique ID of the film
@@ -40,12 +42,22 @@ impl PackageFilm {
     pub async fn film(&self, client: &Client) -> ApiResult<Film> {
         client.get_film(&self.film_id).await
     }
+
+    /// Blocking version of [`Self::film`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn film_blocking(&self, client: &blocking::Client) -> ApiResult<Film> {
+        client.block_on(self.film(client.inner()))
+    }
 }
 
 /// The unique ID of a [`FilmPackage`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 #[serde(transparent)]
-pub struct FilmPackageId(u32);
+pub struct FilmPackageId(pub(crate) u32);
 impl FilmPackageId {
     /// Get the numeric ID of this [`FilmPackageId`]
     #[must_use]
@@ -61,6 +73,16 @@ impl FilmPackageId {
     pub async fn fetch(self, client: &Client) -> ApiResult<FilmPackage> {
         client.get_film_package(self).await
     }
+
+    /// Blocking version of [`Self::fetch`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(self, client: &blocking::Client) -> ApiResult<FilmPackage> {
+        client.block_on(self.fetch(client.inner()))
+    }
 }
 impl Display for FilmPackageId {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -69,7 +91,7 @@ impl Display for FilmPackageId {
 }
 
 /// A package of [`PackageFilm`]s in the Veezi system ("double feature")
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct FilmPackage {
     /// The unique ID of the film package
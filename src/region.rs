@@ -0,0 +1,267 @@
+//! The regional endpoints of the Veezi API
+//!
+//! The primary type is [`VeeziRegion`], which maps each Veezi region to its
+//! official base URL, or holds a custom base URL such as a proxy or mock
+//! server. Use it with [`ClientBuilder::for_region`]:
+//!
+//! ```
+//! use libveezi::{client::ClientBuilder, region::VeeziRegion};
+//!
+//! let client = ClientBuilder::for_region(&VeeziRegion::AuNz, "your_api_key".to_string())
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! Base URLs given as strings, whether to [`ClientBuilder::new`] or
+//! [`VeeziRegion::custom`], are checked by [`normalize_base_url`], which adds
+//! the trailing slash endpoints are joined onto and reports a
+//! [`BaseUrlError`] for anything that is not an `http` or `https` URL.
+//!
+//! [`ClientBuilder::for_region`]: crate::client::ClientBuilder::for_region
+//! [`ClientBuilder::new`]: crate::client::ClientBuilder::new
+
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+
+use url::Url;
+
+/// The base URL of the Veezi API in the United States
+pub const US_BASE_URL: &str = "https://api.us.veezi.com/";
+
+/// The base URL of the Veezi API in Australia and New Zealand
+pub const AU_NZ_BASE_URL: &str = "https://api.au.veezi.com/";
+
+/// The base URL of the Veezi API in the United Kingdom and Europe
+pub const UK_EU_BASE_URL: &str = "https://api.uk.veezi.com/";
+
+/// The list of errors that can occur when validating a base URL
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BaseUrlError {
+    /// The base URL is empty
+    Empty,
+    /// The base URL has no scheme (e.g., `api.us.veezi.com`)
+    MissingScheme(String),
+    /// The base URL has a scheme other than `http` or `https`
+    UnsupportedScheme {
+        /// The base URL
+        url: String,
+        /// The scheme of the base URL
+        scheme: String,
+    },
+    /// The base URL has a query string or fragment, which would be lost when
+    /// endpoints are joined onto it
+    QueryOrFragment(String),
+    /// The base URL could not be parsed
+    Invalid {
+        /// The base URL
+        url: String,
+        /// The reason the base URL could not be parsed
+        source: url::ParseError,
+    },
+}
+impl Display for BaseUrlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Base URL is empty"),
+            Self::MissingScheme(url) => write!(
+                f,
+                "Base URL \"{url}\" has no scheme; did you mean \"https://{}/\"?",
+                url.trim_end_matches('/')
+            ),
+            Self::UnsupportedScheme { url, scheme } => write!(
+                f,
+                "Base URL \"{url}\" has unsupported scheme \"{scheme}\"; expected http or https"
+            ),
+            Self::QueryOrFragment(url) => {
+                write!(f, "Base URL \"{url}\" must not have a query or fragment")
+            }
+            Self::Invalid { url, source } => write!(f, "Base URL \"{url}\" is invalid: {source}"),
+        }
+    }
+}
+impl Error for BaseUrlError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Invalid { source, .. } => Some(source),
+            Self::Empty
+            | Self::MissingScheme(_)
+            | Self::UnsupportedScheme { .. }
+            | Self::QueryOrFragment(_) => None,
+        }
+    }
+}
+
+/// Validate a base URL and normalize it to end with exactly one slash
+///
+/// Surrounding whitespace is ignored, so `" https://api.us.veezi.com "` and
+/// `"https://api.us.veezi.com//"` both become `https://api.us.veezi.com/`.
+///
+/// # Errors
+///
+/// This function will return an error if the base URL is empty, has no scheme
+/// or a scheme other than `http` or `https`, has a query string or fragment,
+/// or cannot be parsed.
+pub fn normalize_base_url(base_url: &str) -> Result<Url, BaseUrlError> {
+    let trimmed = base_url.trim();
+    if trimmed.is_empty() {
+        return Err(BaseUrlError::Empty);
+    }
+    if !trimmed.contains("://") {
+        return Err(BaseUrlError::MissingScheme(trimmed.to_string()));
+    }
+    let mut url = Url::parse(trimmed).map_err(|source| BaseUrlError::Invalid {
+        url: trimmed.to_string(),
+        source,
+    })?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(BaseUrlError::UnsupportedScheme {
+            url: trimmed.to_string(),
+            scheme: url.scheme().to_string(),
+        });
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(BaseUrlError::QueryOrFragment(trimmed.to_string()));
+    }
+    let path = format!("{}/", url.path().trim_end_matches('/'));
+    url.set_path(&path);
+    Ok(url)
+}
+
+/// A region of the Veezi API, or a custom base URL
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub enum VeeziRegion {
+    /// The United States, at [`US_BASE_URL`]
+    #[default]
+    Us,
+    /// Australia and New Zealand, at [`AU_NZ_BASE_URL`]
+    AuNz,
+    /// The United Kingdom and Europe, at [`UK_EU_BASE_URL`]
+    UkEu,
+    /// A custom base URL, such as a proxy or mock server
+    Custom(Url),
+}
+impl VeeziRegion {
+    /// Create a [`VeeziRegion::Custom`] from a base URL, normalized by
+    /// [`normalize_base_url`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the base URL is not valid.
+    pub fn custom(base_url: &str) -> Result<Self, BaseUrlError> {
+        normalize_base_url(base_url).map(Self::Custom)
+    }
+
+    /// Get the base URL of this region
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn base_url(&self) -> Url {
+        let official = match self {
+            Self::Us => US_BASE_URL,
+            Self::AuNz => AU_NZ_BASE_URL,
+            Self::UkEu => UK_EU_BASE_URL,
+            Self::Custom(url) => return url.clone(),
+        };
+        Url::parse(official).expect("official base URLs should be valid")
+    }
+}
+impl Display for VeeziRegion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Us => write!(f, "US"),
+            Self::AuNz => write!(f, "AU/NZ"),
+            Self::UkEu => write!(f, "UK/EU"),
+            Self::Custom(url) => write!(f, "{url}"),
+        }
+    }
+}
+impl FromStr for VeeziRegion {
+    type Err = BaseUrlError;
+
+    /// Parse a region name (`us`, `au`, `nz`, `au/nz`, `uk`, `eu` or `uk/eu`,
+    /// in any case) or a custom base URL
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "us" => Ok(Self::Us),
+            "au" | "nz" | "au/nz" | "au-nz" => Ok(Self::AuNz),
+            "uk" | "eu" | "uk/eu" | "uk-eu" => Ok(Self::UkEu),
+            _ => Self::custom(s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for validating base URLs and parsing [`VeeziRegion`]s
+
+    use super::*;
+
+    /// Internal helper to normalize a base URL that should be valid
+    fn normalized(base_url: &str) -> String {
+        normalize_base_url(base_url)
+            .expect("the base URL should be valid")
+            .to_string()
+    }
+
+    #[test]
+    fn base_urls_end_with_one_slash() {
+        assert_eq!(normalized("https://api.us.veezi.com"), US_BASE_URL);
+        assert_eq!(normalized(" https://api.us.veezi.com// "), US_BASE_URL);
+        assert_eq!(
+            normalized("http://localhost:8080/veezi"),
+            "http://localhost:8080/veezi/"
+        );
+    }
+
+    #[test]
+    fn invalid_base_urls_are_rejected() {
+        assert_eq!(normalize_base_url("  "), Err(BaseUrlError::Empty));
+        assert_eq!(
+            normalize_base_url("api.us.veezi.com"),
+            Err(BaseUrlError::MissingScheme("api.us.veezi.com".to_string()))
+        );
+        assert_eq!(
+            normalize_base_url("ftp://api.us.veezi.com"),
+            Err(BaseUrlError::UnsupportedScheme {
+                url: "ftp://api.us.veezi.com".to_string(),
+                scheme: "ftp".to_string(),
+            })
+        );
+        assert_eq!(
+            normalize_base_url("https://api.us.veezi.com/?key=1"),
+            Err(BaseUrlError::QueryOrFragment(
+                "https://api.us.veezi.com/?key=1".to_string()
+            ))
+        );
+        assert_eq!(
+            normalize_base_url("https://api.us.veezi.com/#top"),
+            Err(BaseUrlError::QueryOrFragment(
+                "https://api.us.veezi.com/#top".to_string()
+            ))
+        );
+        assert!(matches!(
+            normalize_base_url("https://:80"),
+            Err(BaseUrlError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn regions_parse_from_names_or_base_urls() {
+        assert_eq!("US".parse(), Ok(VeeziRegion::Us));
+        assert_eq!("au".parse(), Ok(VeeziRegion::AuNz));
+        assert_eq!("uk/eu".parse(), Ok(VeeziRegion::UkEu));
+        assert_eq!(
+            "http://localhost:8080"
+                .parse::<VeeziRegion>()
+                .map(|region| region.base_url()),
+            normalize_base_url("http://localhost:8080/")
+        );
+        assert_eq!(
+            "mars".parse::<VeeziRegion>(),
+            Err(BaseUrlError::MissingScheme("mars".to_string()))
+        );
+        assert_eq!(VeeziRegion::default().base_url().as_str(), US_BASE_URL);
+    }
+}
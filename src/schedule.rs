@@ -0,0 +1,468 @@
+//! Validation of a [`SessionList`] for scheduling mistakes
+//!
+//! The primary type is [`ScheduleValidator`], which checks the sessions on each
+//! screen for overlaps, cleanup overruns and short turnarounds, and checks each
+//! session's timings against its [`Film`] or [`FilmPackage`].
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+use chrono::TimeDelta;
+use serde::Serialize;
+
+use crate::{
+    film::{Film, FilmId},
+    package::{FilmPackage, FilmPackageId},
+    screen::ScreenId,
+    session::{Session, SessionId, SessionList},
+};
+
+/// A single problem found by a [`ScheduleValidator`]
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub enum ScheduleIssue {
+    /// The session's times are out of order (for example its feature ends
+    /// before it starts)
+    InvalidTimeline {
+        /// The ID of the session
+        session_id: SessionId,
+    },
+    /// The next session on the same screen starts before the feature of the
+    /// previous session has ended
+    Overlap {
+        /// The screen both sessions are shown on
+        screen_id: ScreenId,
+        /// The earlier session
+        session_id: SessionId,
+        /// The later session, which starts too early
+        next_session_id: SessionId,
+    },
+    /// The cleanup window of a session runs into the pre-show of the next
+    /// session on the same screen
+    CleanupOverrun {
+        /// The screen both sessions are shown on
+        screen_id: ScreenId,
+        /// The earlier session, whose cleanup overruns
+        session_id: SessionId,
+        /// The later session
+        next_session_id: SessionId,
+        /// How many minutes the cleanup overruns by
+        overrun_minutes: i64,
+    },
+    /// The time between the end of a feature and the pre-show of the next
+    /// session on the same screen is shorter than the configured minimum
+    ShortTurnaround {
+        /// The screen both sessions are shown on
+        screen_id: ScreenId,
+        /// The earlier session
+        session_id: SessionId,
+        /// The later session
+        next_session_id: SessionId,
+        /// The turnaround between the two sessions in minutes
+        turnaround_minutes: i64,
+    },
+    /// The session's feature length disagrees with [`Film::duration`], or
+    /// with the combined length of the films in its [`FilmPackage`]
+    FeatureLengthMismatch {
+        /// The ID of the session
+        session_id: SessionId,
+        /// The ID of the film shown in the session
+        film_id: FilmId,
+        /// The expected feature length in minutes
+        expected_minutes: i64,
+        /// The scheduled feature length in minutes
+        actual_minutes: i64,
+    },
+    /// The session's pre-show length disagrees with the
+    /// [`crate::package::PackageFilm::trailer_duration`] of the first film in
+    /// its [`FilmPackage`]
+    PreShowLengthMismatch {
+        /// The ID of the session
+        session_id: SessionId,
+        /// The ID of the film package shown in the session
+        film_package_id: FilmPackageId,
+        /// The expected pre-show length in minutes
+        expected_minutes: i64,
+        /// The scheduled pre-show length in minutes
+        actual_minutes: i64,
+    },
+    /// The session's cleanup length disagrees with the
+    /// [`crate::package::PackageFilm::clean_up_duration`] of the last film in
+    /// its [`FilmPackage`]
+    CleanupLengthMismatch {
+        /// The ID of the session
+        session_id: SessionId,
+        /// The ID of the film package shown in the session
+        film_package_id: FilmPackageId,
+        /// The expected cleanup length in minutes
+        expected_minutes: i64,
+        /// The scheduled cleanup length in minutes
+        actual_minutes: i64,
+    },
+}
+impl Display for ScheduleIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTimeline { session_id } => {
+                write!(f, "session {session_id} has its times out of order")
+            }
+            Self::Overlap {
+                screen_id,
+                session_id,
+                next_session_id,
+            } => write!(
+                f,
+                "session {next_session_id} overlaps session {session_id} on screen {screen_id}"
+            ),
+            Self::CleanupOverrun {
+                screen_id,
+                session_id,
+                next_session_id,
+                overrun_minutes,
+            } => write!(
+                f,
+                "cleanup of session {session_id} runs {overrun_minutes}m into session \
+                 {next_session_id} on screen {screen_id}"
+            ),
+            Self::ShortTurnaround {
+                screen_id,
+                session_id,
+                next_session_id,
+                turnaround_minutes,
+            } => write!(
+                f,
+                "only {turnaround_minutes}m turnaround between session {session_id} and session \
+                 {next_session_id} on screen {screen_id}"
+            ),
+            Self::FeatureLengthMismatch {
+                session_id,
+                film_id,
+                expected_minutes,
+                actual_minutes,
+            } => write!(
+                f,
+                "session {session_id} schedules {actual_minutes}m for film {film_id}, expected \
+                 {expected_minutes}m"
+            ),
+            Self::PreShowLengthMismatch {
+                session_id,
+                film_package_id,
+                expected_minutes,
+                actual_minutes,
+            } => write!(
+                f,
+                "session {session_id} has a {actual_minutes}m pre-show, package \
+                 {film_package_id} expects {expected_minutes}m"
+            ),
+            Self::CleanupLengthMismatch {
+                session_id,
+                film_package_id,
+                expected_minutes,
+                actual_minutes,
+            } => write!(
+                f,
+                "session {session_id} has a {actual_minutes}m cleanup, package \
+                 {film_package_id} expects {expected_minutes}m"
+            ),
+        }
+    }
+}
+
+/// Internal helper to convert a duration in minutes into a [`TimeDelta`]
+fn minutes(minutes: u32) -> TimeDelta {
+    TimeDelta::minutes(i64::from(minutes))
+}
+
+/// Internal helper to check that the times of a session are in order
+fn has_valid_timeline(session: &Session) -> bool {
+    session.pre_show_start_time <= session.feature_start_time
+        && session.feature_start_time <= session.feature_end_time
+        && session.feature_end_time <= session.cleanup_end_time
+}
+
+/// Validates a [`SessionList`] for scheduling mistakes
+pub struct ScheduleValidator {
+    /// Report turnarounds shorter than this minimum, if set
+    ///
+    /// The turnaround is the time between [`Session::feature_end_time`] and the
+    /// [`Session::pre_show_start_time`] of the next session on the same screen.
+    pub min_turnaround: Option<TimeDelta>,
+    /// How far a scheduled length may differ from the expected length before
+    /// it is reported
+    pub duration_tolerance: TimeDelta,
+}
+impl Default for ScheduleValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl ScheduleValidator {
+    /// Create a new [`ScheduleValidator`] with no minimum turnaround and no
+    /// tolerance for length mismatches
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            min_turnaround: None,
+            duration_tolerance: TimeDelta::zero(),
+        }
+    }
+
+    /// Report turnarounds shorter than the given minimum
+    #[must_use]
+    pub const fn with_min_turnaround(mut self, min_turnaround: TimeDelta) -> Self {
+        self.min_turnaround = Some(min_turnaround);
+        self
+    }
+
+    /// Allow scheduled lengths to differ from the expected lengths by up to
+    /// the given tolerance
+    #[must_use]
+    pub const fn with_duration_tolerance(mut self, tolerance: TimeDelta) -> Self {
+        self.duration_tolerance = tolerance;
+        self
+    }
+
+    /// Validate the given sessions, returning every issue found
+    ///
+    /// `films` and `packages` are used to check the lengths of each session;
+    /// sessions whose film or package is not in the given lists are only
+    /// checked against the other sessions on their screen. Sessions reported
+    /// with an [`ScheduleIssue::InvalidTimeline`] are left out of the checks
+    /// against other sessions, as their times are meaningless.
+    #[must_use]
+    pub fn validate(
+        &self,
+        sessions: &SessionList,
+        films: &[Film],
+        packages: &[FilmPackage],
+    ) -> Vec<ScheduleIssue> {
+        let films: HashMap<&FilmId, &Film> = films.iter().map(|film| (&film.id, film)).collect();
+        let packages: HashMap<FilmPackageId, &FilmPackage> = packages
+            .iter()
+            .map(|package| (package.id, package))
+            .collect();
+
+        let mut issues = Vec::new();
+        for session in sessions.iter() {
+            self.validate_session(session, &films, &packages, &mut issues);
+        }
+
+        let mut by_screen: HashMap<ScreenId, Vec<&Session>> = HashMap::new();
+        for session in sessions
+            .iter()
+            .filter(|session| has_valid_timeline(session))
+        {
+            by_screen
+                .entry(session.screen_id)
+                .or_default()
+                .push(session);
+        }
+        let mut screens: Vec<(ScreenId, Vec<&Session>)> = by_screen.into_iter().collect();
+        screens.sort_by_key(|(screen_id, _)| screen_id.into_u32());
+        for (screen_id, mut screen_sessions) in screens {
+            screen_sessions.sort_by_key(|session| session.pre_show_start_time);
+            self.validate_screen(screen_id, &screen_sessions, &mut issues);
+        }
+
+        issues
+    }
+
+    /// Check the timings of a single session against its film or package
+    fn validate_session(
+        &self,
+        session: &Session,
+        films: &HashMap<&FilmId, &Film>,
+        packages: &HashMap<FilmPackageId, &FilmPackage>,
+        issues: &mut Vec<ScheduleIssue>,
+    ) {
+        if !has_valid_timeline(session) {
+            issues.push(ScheduleIssue::InvalidTimeline {
+                session_id: session.id,
+            });
+            return;
+        }
+
+        let actual_feature = session.feature_end_time - session.feature_start_time;
+        let package = session
+            .film_package_id
+            .and_then(|id| packages.get(&id).copied());
+
+        let Some(package) = package else {
+            if let Some(film) = films.get(&session.film_id) {
+                let expected = minutes(film.duration);
+                if !self.within_tolerance(expected, actual_feature) {
+                    issues.push(ScheduleIssue::FeatureLengthMismatch {
+                        session_id: session.id,
+                        film_id: session.film_id.clone(),
+                        expected_minutes: expected.num_minutes(),
+                        actual_minutes: actual_feature.num_minutes(),
+                    });
+                }
+            }
+            return;
+        };
+
+        let mut package_films: Vec<_> = package.films.iter().collect();
+        package_films.sort_by_key(|package_film| package_film.order);
+        let (Some(first), Some(last)) = (package_films.first(), package_films.last()) else {
+            return;
+        };
+
+        let expected_pre_show = minutes(first.trailer_duration);
+        let actual_pre_show = session.feature_start_time - session.pre_show_start_time;
+        if !self.within_tolerance(expected_pre_show, actual_pre_show) {
+            issues.push(ScheduleIssue::PreShowLengthMismatch {
+                session_id: session.id,
+                film_package_id: package.id,
+                expected_minutes: expected_pre_show.num_minutes(),
+                actual_minutes: actual_pre_show.num_minutes(),
+            });
+        }
+
+        let expected_cleanup = minutes(last.clean_up_duration);
+        let actual_cleanup = session.cleanup_end_time - session.feature_end_time;
+        if !self.within_tolerance(expected_cleanup, actual_cleanup) {
+            issues.push(ScheduleIssue::CleanupLengthMismatch {
+                session_id: session.id,
+                film_package_id: package.id,
+                expected_minutes: expected_cleanup.num_minutes(),
+                actual_minutes: actual_cleanup.num_minutes(),
+            });
+        }
+
+        // The feature of a package runs from the first film to the end of the
+        // last one, including the trailers and cleanups in between
+        let mut expected_feature = TimeDelta::zero();
+        for (index, package_film) in package_films.iter().enumerate() {
+            let Some(film) = films.get(&package_film.film_id) else {
+                return;
+            };
+            expected_feature += minutes(film.duration);
+            if index > 0 {
+                expected_feature += minutes(package_film.trailer_duration);
+            }
+            if index + 1 < package_films.len() {
+                expected_feature += minutes(package_film.clean_up_duration);
+            }
+        }
+        if !self.within_tolerance(expected_feature, actual_feature) {
+            issues.push(ScheduleIssue::FeatureLengthMismatch {
+                session_id: session.id,
+                film_id: session.film_id.clone(),
+                expected_minutes: expected_feature.num_minutes(),
+                actual_minutes: actual_feature.num_minutes(),
+            });
+        }
+    }
+
+    /// Check the sessions on a single screen, sorted by start time, against
+    /// each other
+    fn validate_screen(
+        &self,
+        screen_id: ScreenId,
+        sessions: &[&Session],
+        issues: &mut Vec<ScheduleIssue>,
+    ) {
+        // Compare each session against the earlier session that ends last, so
+        // that a short session nested inside a long one cannot hide an overlap
+        let mut previous: Option<&Session> = None;
+        for &next in sessions {
+            if let Some(session) = previous {
+                if next.pre_show_start_time < session.feature_end_time {
+                    issues.push(ScheduleIssue::Overlap {
+                        screen_id,
+                        session_id: session.id,
+                        next_session_id: next.id,
+                    });
+                } else if next.pre_show_start_time < session.cleanup_end_time {
+                    issues.push(ScheduleIssue::CleanupOverrun {
+                        screen_id,
+                        session_id: session.id,
+                        next_session_id: next.id,
+                        overrun_minutes: (session.cleanup_end_time - next.pre_show_start_time)
+                            .num_minutes(),
+                    });
+                } else if let Some(min_turnaround) = self.min_turnaround {
+                    let turnaround = next.pre_show_start_time - session.feature_end_time;
+                    if turnaround < min_turnaround {
+                        issues.push(ScheduleIssue::ShortTurnaround {
+                            screen_id,
+                            session_id: session.id,
+                            next_session_id: next.id,
+                            turnaround_minutes: turnaround.num_minutes(),
+                        });
+                    }
+                } else {
+                    // No minimum turnaround is configured
+                }
+            }
+
+            if previous.is_none_or(|session| next.cleanup_end_time > session.cleanup_end_time) {
+                previous = Some(next);
+            }
+        }
+    }
+
+    /// Whether a scheduled length is within the configured tolerance of the
+    /// expected length
+    fn within_tolerance(&self, expected: TimeDelta, actual: TimeDelta) -> bool {
+        (actual - expected).abs() <= self.duration_tolerance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the [`ScheduleValidator`]
+
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder};
+
+    /// Internal helper to get a session start time on a fixed day
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 1)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn invalid_timeline_is_not_checked_against_other_sessions() {
+        let film = FilmBuilder::new("ST00000001").with_duration(90).build();
+        let screen = ScreenBuilder::new(1).build();
+        let valid = SessionBuilder::new(1, &film, &screen, at(18)).build();
+        let mut broken = SessionBuilder::new(2, &film, &screen, at(19)).build();
+        broken.feature_end_time = at(23);
+        broken.cleanup_end_time = at(12);
+        let next = SessionBuilder::new(3, &film, &screen, at(21)).build();
+
+        let issues = ScheduleValidator::new()
+            .with_min_turnaround(TimeDelta::minutes(10))
+            .validate(&vec![valid, broken, next].into(), &[film], &[]);
+        assert_eq!(
+            issues,
+            vec![ScheduleIssue::InvalidTimeline {
+                session_id: SessionId(2),
+            }]
+        );
+    }
+
+    #[test]
+    fn overlapping_sessions_are_reported() {
+        let film = FilmBuilder::new("ST00000001").with_duration(90).build();
+        let screen = ScreenBuilder::new(1).build();
+        let first = SessionBuilder::new(1, &film, &screen, at(18)).build();
+        let second = SessionBuilder::new(2, &film, &screen, at(19)).build();
+
+        let issues = ScheduleValidator::new().validate(&vec![first, second].into(), &[film], &[]);
+        assert_eq!(
+            issues,
+            vec![ScheduleIssue::Overlap {
+                screen_id: screen.id,
+                session_id: SessionId(1),
+                next_session_id: SessionId(2),
+            }]
+        );
+    }
+}
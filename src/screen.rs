@@ -2,14 +2,17 @@
 
 use std::fmt::{self, Debug, Display, Formatter};
 
-use serde::Deserialize;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 
-use crate::{client::Client, error::ApiResult, session::SessionList};
+#[cfg(feature = "blocking")]
+use crate::blocking;
+use crate::{client::Client, error::ApiResult, session::SessionList, timeline::ScreenTimeline};
 
 /// The unique ID of a [`Screen`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 #[serde(transparent)]
-pub struct ScreenId(u32);
+pub struct ScreenId(pub(crate) u32);
 impl ScreenId {
     /// Get the numeric ID of this [`ScreenId`]
     #[must_use]
@@ -25,6 +28,16 @@ impl ScreenId {
     pub async fn fetch(self, client: &Client) -> ApiResult<Screen> {
         client.get_screen(self).await
     }
+
+    /// Blocking version of [`Self::fetch`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(self, client: &blocking::Client) -> ApiResult<Screen> {
+        client.block_on(self.fetch(client.inner()))
+    }
 }
 impl Display for ScreenId {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -33,7 +46,7 @@ impl Display for ScreenId {
 }
 
 /// A particular screen (auditorium) in the Veezi system
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Screen {
     /// The unique ID of the screen
@@ -59,4 +72,42 @@ impl Screen {
     pub async fn sessions(&self, client: &Client) -> ApiResult<SessionList> {
         Ok(client.list_sessions().await?.filter_by_screen(self.id))
     }
+
+    /// Blocking version of [`Self::sessions`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn sessions_blocking(&self, client: &blocking::Client) -> ApiResult<SessionList> {
+        client.block_on(self.sessions(client.inner()))
+    }
+
+    /// Get the [`ScreenTimeline`] of this [`Screen`] for the given business day
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    pub async fn timeline(
+        &self,
+        client: &Client,
+        business_day: NaiveDate,
+    ) -> ApiResult<ScreenTimeline> {
+        let sessions = client.list_sessions().await?;
+        Ok(ScreenTimeline::new(&sessions, self, business_day))
+    }
+
+    /// Blocking version of [`Self::timeline`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn timeline_blocking(
+        &self,
+        client: &blocking::Client,
+        business_day: NaiveDate,
+    ) -> ApiResult<ScreenTimeline> {
+        client.block_on(self.timeline(client.inner(), business_day))
+    }
 }
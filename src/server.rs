@@ -0,0 +1,726 @@
+//! A caching proxy server exposing a Veezi-compatible API
+//!
+//! The primary type is [`ProxyServer`], which serves the same endpoint paths as
+//! the Veezi API (`v1/session`, `v1/websession`, `v4/film`, `v1/screen`,
+//! `v1/attribute`, `v1/filmpackage` and `v1/site`, plus the single-item
+//! variants) from a shared [`Client`]. Every downstream app then benefits from
+//! the [`Client`]'s caches, and only the proxy holds the real
+//! `VeeziAccessToken`.
+//!
+//! Downstream apps authenticate with their own API keys in the
+//! `VeeziAccessToken` header, so an existing libveezi [`Client`] can use the
+//! proxy simply by pointing its base URL at it. The [`Client`]'s cache
+//! statistics are served at `/stats`.
+//!
+//! With [`ProxyServer::with_facade`], the server also exposes higher-level
+//! endpoints for websites, which join [`Film`], [`Screen`] and [`Attribute`]
+//! data onto web sessions:
+//!
+//! - `GET /showtimes?date=&days=&film=&screen=&attribute=`: sessions grouped by
+//!   date and then by film (see [`ShowtimeDay`])
+//! - `GET /films/now-playing?days=` and `GET /films/coming-soon?days=`: films
+//!   classified by [`SessionList::classify_film`] with their next sessions (see
+//!   [`FilmShowing`])
+//! - `GET /screens/{id}/timeline?date=`: the [`ScreenTimeline`] of a screen
+//!
+//! Dates default to today at the site, and `days` defaults to 1 for showtimes
+//! and 7 for films. The films endpoints reject a `days` over [`MAX_FILM_DAYS`]
+//! with `400 Bad Request`.
+//!
+//! The upstream API is whatever base URL the [`Client`] was built with, so the
+//! proxy can be run against a local stand-in for Veezi.
+//!
+//! With the `graphql` feature, the [`VeeziSchema`] is also served at `POST
+//! /graphql`.
+//!
+//! This module is only available with the `server` feature.
+
+use std::{collections::HashSet, io, sync::Arc};
+
+#[cfg(feature = "graphql")]
+use axum::routing::post;
+use axum::{
+    Json, Router,
+    extract::{Path, Query, Request, State},
+    http::{HeaderName, HeaderValue, Method, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::{Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+#[cfg(feature = "graphql")]
+use crate::graphql::{VeeziSchema, build_schema};
+use crate::{
+    attr::{Attribute, AttributeId},
+    client::{Client, ClientCacheStats},
+    error::{ApiResult, LibVeeziError},
+    film::{Film, FilmId, PlayingStatus},
+    package::{FilmPackage, FilmPackageId},
+    screen::{Screen, ScreenId},
+    session::{Session, SessionId, SessionList},
+    site::Site,
+    timeline::ScreenTimeline,
+};
+
+/// The header downstream apps authenticate with, matching the Veezi API
+pub const API_KEY_HEADER: &str = "VeeziAccessToken";
+
+/// An error returned to a downstream app
+///
+/// Errors from the Veezi API keep their status code (e.g., 404 for an unknown
+/// ID); any other failure is reported as `502 Bad Gateway`.
+#[derive(Debug)]
+pub struct ProxyError(pub LibVeeziError);
+impl From<LibVeeziError> for ProxyError {
+    fn from(err: LibVeeziError) -> Self {
+        Self(err)
+    }
+}
+impl IntoResponse for ProxyError {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            LibVeeziError::Http(err) => err
+                .status()
+                .and_then(|status| StatusCode::from_u16(status.as_u16()).ok())
+                .unwrap_or(StatusCode::BAD_GATEWAY),
+            LibVeeziError::Status { status, .. } => {
+                StatusCode::from_u16(status.as_u16()).unwrap_or(StatusCode::BAD_GATEWAY)
+            }
+            LibVeeziError::UrlParse(_)
+            | LibVeeziError::Decode(_)
+            | LibVeeziError::Cassette(_)
+            | LibVeeziError::Transport(_) => StatusCode::BAD_GATEWAY,
+            LibVeeziError::BaseUrl(_) | LibVeeziError::Runtime(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        debug!("Proxy request failed with {status}: {}", self.0);
+        (status, self.0.to_string()).into_response()
+    }
+}
+
+/// A result type for proxy handlers
+pub type ProxyResult<T> = Result<T, ProxyError>;
+
+/// The state shared by every request to a [`ProxyServer`]
+#[derive(Clone)]
+pub struct ProxyState {
+    /// The client used to reach the Veezi API
+    pub client: Arc<Client>,
+    /// The API keys downstream apps may use, or `None` to allow any request
+    pub api_keys: Option<Arc<HashSet<String>>>,
+    /// The GraphQL schema served at `/graphql`
+    #[cfg(feature = "graphql")]
+    pub schema: VeeziSchema,
+}
+
+/// A caching proxy server in front of a [`Client`]
+#[derive(Clone)]
+pub struct ProxyServer {
+    /// The client used to reach the Veezi API
+    client: Arc<Client>,
+    /// The API keys downstream apps may use, or `None` to allow any request
+    pub api_keys: Option<HashSet<String>>,
+    /// The origins allowed to make cross-origin requests, or `None` to
+    /// disable CORS; `"*"` allows any origin
+    pub cors_origins: Option<Vec<String>>,
+    /// Whether to serve the higher-level website endpoints
+    pub facade: bool,
+}
+impl ProxyServer {
+    /// Create a new [`ProxyServer`] in front of a [`Client`], with no
+    /// authentication and CORS disabled
+    #[must_use]
+    pub const fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            api_keys: None,
+            cors_origins: None,
+            facade: false,
+        }
+    }
+
+    /// Require downstream apps to send one of the given API keys in the
+    /// `VeeziAccessToken` header
+    #[must_use]
+    pub fn with_api_keys(mut self, keys: impl IntoIterator<Item = String>) -> Self {
+        self.api_keys = Some(keys.into_iter().collect());
+        self
+    }
+
+    /// Allow cross-origin requests from the given origins; `"*"` allows any
+    /// origin
+    #[must_use]
+    pub fn with_cors_origins(mut self, origins: impl IntoIterator<Item = String>) -> Self {
+        self.cors_origins = Some(origins.into_iter().collect());
+        self
+    }
+
+    /// Also serve the higher-level website endpoints described in the
+    /// [module documentation](self)
+    #[must_use]
+    pub const fn with_facade(mut self) -> Self {
+        self.facade = true;
+        self
+    }
+
+    /// Get the [`ProxyState`] shared by every request
+    #[must_use]
+    pub fn state(&self) -> ProxyState {
+        ProxyState {
+            client: Arc::clone(&self.client),
+            api_keys: self.api_keys.clone().map(Arc::new),
+            #[cfg(feature = "graphql")]
+            schema: build_schema(Arc::clone(&self.client)),
+        }
+    }
+
+    /// Build the [`Router`] serving the Veezi-compatible API
+    pub fn router(&self) -> Router {
+        let state = self.state();
+        let mut router = Router::new()
+            .route("/v1/session", get(list_sessions))
+            .route("/v1/session/{id}", get(get_session))
+            .route("/v1/websession", get(list_web_sessions))
+            .route("/v4/film", get(list_films))
+            .route("/v4/film/{id}", get(get_film))
+            .route("/v1/filmpackage", get(list_film_packages))
+            .route("/v1/filmpackage/{id}", get(get_film_package))
+            .route("/v1/screen", get(list_screens))
+            .route("/v1/screen/{id}", get(get_screen))
+            .route("/v1/attribute", get(list_attributes))
+            .route("/v1/attribute/{id}", get(get_attribute))
+            .route("/v1/site", get(get_site))
+            .route("/stats", get(cache_stats));
+        if self.facade {
+            router = router
+                .route("/showtimes", get(showtimes))
+                .route("/films/now-playing", get(now_playing_films))
+                .route("/films/coming-soon", get(coming_soon_films))
+                .route("/screens/{id}/timeline", get(screen_timeline));
+        }
+        #[cfg(feature = "graphql")]
+        {
+            router = router.route("/graphql", post(graphql));
+        }
+        let mut router = router
+            .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+            .with_state(state);
+        if let Some(origins) = &self.cors_origins {
+            router = router.layer(cors_layer(origins));
+        }
+        router
+    }
+
+    /// Serve the Veezi-compatible API on a listener until the server fails
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the server fails.
+    pub async fn serve(self, listener: TcpListener) -> io::Result<()> {
+        debug!("Serving libveezi proxy on {:?}", listener.local_addr());
+        axum::serve(listener, self.router()).await
+    }
+}
+
+/// Internal helper to build the CORS layer allowing the given origins
+fn cors_layer(origins: &[String]) -> CorsLayer {
+    let allow_origin = if origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        )
+    };
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            HeaderName::from_static("veeziaccesstoken"),
+        ])
+}
+
+/// Internal middleware to reject requests without a valid downstream API key
+async fn authenticate(State(state): State<ProxyState>, request: Request, next: Next) -> Response {
+    if let Some(keys) = &state.api_keys {
+        let key = request
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|key| key.to_str().ok());
+        if !key.is_some_and(|key| keys.contains(key)) {
+            return (StatusCode::UNAUTHORIZED, "Invalid or missing API key").into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Internal handler for `POST /graphql`
+#[cfg(feature = "graphql")]
+async fn graphql(
+    State(state): State<ProxyState>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(state.schema.execute(request).await)
+}
+
+/// Internal handler for `GET /v1/session`
+async fn list_sessions(State(state): State<ProxyState>) -> ProxyResult<Json<SessionList>> {
+    Ok(Json(state.client.list_sessions().await?))
+}
+
+/// Internal handler for `GET /v1/session/{id}`
+async fn get_session(
+    State(state): State<ProxyState>,
+    Path(id): Path<SessionId>,
+) -> ProxyResult<Json<Session>> {
+    Ok(Json(state.client.get_session(id).await?))
+}
+
+/// Internal handler for `GET /v1/websession`
+async fn list_web_sessions(State(state): State<ProxyState>) -> ProxyResult<Json<SessionList>> {
+    Ok(Json(state.client.list_web_sessions().await?))
+}
+
+/// Internal handler for `GET /v4/film`
+async fn list_films(State(state): State<ProxyState>) -> ProxyResult<Json<Vec<Film>>> {
+    Ok(Json(state.client.list_films().await?))
+}
+
+/// Internal handler for `GET /v4/film/{id}`
+async fn get_film(
+    State(state): State<ProxyState>,
+    Path(id): Path<FilmId>,
+) -> ProxyResult<Json<Film>> {
+    Ok(Json(state.client.get_film(&id).await?))
+}
+
+/// Internal handler for `GET /v1/filmpackage`
+async fn list_film_packages(
+    State(state): State<ProxyState>,
+) -> ProxyResult<Json<Vec<FilmPackage>>> {
+    Ok(Json(state.client.list_film_packages().await?))
+}
+
+/// Internal handler for `GET /v1/filmpackage/{id}`
+async fn get_film_package(
+    State(state): State<ProxyState>,
+    Path(id): Path<FilmPackageId>,
+) -> ProxyResult<Json<FilmPackage>> {
+    Ok(Json(state.client.get_film_package(id).await?))
+}
+
+/// Internal handler for `GET /v1/screen`
+async fn list_screens(State(state): State<ProxyState>) -> ProxyResult<Json<Vec<Screen>>> {
+    Ok(Json(state.client.list_screens().await?))
+}
+
+/// Internal handler for `GET /v1/screen/{id}`
+async fn get_screen(
+    State(state): State<ProxyState>,
+    Path(id): Path<ScreenId>,
+) -> ProxyResult<Json<Screen>> {
+    Ok(Json(state.client.get_screen(id).await?))
+}
+
+/// Internal handler for `GET /v1/attribute`
+async fn list_attributes(State(state): State<ProxyState>) -> ProxyResult<Json<Vec<Attribute>>> {
+    Ok(Json(state.client.list_attributes().await?))
+}
+
+/// Internal handler for `GET /v1/attribute/{id}`
+async fn get_attribute(
+    State(state): State<ProxyState>,
+    Path(id): Path<AttributeId>,
+) -> ProxyResult<Json<Attribute>> {
+    Ok(Json(state.client.get_attribute(&id).await?))
+}
+
+/// Internal handler for `GET /v1/site`
+async fn get_site(State(state): State<ProxyState>) -> ProxyResult<Json<Site>> {
+    Ok(Json(state.client.get_site().await?))
+}
+
+/// Internal handler for `GET /stats`
+async fn cache_stats(State(state): State<ProxyState>) -> Json<ClientCacheStats> {
+    Json(state.client.cache_stats())
+}
+
+/// A [`Film`] as shown on a website
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct FilmSummary {
+    /// [`Film::id`]
+    pub id: FilmId,
+    /// [`Film::title`]
+    pub title: String,
+    /// [`Film::synopsis`]
+    pub synopsis: Option<String>,
+    /// [`Film::genre`]
+    pub genre: String,
+    /// [`Film::rating`]
+    pub rating: Option<String>,
+    /// [`Film::duration`]
+    pub duration: u32,
+    /// [`Film::opening_date`]
+    pub opening_date: NaiveDateTime,
+    /// The poster of the film, if any
+    pub poster_url: Option<String>,
+    /// [`Film::film_trailer_url`]
+    pub trailer_url: Option<String>,
+}
+impl FilmSummary {
+    /// Create a [`FilmSummary`] from a [`Film`]
+    #[must_use]
+    pub fn new(film: &Film) -> Self {
+        Self {
+            id: film.id.clone(),
+            title: film.title.clone(),
+            synopsis: film.synopsis.clone(),
+            genre: film.genre.clone(),
+            rating: film.rating.clone(),
+            duration: film.duration,
+            opening_date: film.opening_date,
+            poster_url: film
+                .film_poster_url
+                .clone()
+                .or_else(|| Some(film.film_poster_thumbnail_url.clone()))
+                .filter(|url| !url.is_empty()),
+            trailer_url: film.film_trailer_url.clone(),
+        }
+    }
+}
+
+/// An [`Attribute`] as shown on a website
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct AttributeSummary {
+    /// [`Attribute::id`]
+    pub id: AttributeId,
+    /// [`Attribute::short_name`]
+    pub short_name: String,
+    /// [`Attribute::description`]
+    pub description: String,
+    /// [`Attribute::font_color`]
+    pub font_color: String,
+    /// [`Attribute::background_color`]
+    pub background_color: String,
+}
+
+/// A [`Session`] as shown on a website, with its screen and attributes
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct SessionSummary {
+    /// [`Session::id`]
+    pub id: SessionId,
+    /// [`Session::feature_start_time`]
+    pub start: NaiveDateTime,
+    /// [`Session::feature_end_time`]
+    pub end: NaiveDateTime,
+    /// [`Session::screen_id`]
+    pub screen_id: ScreenId,
+    /// The name of the screen, if known
+    pub screen_name: Option<String>,
+    /// The session's attributes, if known
+    pub attributes: Vec<AttributeSummary>,
+    /// [`Session::seats_available`]
+    pub seats_available: u32,
+    /// [`Session::tickets_sold_out`]
+    pub sold_out: bool,
+    /// [`Session::few_tickets_left`]
+    pub few_tickets_left: bool,
+    /// Whether tickets can currently be bought online
+    pub open_for_sales: bool,
+}
+
+/// The sessions of one film on one day
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct ShowtimeFilm {
+    /// The film, or `None` if it is unknown
+    pub film: Option<FilmSummary>,
+    /// The title of the sessions
+    pub title: String,
+    /// The sessions, ordered by start time
+    pub sessions: Vec<SessionSummary>,
+}
+
+/// The sessions of one day, grouped by film
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct ShowtimeDay {
+    /// The day, by pre-show start time
+    pub date: NaiveDate,
+    /// The films shown on the day, ordered by their first session
+    pub films: Vec<ShowtimeFilm>,
+}
+
+/// A [`Film`] with its [`PlayingStatus`] and next sessions
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct FilmShowing {
+    /// The film
+    pub film: FilmSummary,
+    /// How the film is currently being programmed
+    pub status: PlayingStatus,
+    /// The film's next sessions that can be booked online
+    pub next_sessions: Vec<SessionSummary>,
+}
+
+/// Everything needed to hydrate [`Session`]s for a website
+struct Catalog {
+    /// The current local time at the site
+    now: NaiveDateTime,
+    /// Every film at the site
+    films: Vec<Film>,
+    /// Every screen at the site
+    screens: Vec<Screen>,
+    /// Every attribute at the site
+    attributes: Vec<Attribute>,
+    /// The sessions that are available for web sales
+    sessions: SessionList,
+}
+impl Catalog {
+    /// Fetch the [`Catalog`] from a [`Client`]
+    async fn fetch(client: &Client) -> ApiResult<Self> {
+        Ok(Self {
+            now: client.get_site().await?.now(),
+            films: client.list_films().await?,
+            screens: client.list_screens().await?,
+            attributes: client.list_attributes().await?,
+            sessions: client.list_web_sessions().await?,
+        })
+    }
+
+    /// Get the first day to show, defaulting to today
+    fn start(&self, date: Option<NaiveDate>) -> NaiveDateTime {
+        date.unwrap_or_else(|| self.now.date())
+            .and_time(NaiveTime::MIN)
+    }
+
+    /// Find a [`Film`] by its ID
+    fn film(&self, id: &FilmId) -> Option<&Film> {
+        self.films.iter().find(|film| film.id == *id)
+    }
+
+    /// Hydrate a [`Session`] with its screen and attributes
+    fn session(&self, session: &Session) -> SessionSummary {
+        SessionSummary {
+            id: session.id,
+            start: session.feature_start_time,
+            end: session.feature_end_time,
+            screen_id: session.screen_id,
+            screen_name: self
+                .screens
+                .iter()
+                .find(|screen| screen.id == session.screen_id)
+                .map(|screen| screen.name.clone()),
+            attributes: session
+                .attributes
+                .iter()
+                .filter_map(|id| self.attributes.iter().find(|attribute| attribute.id == *id))
+                .map(|attribute| AttributeSummary {
+                    id: attribute.id.clone(),
+                    short_name: attribute.short_name.clone(),
+                    description: attribute.description.clone(),
+                    font_color: attribute.font_color.clone(),
+                    background_color: attribute.background_color.clone(),
+                })
+                .collect(),
+            seats_available: session.seats_available,
+            sold_out: session.tickets_sold_out,
+            few_tickets_left: session.few_tickets_left,
+            open_for_sales: session.is_open_for_web_sales_at(self.now),
+        }
+    }
+
+    /// List the films with the given statuses, with up to `count` of their
+    /// next sessions, by display sequence
+    fn showings(
+        &self,
+        window: TimeDelta,
+        statuses: &[PlayingStatus],
+        count: usize,
+    ) -> Vec<FilmShowing> {
+        let mut films: Vec<&Film> = self.films.iter().collect();
+        films.sort_by_key(|film| film.display_sequence);
+        films
+            .into_iter()
+            .filter_map(|film| {
+                let status = self.sessions.classify_film(film, self.now, window)?;
+                if !statuses.contains(&status) {
+                    return None;
+                }
+                let mut next: Vec<&Session> = self
+                    .sessions
+                    .iter()
+                    .filter(|session| {
+                        session.film_id == film.id && session.is_open_for_web_sales_at(self.now)
+                    })
+                    .collect();
+                next.sort_by_key(|session| session.feature_start_time);
+                Some(FilmShowing {
+                    film: FilmSummary::new(film),
+                    status,
+                    next_sessions: next
+                        .into_iter()
+                        .take(count)
+                        .map(|session| self.session(session))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The query of `GET /showtimes`
+#[derive(Deserialize, Debug)]
+struct ShowtimesQuery {
+    /// The first day to show, defaulting to today
+    date: Option<NaiveDate>,
+    /// The number of days to show, defaulting to 1
+    days: Option<u64>,
+    /// Only show sessions of this film
+    film: Option<FilmId>,
+    /// Only show sessions on this screen
+    screen: Option<ScreenId>,
+    /// Only show sessions with this attribute
+    attribute: Option<AttributeId>,
+}
+
+/// The query of `GET /films/now-playing` and `GET /films/coming-soon`
+#[derive(Deserialize, Debug)]
+struct FilmsQuery {
+    /// The classification window in days, defaulting to 7
+    days: Option<u64>,
+}
+
+/// The query of `GET /screens/{id}/timeline`
+#[derive(Deserialize, Debug)]
+struct TimelineQuery {
+    /// The business day, defaulting to today
+    date: Option<NaiveDate>,
+}
+
+/// The number of next sessions listed for each film
+const NEXT_SESSION_COUNT: usize = 5;
+
+/// The largest classification window, in days, accepted by
+/// `GET /films/now-playing` and `GET /films/coming-soon`
+pub const MAX_FILM_DAYS: u64 = 366;
+
+/// Internal helper to get the classification window of a [`FilmsQuery`], or
+/// `None` if it is over [`MAX_FILM_DAYS`]
+fn film_window(query: &FilmsQuery) -> Option<TimeDelta> {
+    query
+        .days
+        .or(Some(7))
+        .filter(|days| *days <= MAX_FILM_DAYS)
+        .and_then(|days| i64::try_from(days).ok())
+        .and_then(TimeDelta::try_days)
+}
+
+/// Internal helper to reject a [`FilmsQuery`] whose window is too large
+fn invalid_film_window() -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        format!("days must be at most {MAX_FILM_DAYS}"),
+    )
+        .into_response()
+}
+
+/// Internal handler for `GET /showtimes`
+async fn showtimes(
+    State(state): State<ProxyState>,
+    Query(query): Query<ShowtimesQuery>,
+) -> ProxyResult<Json<Vec<ShowtimeDay>>> {
+    let catalog = Catalog::fetch(&state.client).await?;
+    let start = catalog.start(query.date);
+    let end = start
+        .checked_add_days(Days::new(query.days.unwrap_or(1)))
+        .unwrap_or(NaiveDateTime::MAX)
+        - TimeDelta::seconds(1);
+
+    let mut sessions = catalog.sessions.clone().filter_by_time_range(start, end);
+    if let Some(film) = &query.film {
+        sessions = sessions.filter_by_film(film);
+    }
+    if let Some(screen) = query.screen {
+        sessions = sessions.filter_by_screen(screen);
+    }
+    if let Some(attribute) = &query.attribute {
+        sessions = sessions.filter_containing_attribute(attribute);
+    }
+
+    let days = sessions
+        .group_by_date()
+        .into_iter()
+        .map(|(date, mut sessions)| {
+            sessions.sort_by_key(|session| session.feature_start_time);
+            let mut films: Vec<ShowtimeFilm> = Vec::new();
+            let mut film_ids: Vec<&FilmId> = Vec::new();
+            for session in sessions {
+                let summary = catalog.session(session);
+                if let Some(index) = film_ids.iter().position(|id| **id == session.film_id) {
+                    films[index].sessions.push(summary);
+                } else {
+                    film_ids.push(&session.film_id);
+                    films.push(ShowtimeFilm {
+                        film: catalog.film(&session.film_id).map(FilmSummary::new),
+                        title: session.title.clone(),
+                        sessions: vec![summary],
+                    });
+                }
+            }
+            ShowtimeDay { date, films }
+        })
+        .collect();
+    Ok(Json(days))
+}
+
+/// Internal handler for `GET /films/now-playing`
+async fn now_playing_films(
+    State(state): State<ProxyState>,
+    Query(query): Query<FilmsQuery>,
+) -> ProxyResult<Response> {
+    let Some(window) = film_window(&query) else {
+        return Ok(invalid_film_window());
+    };
+    let catalog = Catalog::fetch(&state.client).await?;
+    Ok(
+        Json(catalog.showings(window, &[PlayingStatus::NowPlaying], NEXT_SESSION_COUNT))
+            .into_response(),
+    )
+}
+
+/// Internal handler for `GET /films/coming-soon`
+async fn coming_soon_films(
+    State(state): State<ProxyState>,
+    Query(query): Query<FilmsQuery>,
+) -> ProxyResult<Response> {
+    let Some(window) = film_window(&query) else {
+        return Ok(invalid_film_window());
+    };
+    let catalog = Catalog::fetch(&state.client).await?;
+    Ok(Json(catalog.showings(
+        window,
+        &[PlayingStatus::AdvanceSales, PlayingStatus::ComingSoon],
+        NEXT_SESSION_COUNT,
+    ))
+    .into_response())
+}
+
+/// Internal handler for `GET /screens/{id}/timeline`
+async fn screen_timeline(
+    State(state): State<ProxyState>,
+    Path(id): Path<ScreenId>,
+    Query(query): Query<TimelineQuery>,
+) -> ProxyResult<Json<ScreenTimeline>> {
+    let business_day = match query.date {
+        Some(date) => date,
+        None => state.client.get_site().await?.now().date(),
+    };
+    let screen = state.client.get_screen(id).await?;
+    Ok(Json(screen.timeline(&state.client, business_day).await?))
+}
@@ -5,23 +5,29 @@
 
 use std::{
     fmt::{self, Debug, Display, Formatter},
+    slice,
     vec::IntoIter,
 };
 
-use chrono::{NaiveDate, NaiveDateTime};
-use serde::Deserialize;
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "blocking")]
+use crate::blocking;
 use crate::{
     attr::{Attribute, AttributeId},
     client::Client,
     error::ApiResult,
-    film::{Film, FilmFormat, FilmId},
+    film::{Film, FilmFormat, FilmId, PlayingStatus},
+    ical,
     package::{FilmPackage, FilmPackageId},
     screen::{Screen, ScreenId},
+    site::Site,
 };
 
 /// The seating type for a particular [Session]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "PascalCase")]
 pub enum Seating {
     /// Allocated (reserved) seating
@@ -31,9 +37,19 @@ pub enum Seating {
     /// Unallocated (general admission) seating
     Open,
 }
+impl Display for Seating {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Allocated => "Allocated",
+            Self::Select => "Select",
+            Self::Open => "Open",
+        })
+    }
+}
 
 /// The show type for a particular [Session]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "PascalCase")]
 pub enum ShowType {
     /// Private show not available to the general public
@@ -41,9 +57,18 @@ pub enum ShowType {
     /// Public show
     Public,
 }
+impl Display for ShowType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Private => "Private",
+            Self::Public => "Public",
+        })
+    }
+}
 
 /// The status of a particular [Session]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "PascalCase")]
 pub enum SessionStatus {
     /// Open, tickets can be sold
@@ -53,6 +78,15 @@ pub enum SessionStatus {
     /// Planned, session is planned but not yet open for sales
     Planned,
 }
+impl Display for SessionStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Open => "Open",
+            Self::Closed => "Closed",
+            Self::Planned => "Planned",
+        })
+    }
+}
 
 /// The sales channels via which tickets for a particular [Session] can be sold
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -95,9 +129,39 @@ impl<'de> Deserialize<'de> for SalesVia {
         Ok(sales_via)
     }
 }
+impl Serialize for SalesVia {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.channels().serialize(serializer)
+    }
+}
+impl SalesVia {
+    /// Get the names of the sales channels enabled in this [`SalesVia`], as
+    /// used by the Veezi API
+    #[must_use]
+    pub fn channels(&self) -> Vec<&'static str> {
+        [
+            (self.kiosk, "KIOSK"),
+            (self.pos, "POS"),
+            (self.www, "WWW"),
+            (self.mx, "MX"),
+            (self.rsp, "RSP"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect()
+    }
+}
+impl Display for SalesVia {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.channels().join(", "))
+    }
+}
 
 /// A list of [Session]s with some useful helper methods
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct SessionList(Vec<Session>);
 impl SessionList {
     /// Obtain the [`Vec<Session>`] contained within this [`SessionList`]
@@ -188,7 +252,7 @@ impl SessionList {
                 grouped.push((date, vec![session]));
             }
         }
-        grouped.sort_by(|(a, _), (b, _)| a.cmp(b));
+        grouped.sort_by_key(|(date, _)| *date);
         grouped
     }
 
@@ -210,6 +274,16 @@ impl SessionList {
         Ok(films)
     }
 
+    /// Blocking version of [`Self::films`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any of the API requests fail.
+    #[cfg(feature = "blocking")]
+    pub fn films_blocking(&self, client: &blocking::Client) -> ApiResult<Vec<Film>> {
+        client.block_on(self.films(client.inner()))
+    }
+
     /// Get all of the screens represented in this [`SessionList`]
     ///
     /// # Errors
@@ -228,10 +302,114 @@ impl SessionList {
         Ok(screens)
     }
 
+    /// Blocking version of [`Self::screens`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any of the API requests fail.
+    #[cfg(feature = "blocking")]
+    pub fn screens_blocking(&self, client: &blocking::Client) -> ApiResult<Vec<Screen>> {
+        client.block_on(self.screens(client.inner()))
+    }
+
     /// Get an iterator over the sessions in this [`SessionList`]
     pub fn iter(&self) -> impl Iterator<Item = &Session> {
         self.0.iter()
     }
+
+    /// Classify a [`Film`] as now playing, on advance sale or coming soon
+    /// based on its [`Film::opening_date`] and its public sessions in this
+    /// [`SessionList`]
+    ///
+    /// A film that has opened is [`PlayingStatus::NowPlaying`] if it has a
+    /// session starting within `window` of `now`, or
+    /// [`PlayingStatus::ComingSoon`] if its first session is further away; a
+    /// window too large to add to `now` has no upper bound. A
+    /// film that has not opened yet is [`PlayingStatus::AdvanceSales`] if any
+    /// of its sessions is open for sales, otherwise
+    /// [`PlayingStatus::ComingSoon`]. Returns `None` for a film that has opened
+    /// but has no upcoming sessions.
+    #[must_use]
+    pub fn classify_film(
+        &self,
+        film: &Film,
+        now: NaiveDateTime,
+        window: TimeDelta,
+    ) -> Option<PlayingStatus> {
+        let upcoming: Vec<&Session> = self
+            .0
+            .iter()
+            .filter(|session| {
+                session.film_id == film.id
+                    && session.show_type == ShowType::Public
+                    && session.feature_start_time >= now
+            })
+            .collect();
+
+        if film.opening_date > now {
+            if upcoming
+                .iter()
+                .any(|session| session.is_open_for_sales_at(now))
+            {
+                return Some(PlayingStatus::AdvanceSales);
+            }
+            return Some(PlayingStatus::ComingSoon);
+        }
+
+        let first_start = upcoming
+            .iter()
+            .map(|session| session.feature_start_time)
+            .min()?;
+        if now
+            .checked_add_signed(window)
+            .is_none_or(|end| first_start < end)
+        {
+            Some(PlayingStatus::NowPlaying)
+        } else {
+            Some(PlayingStatus::ComingSoon)
+        }
+    }
+
+    /// Get the next session of a given film that can be booked online, as
+    /// determined by [`Session::is_open_for_web_sales_at`]
+    #[must_use]
+    pub fn next_session_for_film(&self, film_id: &FilmId, now: NaiveDateTime) -> Option<&Session> {
+        self.0
+            .iter()
+            .filter(|session| session.film_id == *film_id && session.is_open_for_web_sales_at(now))
+            .min_by_key(|session| session.feature_start_time)
+    }
+
+    /// Get up to `count` of the next sessions on a given screen that can be
+    /// booked online, as determined by [`Session::is_open_for_web_sales_at`],
+    /// ordered by their feature start time
+    #[must_use]
+    pub fn next_sessions_on_screen(
+        &self,
+        screen_id: ScreenId,
+        now: NaiveDateTime,
+        count: usize,
+    ) -> Vec<&Session> {
+        let mut sessions: Vec<&Session> = self
+            .0
+            .iter()
+            .filter(|session| {
+                session.screen_id == screen_id && session.is_open_for_web_sales_at(now)
+            })
+            .collect();
+        sessions.sort_by_key(|session| session.feature_start_time);
+        sessions.truncate(count);
+        sessions
+    }
+
+    /// Render this [`SessionList`] as an iCalendar document with one event per
+    /// session
+    ///
+    /// See the [`crate::ical`] module for details.
+    #[must_use]
+    pub fn to_ical(&self, screens: &[Screen], site: &Site) -> String {
+        ical::to_ical(&self.0, screens, site)
+    }
 }
 impl From<Vec<Session>> for SessionList {
     fn from(sessions: Vec<Session>) -> Self {
@@ -253,9 +431,9 @@ impl IntoIterator for SessionList {
 }
 
 /// The unique ID of a [`Session`]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 #[serde(transparent)]
-pub struct SessionId(u32);
+pub struct SessionId(pub(crate) u32);
 impl SessionId {
     /// Get the numeric ID of this [`SessionId`]
     #[must_use]
@@ -271,6 +449,16 @@ impl SessionId {
     pub async fn fetch(self, client: &Client) -> ApiResult<Session> {
         client.get_session(self).await
     }
+
+    /// Blocking version of [`Self::fetch`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(self, client: &blocking::Client) -> ApiResult<Session> {
+        client.block_on(self.fetch(client.inner()))
+    }
 }
 impl Display for SessionId {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -279,7 +467,7 @@ impl Display for SessionId {
 }
 
 /// A particular screening session of a [Film]
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Session {
     /// The unique ID of the session
@@ -343,6 +531,16 @@ impl Session {
         client.get_film(&self.film_id).await
     }
 
+    /// Blocking version of [`Self::film`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn film_blocking(&self, client: &blocking::Client) -> ApiResult<Film> {
+        client.block_on(self.film(client.inner()))
+    }
+
     /// Get the [`FilmPackage`] associated with this [`Session`], if any
     ///
     /// # Errors
@@ -358,6 +556,19 @@ impl Session {
         }
     }
 
+    /// Blocking version of [`Self::film_package`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn film_package_blocking(
+        &self,
+        client: &blocking::Client,
+    ) -> ApiResult<Option<FilmPackage>> {
+        client.block_on(self.film_package(client.inner()))
+    }
+
     /// Get the [`Screen`] associated with this [`Session`]
     ///
     /// # Errors
@@ -367,6 +578,16 @@ impl Session {
         client.get_screen(self.screen_id).await
     }
 
+    /// Blocking version of [`Self::screen`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn screen_blocking(&self, client: &blocking::Client) -> ApiResult<Screen> {
+        client.block_on(self.screen(client.inner()))
+    }
+
     /// Get the list of [Attribute]s associated with this [`Session`]
     ///
     /// # Errors
@@ -381,12 +602,223 @@ impl Session {
         Ok(attrs)
     }
 
+    /// Blocking version of [`Self::attributes`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the API request fails.
+    #[cfg(feature = "blocking")]
+    pub fn attributes_blocking(&self, client: &blocking::Client) -> ApiResult<Vec<Attribute>> {
+        client.block_on(self.attributes(client.inner()))
+    }
+
+    /// Render this [`Session`] as an iCalendar document containing a single
+    /// event
+    ///
+    /// See the [`crate::ical`] module for details.
+    #[must_use]
+    pub fn to_ical(&self, screen: Option<&Screen>, site: &Site) -> String {
+        ical::to_ical(
+            [self],
+            screen.map(slice::from_ref).unwrap_or_default(),
+            site,
+        )
+    }
+
     /// Returns whether tickets can still be sold for this session
     #[must_use]
+    #[deprecated(
+        note = "uses UTC rather than the site's local time; use `is_open_for_sales_at_site`"
+    )]
     pub fn is_open_for_sales(&self) -> bool {
-        let now = chrono::Utc::now().naive_utc();
+        self.is_open_for_sales_at(chrono::Utc::now().naive_utc())
+    }
+
+    /// Returns whether tickets can still be sold for this session, as of the
+    /// current local time at the given [`Site`]
+    #[must_use]
+    pub fn is_open_for_sales_at_site(&self, site: &Site) -> bool {
+        self.is_open_for_sales_at(site.now())
+    }
+
+    /// Returns whether tickets can be sold for this session at the given local
+    /// time at its site
+    #[must_use]
+    pub fn is_open_for_sales_at(&self, now: NaiveDateTime) -> bool {
         self.status == SessionStatus::Open
             && now < self.sales_cut_off_time
             && self.seats_available > 0
     }
+
+    /// Returns whether tickets can still be sold online for this session
+    #[must_use]
+    #[deprecated(
+        note = "uses UTC rather than the site's local time; use `is_open_for_web_sales_at_site`"
+    )]
+    pub fn is_open_for_web_sales(&self) -> bool {
+        self.is_open_for_web_sales_at(chrono::Utc::now().naive_utc())
+    }
+
+    /// Returns whether tickets can still be sold online for this session, as
+    /// of the current local time at the given [`Site`]
+    #[must_use]
+    pub fn is_open_for_web_sales_at_site(&self, site: &Site) -> bool {
+        self.is_open_for_web_sales_at(site.now())
+    }
+
+    /// Returns whether tickets can be sold online for this session at the
+    /// given local time at its site
+    ///
+    /// In addition to [`Session::is_open_for_sales_at`], this requires a
+    /// [`ShowType::Public`] show that allows [`SalesVia::www`] sales.
+    #[must_use]
+    pub fn is_open_for_web_sales_at(&self, now: NaiveDateTime) -> bool {
+        self.show_type == ShowType::Public && self.sales_via.www && self.is_open_for_sales_at(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for classifying films and finding the next sessions
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder, SiteBuilder};
+
+    /// Internal helper to get a time on a day of June 2025
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, day)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .expect("the time should exist")
+    }
+
+    /// Internal helper to build a film opening on a day of June 2025
+    fn film_opening(day: u32) -> Film {
+        FilmBuilder::new("ST00000001")
+            .with_opening_date(at(day, 0))
+            .build()
+    }
+
+    #[test]
+    fn opened_films_are_classified_by_window() {
+        let film = film_opening(1);
+        let screen = ScreenBuilder::new(1).build();
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screen, at(1, 10)).build(),
+            SessionBuilder::new(2, &film, &screen, at(4, 18)).build(),
+        ]);
+        let now = at(1, 12);
+
+        assert_eq!(
+            sessions.classify_film(&film, now, TimeDelta::days(7)),
+            Some(PlayingStatus::NowPlaying)
+        );
+        assert_eq!(
+            sessions.classify_film(&film, now, TimeDelta::days(1)),
+            Some(PlayingStatus::ComingSoon)
+        );
+        assert_eq!(
+            sessions.classify_film(&film, now, TimeDelta::MAX),
+            Some(PlayingStatus::NowPlaying)
+        );
+        assert_eq!(
+            sessions.classify_film(&film, at(5, 0), TimeDelta::days(7)),
+            None
+        );
+    }
+
+    #[test]
+    fn unopened_films_are_on_advance_sale_once_sessions_open() {
+        let film = film_opening(10);
+        let screen = ScreenBuilder::new(1).build();
+        let open = SessionBuilder::new(1, &film, &screen, at(10, 18)).build();
+        let planned = SessionBuilder::new(2, &film, &screen, at(10, 21))
+            .with_status(SessionStatus::Planned)
+            .build();
+        let now = at(1, 12);
+
+        let sessions = SessionList::from(vec![open, planned.clone()]);
+        assert_eq!(
+            sessions.classify_film(&film, now, TimeDelta::days(7)),
+            Some(PlayingStatus::AdvanceSales)
+        );
+        let sessions = SessionList::from(vec![planned]);
+        assert_eq!(
+            sessions.classify_film(&film, now, TimeDelta::days(7)),
+            Some(PlayingStatus::ComingSoon)
+        );
+    }
+
+    #[test]
+    fn private_sessions_do_not_classify_films() {
+        let film = film_opening(1);
+        let screen = ScreenBuilder::new(1).build();
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screen, at(2, 18))
+                .with_show_type(ShowType::Private)
+                .build(),
+        ]);
+        assert_eq!(
+            sessions.classify_film(&film, at(1, 12), TimeDelta::days(7)),
+            None
+        );
+    }
+
+    #[test]
+    fn next_session_for_film_skips_unbookable_sessions() {
+        let film = film_opening(1);
+        let other = FilmBuilder::new("ST00000002").build();
+        let screen = ScreenBuilder::new(1).with_seats(100, 0).build();
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screen, at(1, 10)).build(),
+            SessionBuilder::new(2, &film, &screen, at(1, 14))
+                .with_seats_sold(100)
+                .build(),
+            SessionBuilder::new(3, &film, &screen, at(1, 16))
+                .with_show_type(ShowType::Private)
+                .build(),
+            SessionBuilder::new(4, &other, &screen, at(1, 17)).build(),
+            SessionBuilder::new(5, &film, &screen, at(1, 21)).build(),
+            SessionBuilder::new(6, &film, &screen, at(1, 18)).build(),
+        ]);
+
+        let next = sessions.next_session_for_film(&film.id, at(1, 12));
+        assert_eq!(next.map(|session| session.id), Some(SessionId(6)));
+        assert!(sessions.next_session_for_film(&film.id, at(2, 0)).is_none());
+    }
+
+    #[test]
+    fn next_sessions_on_screen_are_ordered_and_limited() {
+        let film = film_opening(1);
+        let screen = ScreenBuilder::new(1).build();
+        let other = ScreenBuilder::new(2).build();
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screen, at(1, 20)).build(),
+            SessionBuilder::new(2, &film, &other, at(1, 13)).build(),
+            SessionBuilder::new(3, &film, &screen, at(1, 14)).build(),
+            SessionBuilder::new(4, &film, &screen, at(1, 17)).build(),
+            SessionBuilder::new(5, &film, &screen, at(1, 10)).build(),
+        ]);
+
+        let ids: Vec<SessionId> = sessions
+            .next_sessions_on_screen(screen.id, at(1, 12), 2)
+            .into_iter()
+            .map(|session| session.id)
+            .collect();
+        assert_eq!(ids, [SessionId(3), SessionId(4)]);
+    }
+
+    #[test]
+    fn sales_follow_the_site_clock() {
+        let film = film_opening(1);
+        let screen = ScreenBuilder::new(1).build();
+        let site = SiteBuilder::new().build();
+        let now = site.now();
+        let upcoming = SessionBuilder::new(1, &film, &screen, now + TimeDelta::hours(2)).build();
+        let past = SessionBuilder::new(2, &film, &screen, now - TimeDelta::hours(2)).build();
+
+        assert!(upcoming.is_open_for_sales_at_site(&site));
+        assert!(upcoming.is_open_for_web_sales_at_site(&site));
+        assert!(!past.is_open_for_sales_at_site(&site));
+        assert!(!past.is_open_for_web_sales_at_site(&site));
+    }
 }
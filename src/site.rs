@@ -2,10 +2,12 @@
 
 use std::fmt::Debug;
 
-use serde::Deserialize;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
 
 /// Information about the current Veezi site
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Site {
     /// The name of the site
@@ -53,6 +55,63 @@ pub struct Site {
     /// The country where the site is located
     pub country: String,
     /// The list of screen IDs associated with the site
-    #[serde(deserialize_with = "crate::utils::deserialize_id_array")]
+    #[serde(
+        deserialize_with = "crate::utils::deserialize_id_array",
+        serialize_with = "crate::utils::serialize_id_array"
+    )]
     pub screens: Vec<u32>,
 }
+impl Site {
+    /// Get the [`Tz`] named by [`Site::time_zone_identifier`], if it is a known
+    /// IANA time zone identifier
+    #[must_use]
+    pub fn time_zone(&self) -> Option<Tz> {
+        self.time_zone_identifier.parse().ok()
+    }
+
+    /// Interpret a local time at this site (such as
+    /// [`crate::session::Session::feature_start_time`]) in the site's time
+    /// zone
+    ///
+    /// Returns `None` if the time zone is unknown or the local time does not
+    /// exist (for example, during a daylight saving time gap). Ambiguous local
+    /// times resolve to the earlier instant.
+    #[must_use]
+    pub fn localize(&self, time: NaiveDateTime) -> Option<DateTime<Tz>> {
+        self.time_zone()?.from_local_datetime(&time).earliest()
+    }
+
+    /// Get the current local time at this site, or the current UTC time if the
+    /// site's time zone is unknown
+    #[must_use]
+    pub fn now(&self) -> NaiveDateTime {
+        let now = Utc::now();
+        self.time_zone().map_or_else(
+            || now.naive_utc(),
+            |tz| now.with_timezone(&tz).naive_local(),
+        )
+    }
+
+    /// Get the non-empty lines of the site's street address
+    #[must_use]
+    pub fn street_address_lines(&self) -> Vec<&str> {
+        [&self.address_1, &self.address_2, &self.address_3]
+            .into_iter()
+            .filter_map(|line| line.as_deref().map(str::trim))
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    /// Get the non-empty lines of the site's street address, followed by its
+    /// post code
+    #[must_use]
+    pub fn address_lines(&self) -> Vec<&str> {
+        let mut lines = self.street_address_lines();
+        if let Some(post_code) = self.post_code.as_deref().map(str::trim)
+            && !post_code.is_empty()
+        {
+            lines.push(post_code);
+        }
+        lines
+    }
+}
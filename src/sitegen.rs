@@ -0,0 +1,775 @@
+//! A static HTML showtimes site generator
+//!
+//! The primary type is [`SiteGenerator`], which renders a [`Snapshot`] of a
+//! [`Client`] into a complete static site with no server-side component:
+//!
+//! - `index.html`, listing the films now showing and coming soon
+//! - `days/<YYYY-MM-DD>.html`, the schedule of each day
+//! - `films/<id>.html`, the details and showtimes of each film, including
+//!   schema.org JSON-LD markup (see [`crate::jsonld`])
+//! - `screens/<id>.html`, the schedule of each screen
+//!
+//! Pages are rendered with [`minijinja`] templates. The built-in templates can
+//! be replaced one at a time with [`SiteGenerator::with_template`] or from a
+//! directory with [`SiteGenerator::with_template_dir`]. Every page is rendered
+//! with the fields of [`PageContext`], and templates are free to extend
+//! `base.html` and import the `session_list` macro from `_macros.html`.
+//!
+//! This module is only available with the `sitegen` feature.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Debug, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Days, NaiveDate, NaiveDateTime, Utc};
+use minijinja::Environment;
+use serde::Serialize;
+use url::Url;
+
+use crate::{
+    attr::Attribute,
+    client::Client,
+    error::{ApiResult, LibVeeziError},
+    film::{Film, FilmStatus},
+    jsonld::ScreeningEvent,
+    screen::Screen,
+    session::{Session, SessionList},
+    site::Site,
+};
+
+/// The names and sources of the built-in templates
+const DEFAULT_TEMPLATES: &[(&str, &str)] = &[
+    ("base.html", include_str!("../templates/sitegen/base.html")),
+    (
+        "_macros.html",
+        include_str!("../templates/sitegen/_macros.html"),
+    ),
+    (
+        "index.html",
+        include_str!("../templates/sitegen/index.html"),
+    ),
+    ("day.html", include_str!("../templates/sitegen/day.html")),
+    ("film.html", include_str!("../templates/sitegen/film.html")),
+    (
+        "screen.html",
+        include_str!("../templates/sitegen/screen.html"),
+    ),
+];
+
+/// Internal helper to turn an ID into a string that is safe to use as a file
+/// name
+fn slug(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Internal helper to keep a URL for use in a link only if it is an `http` or
+/// `https` URL, so values such as `javascript:` URLs never reach a page
+fn web_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    Url::parse(url)
+        .ok()
+        .filter(|parsed| matches!(parsed.scheme(), "http" | "https"))
+        .map(|_| url.to_string())
+}
+
+/// Internal helper to build the context of a [`Site`]
+fn site_context(site: &Site) -> SiteContext {
+    SiteContext {
+        name: site.name.clone(),
+        address_lines: site
+            .address_lines()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        phone: site.phone_1.clone(),
+    }
+}
+
+/// Internal helper to build the context of a single [`Session`]
+fn session_context(snapshot: &Snapshot, session: &Session) -> SessionContext {
+    let screen = snapshot
+        .screens
+        .iter()
+        .find(|screen| screen.id == session.screen_id);
+    SessionContext {
+        title: session.title.clone(),
+        film_slug: slug(session.film_id.as_str()),
+        screen_name: screen.map_or_else(
+            || format!("Screen {}", session.screen_id),
+            |screen| screen.name.clone(),
+        ),
+        screen_slug: slug(&session.screen_id.to_string()),
+        start: session
+            .feature_start_time
+            .format("%Y-%m-%dT%H:%M:%S")
+            .to_string(),
+        time: session.feature_start_time.format("%H:%M").to_string(),
+        sold_out: session.tickets_sold_out,
+        few_tickets_left: session.few_tickets_left,
+        attributes: session
+            .attributes
+            .iter()
+            .filter_map(|id| {
+                snapshot
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.id == *id)
+            })
+            .map(|attribute| AttributeBadge {
+                short_name: attribute.short_name.clone(),
+                description: attribute.description.clone(),
+                font_color: attribute.font_color.clone(),
+                background_color: attribute.background_color.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// Internal helper to build the context of a single day, including the
+/// sessions that start on it
+fn day_context(snapshot: &Snapshot, day: NaiveDate, sessions: &[&Session]) -> DayContext {
+    DayContext {
+        slug: day.format("%Y-%m-%d").to_string(),
+        label: day.format("%A %-d %B").to_string(),
+        short_label: day.format("%a %-d").to_string(),
+        sessions: sessions
+            .iter()
+            .filter(|session| session.feature_start_time.date() == day)
+            .map(|session| session_context(snapshot, session))
+            .collect(),
+    }
+}
+
+/// Internal helper to group sessions by the day they start on
+fn group_days(snapshot: &Snapshot, sessions: &[&Session]) -> Vec<DayContext> {
+    let mut days: BTreeMap<NaiveDate, Vec<&Session>> = BTreeMap::new();
+    for session in sessions {
+        days.entry(session.feature_start_time.date())
+            .or_default()
+            .push(session);
+    }
+    days.into_iter()
+        .map(|(day, sessions)| day_context(snapshot, day, &sessions))
+        .collect()
+}
+
+/// Internal helper to build the context of a single [`Film`] with its
+/// sessions
+fn film_context(snapshot: &Snapshot, film: &Film, sessions: &[&Session]) -> FilmContext {
+    let json_ld = sessions
+        .iter()
+        .filter_map(|session| {
+            let screen = snapshot
+                .screens
+                .iter()
+                .find(|screen| screen.id == session.screen_id)?;
+            ScreeningEvent::new(session, film, screen, &snapshot.site)
+                .to_json_ld()
+                .ok()
+        })
+        .collect();
+
+    FilmContext {
+        slug: slug(film.id.as_str()),
+        title: film.title.clone(),
+        synopsis: film.synopsis.clone(),
+        rating: film.rating.clone(),
+        genre: film.genre.clone(),
+        duration: film.duration,
+        poster_url: film
+            .film_poster_url
+            .as_deref()
+            .or(Some(film.film_poster_thumbnail_url.as_str()))
+            .and_then(web_url),
+        trailer_url: film.film_trailer_url.as_deref().and_then(web_url),
+        opening_date: film.opening_date.format("%A %-d %B %Y").to_string(),
+        cast: film
+            .people
+            .iter()
+            .map(|person| PersonContext {
+                name: format!("{} {}", person.first_name, person.last_name)
+                    .trim()
+                    .to_string(),
+                role: person.role.clone(),
+            })
+            .collect(),
+        days: group_days(snapshot, sessions),
+        json_ld,
+    }
+}
+
+/// Internal helper to build the context of a single [`Screen`] with its
+/// sessions
+fn screen_context(snapshot: &Snapshot, screen: &Screen, sessions: &[&Session]) -> ScreenContext {
+    let screen_sessions: Vec<&Session> = sessions
+        .iter()
+        .copied()
+        .filter(|session| session.screen_id == screen.id)
+        .collect();
+    ScreenContext {
+        slug: slug(&screen.id.to_string()),
+        name: screen.name.clone(),
+        total_seats: screen.total_seats,
+        days: group_days(snapshot, &screen_sessions),
+    }
+}
+
+/// The list of errors that can occur when generating a static site
+#[derive(Debug)]
+pub enum SiteGenError {
+    /// An error occurred while fetching data from the Veezi API
+    Api(LibVeeziError),
+    /// An error occurred while loading or rendering a template
+    Template(minijinja::Error),
+    /// An error occurred while reading templates or writing pages
+    Io(io::Error),
+}
+impl Display for SiteGenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Api(err) => write!(f, "API error: {err}"),
+            Self::Template(err) => write!(f, "Template error: {err}"),
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+        }
+    }
+}
+impl Error for SiteGenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Api(err) => Some(err),
+            Self::Template(err) => Some(err),
+            Self::Io(err) => Some(err),
+        }
+    }
+}
+impl From<LibVeeziError> for SiteGenError {
+    fn from(err: LibVeeziError) -> Self {
+        Self::Api(err)
+    }
+}
+impl From<minijinja::Error> for SiteGenError {
+    fn from(err: minijinja::Error) -> Self {
+        Self::Template(err)
+    }
+}
+impl From<io::Error> for SiteGenError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Everything needed to render a static site, fetched from a [`Client`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Snapshot {
+    /// The site the snapshot was taken from
+    pub site: Site,
+    /// Every film at the site
+    pub films: Vec<Film>,
+    /// Every screen at the site
+    pub screens: Vec<Screen>,
+    /// The sessions that are available for web sales
+    pub sessions: SessionList,
+    /// Every attribute at the site
+    pub attributes: Vec<Attribute>,
+}
+impl Snapshot {
+    /// Take a [`Snapshot`] of the current state of a [`Client`]'s site
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any API request fails.
+    pub async fn fetch(client: &Client) -> ApiResult<Self> {
+        Ok(Self {
+            site: client.get_site().await?,
+            films: client.list_films().await?,
+            screens: client.list_screens().await?,
+            sessions: client.list_web_sessions().await?,
+            attributes: client.list_attributes().await?,
+        })
+    }
+}
+
+/// The site details available to templates
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct SiteContext {
+    /// The name of the site
+    pub name: String,
+    /// The address of the site, one line per entry
+    pub address_lines: Vec<String>,
+    /// The primary phone number of the site
+    pub phone: Option<String>,
+}
+
+/// An [`Attribute`] badge, styled with the attribute's colors
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct AttributeBadge {
+    /// [`Attribute::short_name`]
+    pub short_name: String,
+    /// [`Attribute::description`]
+    pub description: String,
+    /// [`Attribute::font_color`]
+    pub font_color: String,
+    /// [`Attribute::background_color`]
+    pub background_color: String,
+}
+
+/// A [`Session`] as shown in a schedule
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct SessionContext {
+    /// The title of the session
+    pub title: String,
+    /// The file name (without extension) of the film's page
+    pub film_slug: String,
+    /// The name of the screen, or its ID if the screen is unknown
+    pub screen_name: String,
+    /// The file name (without extension) of the screen's page
+    pub screen_slug: String,
+    /// The feature start time as an ISO 8601 date-time
+    pub start: String,
+    /// The feature start time of day (e.g., "19:30")
+    pub time: String,
+    /// Whether the session is sold out
+    pub sold_out: bool,
+    /// Whether there are only a few tickets left
+    pub few_tickets_left: bool,
+    /// The badges of the session's attributes
+    pub attributes: Vec<AttributeBadge>,
+}
+
+/// The sessions of a single day
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct DayContext {
+    /// The file name (without extension) of the day's page
+    pub slug: String,
+    /// The full name of the day (e.g., "Friday 30 October")
+    pub label: String,
+    /// The short name of the day (e.g., "Fri 30")
+    pub short_label: String,
+    /// The sessions on the day, ordered by feature start time
+    pub sessions: Vec<SessionContext>,
+}
+
+/// A person in the cast or crew of a film
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct PersonContext {
+    /// The full name of the person
+    pub name: String,
+    /// The role of the person (e.g., "Actor")
+    pub role: String,
+}
+
+/// A [`Film`] with its showtimes
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct FilmContext {
+    /// The file name (without extension) of the film's page
+    pub slug: String,
+    /// [`Film::title`]
+    pub title: String,
+    /// [`Film::synopsis`]
+    pub synopsis: Option<String>,
+    /// [`Film::rating`]
+    pub rating: Option<String>,
+    /// [`Film::genre`]
+    pub genre: String,
+    /// [`Film::duration`]
+    pub duration: u32,
+    /// The poster of the film, if it has an `http` or `https` URL
+    pub poster_url: Option<String>,
+    /// [`Film::film_trailer_url`], if it is an `http` or `https` URL
+    pub trailer_url: Option<String>,
+    /// The opening date of the film (e.g., "Friday 30 October 2026")
+    pub opening_date: String,
+    /// [`Film::people`]
+    pub cast: Vec<PersonContext>,
+    /// The film's sessions, grouped by day
+    pub days: Vec<DayContext>,
+    /// The schema.org `ScreeningEvent` JSON-LD of each session, safe to embed
+    /// in a `<script>` element
+    pub json_ld: Vec<String>,
+}
+
+/// A [`Screen`] with its showtimes
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct ScreenContext {
+    /// The file name (without extension) of the screen's page
+    pub slug: String,
+    /// [`Screen::name`]
+    pub name: String,
+    /// [`Screen::total_seats`]
+    pub total_seats: u32,
+    /// The screen's sessions, grouped by day
+    pub days: Vec<DayContext>,
+}
+
+/// The context every page is rendered with
+///
+/// Only the field matching the page's template is set: `day` for
+/// `day.html`, `film` for `film.html` and `screen` for `screen.html`.
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct PageContext<'a> {
+    /// The relative path from the page to the root of the site (e.g., "../")
+    pub root: &'static str,
+    /// The time the site was generated
+    pub generated: &'a str,
+    /// The site details
+    pub site: &'a SiteContext,
+    /// Every day in the schedule
+    pub days: &'a [DayContext],
+    /// The films with sessions in the schedule, by display sequence
+    pub now_showing: &'a [FilmContext],
+    /// The films that open after the schedule, by display sequence
+    pub coming_soon: &'a [FilmContext],
+    /// The day of a `day.html` page
+    pub day: Option<&'a DayContext>,
+    /// The film of a `film.html` page
+    pub film: Option<&'a FilmContext>,
+    /// The screen of a `screen.html` page
+    pub screen: Option<&'a ScreenContext>,
+}
+
+/// A single rendered page of a static site
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Page {
+    /// The path of the page, relative to the root of the site
+    pub path: PathBuf,
+    /// The rendered HTML of the page
+    pub contents: String,
+}
+
+/// A generator of static showtimes sites
+pub struct SiteGenerator {
+    /// The templates pages are rendered with
+    templates: Environment<'static>,
+    /// The number of days to include in the schedule
+    pub days: u64,
+}
+impl Debug for SiteGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SiteGenerator")
+            .field("days", &self.days)
+            .finish_non_exhaustive()
+    }
+}
+impl SiteGenerator {
+    /// The number of days included in the schedule by default
+    pub const DEFAULT_DAYS: u64 = 7;
+
+    /// Create a new [`SiteGenerator`] with the built-in templates
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn new() -> Self {
+        let mut templates = Environment::new();
+        templates.set_trim_blocks(true);
+        templates.set_lstrip_blocks(true);
+        for (name, source) in DEFAULT_TEMPLATES {
+            templates
+                .add_template(name, source)
+                .expect("built-in templates should be valid");
+        }
+        Self {
+            templates,
+            days: Self::DEFAULT_DAYS,
+        }
+    }
+
+    /// Set the number of days to include in the schedule, starting today
+    #[must_use]
+    pub const fn with_days(mut self, days: u64) -> Self {
+        self.days = days;
+        self
+    }
+
+    /// Replace one of the built-in templates (`base.html`, `_macros.html`,
+    /// `index.html`, `day.html`, `film.html` or `screen.html`), or add a new
+    /// template for the others to include
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the template is invalid.
+    pub fn with_template(mut self, name: String, source: String) -> Result<Self, SiteGenError> {
+        self.templates.add_template_owned(name, source)?;
+        Ok(self)
+    }
+
+    /// Load every `.html` file in a directory as a template, replacing any
+    /// built-in template with the same name
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory cannot be read or
+    /// any template is invalid.
+    pub fn with_template_dir(mut self, dir: &Path) -> Result<Self, SiteGenError> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "html") {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let name = name.to_string();
+            self = self.with_template(name, fs::read_to_string(&path)?)?;
+        }
+        Ok(self)
+    }
+
+    /// Render every page of the site for a [`Snapshot`]
+    ///
+    /// `now` is the current local time at the site (see [`Site::now`]); the
+    /// schedule covers [`SiteGenerator::days`] days starting from its date, and
+    /// sessions that have already started are left out.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any template fails to render.
+    pub fn render(
+        &self,
+        snapshot: &Snapshot,
+        now: NaiveDateTime,
+    ) -> Result<Vec<Page>, SiteGenError> {
+        let today = now.date();
+        let end = today
+            .checked_add_days(Days::new(self.days))
+            .unwrap_or(NaiveDate::MAX);
+        let mut sessions: Vec<&Session> = snapshot
+            .sessions
+            .iter()
+            .filter(|session| {
+                session.feature_start_time >= now && session.feature_start_time.date() < end
+            })
+            .collect();
+        sessions.sort_by_key(|session| session.feature_start_time);
+
+        let site = site_context(&snapshot.site);
+        let generated = Utc::now().format("%Y-%m-%d %H:%M UTC").to_string();
+
+        let mut days: Vec<DayContext> = today
+            .iter_days()
+            .take_while(|day| *day < end)
+            .map(|day| day_context(snapshot, day, &sessions))
+            .collect();
+        // Days are always listed, but empty days after the last session are not
+        while days.last().is_some_and(|day| day.sessions.is_empty()) && days.len() > 1 {
+            days.pop();
+        }
+
+        let mut films: Vec<&Film> = snapshot.films.iter().collect();
+        films.sort_by_key(|film| film.display_sequence);
+        let mut now_showing = Vec::new();
+        let mut coming_soon = Vec::new();
+        for film in films {
+            let film_sessions: Vec<&Session> = sessions
+                .iter()
+                .copied()
+                .filter(|session| session.film_id == film.id)
+                .collect();
+            if !film_sessions.is_empty() {
+                now_showing.push(film_context(snapshot, film, &film_sessions));
+            } else if film.status == FilmStatus::Active && film.opening_date > now {
+                coming_soon.push(film_context(snapshot, film, &film_sessions));
+            } else {
+                // Films without sessions in the schedule are not listed
+            }
+        }
+
+        let screens: Vec<ScreenContext> = snapshot
+            .screens
+            .iter()
+            .map(|screen| screen_context(snapshot, screen, &sessions))
+            .collect();
+
+        let base = PageContext {
+            root: "",
+            generated: &generated,
+            site: &site,
+            days: &days,
+            now_showing: &now_showing,
+            coming_soon: &coming_soon,
+            day: None,
+            film: None,
+            screen: None,
+        };
+
+        let mut pages = vec![self.render_page(PathBuf::from("index.html"), "index.html", &base)?];
+        for day in &days {
+            let context = PageContext {
+                root: "../",
+                day: Some(day),
+                ..base.clone()
+            };
+            let path = Path::new("days").join(format!("{}.html", day.slug));
+            pages.push(self.render_page(path, "day.html", &context)?);
+        }
+        for film in now_showing.iter().chain(&coming_soon) {
+            let context = PageContext {
+                root: "../",
+                film: Some(film),
+                ..base.clone()
+            };
+            let path = Path::new("films").join(format!("{}.html", film.slug));
+            pages.push(self.render_page(path, "film.html", &context)?);
+        }
+        for screen in &screens {
+            let context = PageContext {
+                root: "../",
+                screen: Some(screen),
+                ..base.clone()
+            };
+            let path = Path::new("screens").join(format!("{}.html", screen.slug));
+            pages.push(self.render_page(path, "screen.html", &context)?);
+        }
+        Ok(pages)
+    }
+
+    /// Render every page of the site for a [`Snapshot`] and write them to a
+    /// directory, returning the paths of the written pages
+    ///
+    /// See [`SiteGenerator::render`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any template fails to render or
+    /// any page cannot be written.
+    pub fn generate(
+        &self,
+        snapshot: &Snapshot,
+        now: NaiveDateTime,
+        out_dir: &Path,
+    ) -> Result<Vec<PathBuf>, SiteGenError> {
+        let mut written = Vec::new();
+        for page in self.render(snapshot, now)? {
+            let path = out_dir.join(&page.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, page.contents)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Internal helper to render a single page with the given template
+    fn render_page(
+        &self,
+        path: PathBuf,
+        template: &str,
+        context: &PageContext<'_>,
+    ) -> Result<Page, SiteGenError> {
+        Ok(Page {
+            path,
+            contents: self.templates.get_template(template)?.render(context)?,
+        })
+    }
+}
+impl Default for SiteGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for generating static sites
+
+    use std::{env, process};
+
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::*;
+    use crate::testing::ScheduleGenerator;
+
+    /// Internal helper to take a [`Snapshot`] of two days of fixtures, with a
+    /// trailer URL on the first film
+    fn snapshot(trailer_url: &str) -> (Snapshot, NaiveDateTime) {
+        let start = NaiveDate::from_ymd_opt(2025, 6, 1).expect("the start date should exist");
+        let mut fixtures = ScheduleGenerator::new(start).with_days(2).generate();
+        fixtures.films[0].film_trailer_url = Some(trailer_url.to_string());
+        let snapshot = Snapshot {
+            site: fixtures.site,
+            films: fixtures.films,
+            screens: fixtures.screens,
+            sessions: SessionList::from(fixtures.sessions),
+            attributes: fixtures.attributes,
+        };
+        (snapshot, start.and_time(NaiveTime::MIN))
+    }
+
+    #[test]
+    fn generates_every_page() {
+        let (snapshot, now) = snapshot("https://example.com/trailer");
+        let out_dir = env::temp_dir().join(format!("libveezi-sitegen-{}", process::id()));
+
+        let written = SiteGenerator::new()
+            .generate(&snapshot, now, &out_dir)
+            .expect("the site should generate");
+        let index = fs::read_to_string(out_dir.join("index.html"));
+        let film = fs::read_to_string(out_dir.join("films").join("ST00000001.html"));
+        let day = out_dir.join("days").join("2025-06-02.html").exists();
+        let screen = out_dir.join("screens").join("1.html").exists();
+        fs::remove_dir_all(&out_dir).expect("the site should be removed");
+
+        assert_eq!(
+            written.len(),
+            1 + 2 + snapshot.films.len() + snapshot.screens.len()
+        );
+        assert!(
+            index
+                .expect("the index should be written")
+                .contains(&snapshot.site.name)
+        );
+        let film = film.expect("the film page should be written");
+        assert!(film.contains("Watch the trailer"));
+        assert!(film.contains("application/ld+json"));
+        assert!(day);
+        assert!(screen);
+    }
+
+    #[test]
+    fn templates_can_be_overridden() {
+        let (snapshot, now) = snapshot("https://example.com/trailer");
+        let pages = SiteGenerator::new()
+            .with_template(
+                "index.html".to_string(),
+                "Welcome to {{ site.name }}".to_string(),
+            )
+            .expect("the template should be valid")
+            .render(&snapshot, now)
+            .expect("the site should render");
+
+        let index = pages
+            .iter()
+            .find(|page| page.path == Path::new("index.html"))
+            .expect("the index should be rendered");
+        assert_eq!(index.contents, format!("Welcome to {}", snapshot.site.name));
+    }
+
+    #[test]
+    fn only_web_trailer_urls_are_linked() {
+        for trailer_url in ["javascript:alert(1)", "data:text/html,hi", "not a url"] {
+            let (snapshot, now) = snapshot(trailer_url);
+            let pages = SiteGenerator::new()
+                .render(&snapshot, now)
+                .expect("the site should render");
+            let film = pages
+                .iter()
+                .find(|page| page.path == Path::new("films").join("ST00000001.html"))
+                .expect("the film page should be rendered");
+            assert!(
+                !film.contents.contains("Watch the trailer"),
+                "{trailer_url}"
+            );
+        }
+    }
+}
@@ -0,0 +1,859 @@
+//! Test fixture builders and fake data generators for every model type
+//!
+//! Each model type has a builder with sensible defaults, so a test only needs
+//! to set the fields it cares about. Builders for types that reference other
+//! objects take those objects, so the resulting IDs are always consistent:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use libveezi::testing::{FilmBuilder, ScreenBuilder, SessionBuilder};
+//!
+//! let film = FilmBuilder::new("ST00000001").with_title("Reel Time").build();
+//! let screen = ScreenBuilder::new(1).build();
+//! let start = NaiveDate::from_ymd_opt(2025, 6, 1)
+//!     .and_then(|date| date.and_hms_opt(19, 0, 0))
+//!     .unwrap_or_default();
+//! let session = SessionBuilder::new(1, &film, &screen, start)
+//!     .with_seats_sold(20)
+//!     .build();
+//! assert_eq!(session.film_id, film.id);
+//! assert_eq!(session.title, "Reel Time");
+//! ```
+//!
+//! For tests that need a whole cinema, [`ScheduleGenerator`] generates a
+//! coherent [`FixtureSet`] of films, screens, attributes and a week of
+//! sessions for a multi-screen site.
+//!
+//! This module is only available with the `testing` feature.
+
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike, Weekday};
+
+#[cfg(feature = "mock")]
+use crate::mock::MockVeezi;
+use crate::{
+    attr::{Attribute, AttributeId},
+    film::{Film, FilmFormat, FilmId, FilmStatus, Person, PersonId},
+    package::{FilmPackage, FilmPackageId, PackageFilm},
+    screen::{Screen, ScreenId},
+    session::{SalesVia, Seating, Session, SessionId, SessionList, SessionStatus, ShowType},
+    site::Site,
+};
+
+/// The titles used for generated films, in order
+const FILM_TITLES: [&str; 8] = [
+    "Reel Time",
+    "The Long Projection",
+    "Matinee Idol",
+    "Coming Attractions",
+    "Intermission",
+    "The Last Reel",
+    "Popcorn Season",
+    "Double Exposure",
+];
+
+/// The genres used for generated films, in order
+const FILM_GENRES: [&str; 4] = ["Drama", "Comedy", "Thriller", "Family"];
+
+/// The ratings used for generated films, in order
+const FILM_RATINGS: [&str; 4] = ["PG", "PG-13", "R", "G"];
+
+/// The trailer duration of sessions built by [`SessionBuilder`], in minutes
+const DEFAULT_TRAILER_MINUTES: i64 = 15;
+
+/// The cleanup duration of sessions built by [`SessionBuilder`], in minutes
+const DEFAULT_CLEANUP_MINUTES: i64 = 15;
+
+/// Internal helper to build a [`NaiveDateTime`] from a date and a time of day
+#[allow(clippy::missing_panics_doc)]
+const fn at(date: NaiveDate, hour: u32, minute: u32) -> NaiveDateTime {
+    date.and_time(NaiveTime::from_hms_opt(hour, minute, 0).expect("time of day should be valid"))
+}
+
+/// A builder for [`Person`] fixtures
+#[derive(Debug, Clone)]
+pub struct PersonBuilder {
+    /// The person being built
+    person: Person,
+}
+impl PersonBuilder {
+    /// Create a new [`PersonBuilder`] for an actor with the given ID
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self {
+            person: Person {
+                id: PersonId(id.to_string()),
+                first_name: "Jane".to_string(),
+                last_name: "Doe".to_string(),
+                role: "Actor".to_string(),
+            },
+        }
+    }
+
+    /// Set the first and last name of the person
+    #[must_use]
+    pub fn with_name(mut self, first_name: &str, last_name: &str) -> Self {
+        self.person.first_name = first_name.to_string();
+        self.person.last_name = last_name.to_string();
+        self
+    }
+
+    /// Set the role of the person (e.g., "Director")
+    #[must_use]
+    pub fn with_role(mut self, role: &str) -> Self {
+        self.person.role = role.to_string();
+        self
+    }
+
+    /// Build the [`Person`]
+    #[must_use]
+    pub fn build(self) -> Person {
+        self.person
+    }
+}
+
+/// A builder for [`Film`] fixtures
+#[derive(Debug, Clone)]
+pub struct FilmBuilder {
+    /// The film being built
+    film: Film,
+}
+impl FilmBuilder {
+    /// Create a new [`FilmBuilder`] for an active, two hour, 2D digital film
+    /// with the given ID
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn new(id: &str) -> Self {
+        let title = format!("Film {id}");
+        Self {
+            film: Film {
+                id: FilmId(id.to_string()),
+                short_name: title.clone(),
+                synopsis: Some(format!("The synopsis of {title}.")),
+                genre: "Drama".to_string(),
+                signage_text: title.clone(),
+                title,
+                distributor: "Libveezi Pictures".to_string(),
+                opening_date: at(
+                    NaiveDate::from_ymd_opt(2025, 1, 1).expect("opening date should be valid"),
+                    0,
+                    0,
+                ),
+                rating: Some("PG".to_string()),
+                status: FilmStatus::Active,
+                content: None,
+                duration: 120,
+                display_sequence: 0,
+                national_code: None,
+                format: FilmFormat::Digital2D,
+                is_restricted: false,
+                people: Vec::new(),
+                audio_language: None,
+                government_film_title: None,
+                film_poster_url: None,
+                film_poster_thumbnail_url: String::new(),
+                backdrop_image_url: None,
+                film_trailer_url: None,
+            },
+        }
+    }
+
+    /// Set the title of the film, along with its short name and signage text
+    #[must_use]
+    pub fn with_title(mut self, title: &str) -> Self {
+        title.clone_into(&mut self.film.title);
+        title.clone_into(&mut self.film.short_name);
+        title.clone_into(&mut self.film.signage_text);
+        self
+    }
+
+    /// Set the synopsis of the film
+    #[must_use]
+    pub fn with_synopsis(mut self, synopsis: Option<&str>) -> Self {
+        self.film.synopsis = synopsis.map(str::to_string);
+        self
+    }
+
+    /// Set the genre of the film
+    #[must_use]
+    pub fn with_genre(mut self, genre: &str) -> Self {
+        genre.clone_into(&mut self.film.genre);
+        self
+    }
+
+    /// Set the rating of the film
+    #[must_use]
+    pub fn with_rating(mut self, rating: Option<&str>) -> Self {
+        self.film.rating = rating.map(str::to_string);
+        self
+    }
+
+    /// Set the status of the film
+    #[must_use]
+    pub const fn with_status(mut self, status: FilmStatus) -> Self {
+        self.film.status = status;
+        self
+    }
+
+    /// Set the opening date of the film
+    #[must_use]
+    pub const fn with_opening_date(mut self, opening_date: NaiveDateTime) -> Self {
+        self.film.opening_date = opening_date;
+        self
+    }
+
+    /// Set the duration of the film in minutes
+    #[must_use]
+    pub const fn with_duration(mut self, duration: u32) -> Self {
+        self.film.duration = duration;
+        self
+    }
+
+    /// Set the display sequence of the film
+    #[must_use]
+    pub const fn with_display_sequence(mut self, display_sequence: u32) -> Self {
+        self.film.display_sequence = display_sequence;
+        self
+    }
+
+    /// Set the format of the film
+    #[must_use]
+    pub const fn with_format(mut self, format: FilmFormat) -> Self {
+        self.film.format = format;
+        self
+    }
+
+    /// Add a [`Person`] to the film
+    #[must_use]
+    pub fn with_person(mut self, person: Person) -> Self {
+        self.film.people.push(person);
+        self
+    }
+
+    /// Set the poster URL of the film
+    #[must_use]
+    pub fn with_poster_url(mut self, poster_url: Option<&str>) -> Self {
+        self.film.film_poster_url = poster_url.map(str::to_string);
+        self
+    }
+
+    /// Build the [`Film`]
+    #[must_use]
+    pub fn build(self) -> Film {
+        self.film
+    }
+}
+
+/// A builder for [`PackageFilm`] fixtures
+#[derive(Debug, Clone)]
+pub struct PackageFilmBuilder {
+    /// The package film being built
+    package_film: PackageFilm,
+}
+impl PackageFilmBuilder {
+    /// Create a new [`PackageFilmBuilder`] for a [`Film`], taking the whole
+    /// box office
+    #[must_use]
+    pub fn new(film: &Film) -> Self {
+        Self {
+            package_film: PackageFilm {
+                film_id: film.id.clone(),
+                title: film.title.clone(),
+                split_percent: 100.0,
+                trailer_duration: 15,
+                clean_up_duration: 15,
+                order: 1,
+            },
+        }
+    }
+
+    /// Set the order of the film within the package
+    #[must_use]
+    pub const fn with_order(mut self, order: u32) -> Self {
+        self.package_film.order = order;
+        self
+    }
+
+    /// Set what percent of the box office the film receives
+    #[must_use]
+    pub const fn with_split_percent(mut self, split_percent: f32) -> Self {
+        self.package_film.split_percent = split_percent;
+        self
+    }
+
+    /// Set the trailer and clean up durations in minutes
+    #[must_use]
+    pub const fn with_durations(mut self, trailer_duration: u32, clean_up_duration: u32) -> Self {
+        self.package_film.trailer_duration = trailer_duration;
+        self.package_film.clean_up_duration = clean_up_duration;
+        self
+    }
+
+    /// Build the [`PackageFilm`]
+    #[must_use]
+    pub fn build(self) -> PackageFilm {
+        self.package_film
+    }
+}
+
+/// A builder for [`FilmPackage`] fixtures
+#[derive(Debug, Clone)]
+pub struct FilmPackageBuilder {
+    /// The film package being built
+    film_package: FilmPackage,
+}
+impl FilmPackageBuilder {
+    /// Create a new, empty, active [`FilmPackageBuilder`] with the given ID
+    #[must_use]
+    pub fn new(id: u32) -> Self {
+        Self {
+            film_package: FilmPackage {
+                id: FilmPackageId(id),
+                title: format!("Package {id}"),
+                status: FilmStatus::Active,
+                films: Vec::new(),
+            },
+        }
+    }
+
+    /// Set the title of the film package
+    #[must_use]
+    pub fn with_title(mut self, title: &str) -> Self {
+        title.clone_into(&mut self.film_package.title);
+        self
+    }
+
+    /// Set the status of the film package
+    #[must_use]
+    pub const fn with_status(mut self, status: FilmStatus) -> Self {
+        self.film_package.status = status;
+        self
+    }
+
+    /// Add [`Film`]s to the package, in order, splitting the box office evenly
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::as_conversions)]
+    pub fn with_films(mut self, films: &[&Film]) -> Self {
+        let split = 100.0 / films.len().max(1) as f32;
+        self.film_package.films = films
+            .iter()
+            .zip(1..)
+            .map(|(film, order)| {
+                PackageFilmBuilder::new(film)
+                    .with_order(order)
+                    .with_split_percent(split)
+                    .build()
+            })
+            .collect();
+        self
+    }
+
+    /// Add a [`PackageFilm`] to the package
+    #[must_use]
+    pub fn with_package_film(mut self, package_film: PackageFilm) -> Self {
+        self.film_package.films.push(package_film);
+        self
+    }
+
+    /// Build the [`FilmPackage`]
+    #[must_use]
+    pub fn build(self) -> FilmPackage {
+        self.film_package
+    }
+}
+
+/// A builder for [`Screen`] fixtures
+#[derive(Debug, Clone)]
+pub struct ScreenBuilder {
+    /// The screen being built
+    screen: Screen,
+}
+impl ScreenBuilder {
+    /// Create a new [`ScreenBuilder`] for a 100 seat screen with the given ID
+    #[must_use]
+    pub fn new(id: u32) -> Self {
+        Self {
+            screen: Screen {
+                id: ScreenId(id),
+                name: format!("Screen {id}"),
+                screen_number: id.to_string(),
+                has_custom_layout: false,
+                total_seats: 100,
+                house_seats: 0,
+            },
+        }
+    }
+
+    /// Set the name of the screen
+    #[must_use]
+    pub fn with_name(mut self, name: &str) -> Self {
+        name.clone_into(&mut self.screen.name);
+        self
+    }
+
+    /// Set the total and house seats of the screen
+    #[must_use]
+    pub const fn with_seats(mut self, total_seats: u32, house_seats: u32) -> Self {
+        self.screen.total_seats = total_seats;
+        self.screen.house_seats = house_seats;
+        self
+    }
+
+    /// Build the [`Screen`]
+    #[must_use]
+    pub fn build(self) -> Screen {
+        self.screen
+    }
+}
+
+/// A builder for [`Attribute`] fixtures
+#[derive(Debug, Clone)]
+pub struct AttributeBuilder {
+    /// The attribute being built
+    attribute: Attribute,
+}
+impl AttributeBuilder {
+    /// Create a new [`AttributeBuilder`] with the given ID
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self {
+            attribute: Attribute {
+                id: AttributeId(id.to_string()),
+                description: format!("Attribute {id}"),
+                short_name: id.to_string(),
+                font_color: "#FFFFFF".to_string(),
+                background_color: "#000000".to_string(),
+                show_on_sessions_with_no_comps: true,
+            },
+        }
+    }
+
+    /// Set the short name and description of the attribute
+    #[must_use]
+    pub fn with_name(mut self, short_name: &str, description: &str) -> Self {
+        short_name.clone_into(&mut self.attribute.short_name);
+        description.clone_into(&mut self.attribute.description);
+        self
+    }
+
+    /// Set the font and background colors of the attribute (hex codes)
+    #[must_use]
+    pub fn with_colors(mut self, font_color: &str, background_color: &str) -> Self {
+        font_color.clone_into(&mut self.attribute.font_color);
+        background_color.clone_into(&mut self.attribute.background_color);
+        self
+    }
+
+    /// Build the [`Attribute`]
+    #[must_use]
+    pub fn build(self) -> Attribute {
+        self.attribute
+    }
+}
+
+/// A builder for [`Site`] fixtures
+#[derive(Debug, Clone)]
+pub struct SiteBuilder {
+    /// The site being built
+    site: Site,
+}
+impl Default for SiteBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl SiteBuilder {
+    /// Create a new [`SiteBuilder`] for a site in `America/Los_Angeles` with
+    /// no screens
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            site: Site {
+                name: "Libveezi Cinema".to_string(),
+                short_name: "Libveezi".to_string(),
+                legal_name: "Libveezi Cinema LLC".to_string(),
+                national_code: None,
+                address_1: Some("1 Main St".to_string()),
+                address_2: None,
+                address_3: Some("Springfield".to_string()),
+                post_code: Some("90000".to_string()),
+                phone_1: Some("555-0100".to_string()),
+                phone_2: None,
+                fax: None,
+                sales_tax_registration: None,
+                ticket_message_1: None,
+                ticket_message_2: None,
+                receipt_message_1: None,
+                receipt_message_2: None,
+                receipt_message_3: None,
+                receipt_message_4: None,
+                receipt_message_5: None,
+                receipt_message_6: None,
+                time_zone_identifier: "America/Los_Angeles".to_string(),
+                country: "US".to_string(),
+                screens: Vec::new(),
+            },
+        }
+    }
+
+    /// Set the name and short name of the site
+    #[must_use]
+    pub fn with_name(mut self, name: &str, short_name: &str) -> Self {
+        name.clone_into(&mut self.site.name);
+        short_name.clone_into(&mut self.site.short_name);
+        self
+    }
+
+    /// Set the IANA time zone identifier of the site
+    #[must_use]
+    pub fn with_time_zone(mut self, time_zone_identifier: &str) -> Self {
+        time_zone_identifier.clone_into(&mut self.site.time_zone_identifier);
+        self
+    }
+
+    /// Set the [`Screen`]s of the site
+    #[must_use]
+    pub fn with_screens(mut self, screens: &[Screen]) -> Self {
+        self.site.screens = screens.iter().map(|screen| screen.id.into_u32()).collect();
+        self
+    }
+
+    /// Build the [`Site`]
+    #[must_use]
+    pub fn build(self) -> Site {
+        self.site
+    }
+}
+
+/// A builder for [`Session`] fixtures
+#[derive(Debug, Clone)]
+pub struct SessionBuilder {
+    /// The session being built
+    session: Session,
+    /// The sellable seats of the session's screen
+    capacity: u32,
+}
+impl SessionBuilder {
+    /// Create a new [`SessionBuilder`] for an open, public session of a
+    /// [`Film`] on a [`Screen`], with its pre-show starting at `start`
+    ///
+    /// The feature starts after 15 minutes of trailers and runs for the
+    /// film's duration, followed by 15 minutes of cleanup. Sales are allowed
+    /// at the box office and online until the feature starts.
+    #[must_use]
+    pub fn new(id: u32, film: &Film, screen: &Screen, start: NaiveDateTime) -> Self {
+        let feature_start_time = start + TimeDelta::minutes(DEFAULT_TRAILER_MINUTES);
+        let feature_end_time = feature_start_time + TimeDelta::minutes(film.duration.into());
+        let capacity = screen.total_seats.saturating_sub(screen.house_seats);
+        Self {
+            session: Session {
+                id: SessionId(id),
+                film_id: film.id.clone(),
+                film_package_id: None,
+                title: film.title.clone(),
+                screen_id: screen.id,
+                seating: Seating::Allocated,
+                are_complimentaries_allowed: true,
+                show_type: ShowType::Public,
+                sales_via: SalesVia {
+                    kiosk: false,
+                    pos: true,
+                    www: true,
+                    mx: false,
+                    rsp: false,
+                },
+                status: SessionStatus::Open,
+                pre_show_start_time: start,
+                sales_cut_off_time: feature_start_time,
+                feature_start_time,
+                feature_end_time,
+                cleanup_end_time: feature_end_time + TimeDelta::minutes(DEFAULT_CLEANUP_MINUTES),
+                tickets_sold_out: capacity == 0,
+                few_tickets_left: false,
+                seats_available: capacity,
+                seats_held: 0,
+                seats_house: screen.house_seats,
+                seats_sold: 0,
+                film_format: film.format,
+                price_card_name: "Standard".to_string(),
+                attributes: Vec::new(),
+                audio_language: film.audio_language.clone(),
+            },
+            capacity,
+        }
+    }
+
+    /// Show the session as part of a [`FilmPackage`]
+    #[must_use]
+    pub fn with_film_package(mut self, film_package: &FilmPackage) -> Self {
+        self.session.film_package_id = Some(film_package.id);
+        film_package.title.clone_into(&mut self.session.title);
+        self
+    }
+
+    /// Add an [`Attribute`] to the session
+    #[must_use]
+    pub fn with_attribute(mut self, attribute: &Attribute) -> Self {
+        self.session.attributes.push(attribute.id.clone());
+        self
+    }
+
+    /// Set the status of the session
+    #[must_use]
+    pub const fn with_status(mut self, status: SessionStatus) -> Self {
+        self.session.status = status;
+        self
+    }
+
+    /// Set the show type of the session
+    #[must_use]
+    pub const fn with_show_type(mut self, show_type: ShowType) -> Self {
+        self.session.show_type = show_type;
+        self
+    }
+
+    /// Set the seating type of the session
+    #[must_use]
+    pub const fn with_seating(mut self, seating: Seating) -> Self {
+        self.session.seating = seating;
+        self
+    }
+
+    /// Set the sales channels of the session
+    #[must_use]
+    pub const fn with_sales_via(mut self, sales_via: SalesVia) -> Self {
+        self.session.sales_via = sales_via;
+        self
+    }
+
+    /// Set the time sales end for the session
+    #[must_use]
+    pub const fn with_sales_cut_off_time(mut self, sales_cut_off_time: NaiveDateTime) -> Self {
+        self.session.sales_cut_off_time = sales_cut_off_time;
+        self
+    }
+
+    /// Set the price card name of the session
+    #[must_use]
+    pub fn with_price_card_name(mut self, price_card_name: &str) -> Self {
+        price_card_name.clone_into(&mut self.session.price_card_name);
+        self
+    }
+
+    /// Set the number of seats sold, updating the seats available and the
+    /// sold out and few tickets left flags to match
+    #[must_use]
+    pub const fn with_seats_sold(mut self, seats_sold: u32) -> Self {
+        let seats_sold = if seats_sold > self.capacity {
+            self.capacity
+        } else {
+            seats_sold
+        };
+        let seats_available = self.capacity - seats_sold;
+        self.session.seats_sold = seats_sold;
+        self.session.seats_available = seats_available;
+        self.session.tickets_sold_out = seats_available == 0;
+        self.session.few_tickets_left =
+            seats_available > 0 && seats_available * 10 <= self.capacity;
+        self
+    }
+
+    /// Build the [`Session`]
+    #[must_use]
+    pub fn build(self) -> Session {
+        self.session
+    }
+}
+
+/// A coherent set of fixtures for one site, generated by [`ScheduleGenerator`]
+#[derive(Debug, Clone)]
+pub struct FixtureSet {
+    /// The site, listing every screen
+    pub site: Site,
+    /// The screens of the site
+    pub screens: Vec<Screen>,
+    /// The films being shown
+    pub films: Vec<Film>,
+    /// A double feature of the first two films, if there are at least two
+    pub film_packages: Vec<FilmPackage>,
+    /// The attributes used by the sessions
+    pub attributes: Vec<Attribute>,
+    /// The sessions, ordered by screen and then by start time
+    pub sessions: Vec<Session>,
+}
+impl FixtureSet {
+    /// Get the sessions as a [`SessionList`]
+    #[must_use]
+    pub fn session_list(&self) -> SessionList {
+        SessionList::from(self.sessions.clone())
+    }
+
+    /// Create a [`MockVeezi`] serving these fixtures
+    #[cfg(feature = "mock")]
+    #[must_use]
+    pub fn mock(&self) -> MockVeezi {
+        MockVeezi::new()
+            .with_site(self.site.clone())
+            .with_screens(self.screens.clone())
+            .with_films(self.films.clone())
+            .with_film_packages(self.film_packages.clone())
+            .with_attributes(self.attributes.clone())
+            .with_sessions(self.sessions.clone())
+    }
+}
+
+/// A generator of a coherent schedule of sessions for a multi-screen site
+///
+/// Every screen shows sessions back to back from noon until 10pm each day,
+/// rotating through the films. Sessions starting before 5pm are matinees, the
+/// last session of each day on each screen is open captioned, and seats sold
+/// vary between sessions so some are nearly or fully sold out. The output is
+/// deterministic, so tests can rely on it.
+#[derive(Debug, Clone, Copy)]
+pub struct ScheduleGenerator {
+    /// The first day of the schedule
+    pub start: NaiveDate,
+    /// The number of days to schedule
+    pub days: u64,
+    /// The number of screens at the site
+    pub screens: u32,
+    /// The number of films to rotate through
+    pub films: u32,
+}
+impl ScheduleGenerator {
+    /// Create a new [`ScheduleGenerator`] for a week on three screens,
+    /// rotating through six films
+    #[must_use]
+    pub const fn new(start: NaiveDate) -> Self {
+        Self {
+            start,
+            days: 7,
+            screens: 3,
+            films: 6,
+        }
+    }
+
+    /// Set the number of days to schedule
+    #[must_use]
+    pub const fn with_days(mut self, days: u64) -> Self {
+        self.days = days;
+        self
+    }
+
+    /// Set the number of screens at the site
+    #[must_use]
+    pub const fn with_screens(mut self, screens: u32) -> Self {
+        self.screens = screens;
+        self
+    }
+
+    /// Set the number of films to rotate through
+    #[must_use]
+    pub const fn with_films(mut self, films: u32) -> Self {
+        self.films = films;
+        self
+    }
+
+    /// Internal helper to generate the [`Film`]s
+    fn generate_films(&self) -> Vec<Film> {
+        let titles = FILM_TITLES.iter().cycle();
+        let genres = FILM_GENRES.iter().cycle();
+        let ratings = FILM_RATINGS.iter().cycle();
+        (1..=self.films)
+            .zip(titles.zip(genres).zip(ratings))
+            .map(|(n, ((title, genre), rating))| {
+                let title = if n > 8 {
+                    format!("{title} {}", n.div_ceil(8))
+                } else {
+                    (*title).to_string()
+                };
+                FilmBuilder::new(&format!("ST{n:08}"))
+                    .with_title(&title)
+                    .with_genre(genre)
+                    .with_rating(Some(rating))
+                    .with_duration(85 + (n * 11) % 60)
+                    .with_display_sequence(n)
+                    .with_opening_date(self.start.and_time(NaiveTime::MIN))
+                    .with_person(
+                        PersonBuilder::new(&format!("P{n:08}"))
+                            .with_name("Alex", &format!("Director{n}"))
+                            .with_role("Director")
+                            .build(),
+                    )
+                    .build()
+            })
+            .collect()
+    }
+
+    /// Generate the [`FixtureSet`]
+    #[must_use]
+    pub fn generate(&self) -> FixtureSet {
+        let screens: Vec<Screen> = (1..=self.screens)
+            .map(|n| ScreenBuilder::new(n).with_seats(60 + n * 40, 2).build())
+            .collect();
+        let films = self.generate_films();
+        let film_packages = match films.as_slice() {
+            [first, second, ..] => vec![
+                FilmPackageBuilder::new(1)
+                    .with_title(&format!("{} + {}", first.title, second.title))
+                    .with_films(&[first, second])
+                    .build(),
+            ],
+            _ => Vec::new(),
+        };
+        let matinee = AttributeBuilder::new("0000000001")
+            .with_name("MAT", "Matinee")
+            .with_colors("#000000", "#FFD700")
+            .build();
+        let open_captions = AttributeBuilder::new("0000000002")
+            .with_name("OC", "Open Captions")
+            .with_colors("#FFFFFF", "#1E90FF")
+            .build();
+
+        let mut sessions = Vec::new();
+        let mut id = 1;
+        for (screen_index, screen) in screens.iter().enumerate() {
+            for day in 0..usize::try_from(self.days).unwrap_or(usize::MAX) {
+                let Some(date) = self
+                    .start
+                    .checked_add_days(Days::new(u64::try_from(day).unwrap_or(u64::MAX)))
+                else {
+                    break;
+                };
+                let mut start = at(date, 12, 0);
+                let mut day_sessions: Vec<Session> = Vec::new();
+                let mut slot = 0;
+                while !films.is_empty() && start.hour() < 22 {
+                    let film = &films[(screen_index + day + slot) % films.len()];
+                    let mut builder = SessionBuilder::new(id, film, screen, start)
+                        .with_seats_sold((id * 37) % (screen.total_seats + 10));
+                    if start.hour() < 17 {
+                        builder = builder.with_attribute(&matinee);
+                    }
+                    if date.weekday() == Weekday::Sat && start.hour() >= 17 {
+                        builder = builder.with_price_card_name("Weekend");
+                    }
+                    let session = builder.build();
+                    let cleanup = session.cleanup_end_time;
+                    start =
+                        cleanup + TimeDelta::minutes(i64::from((15 - cleanup.minute() % 15) % 15));
+                    day_sessions.push(session);
+                    id += 1;
+                    slot += 1;
+                }
+                if let Some(last) = day_sessions.last_mut() {
+                    last.attributes.push(open_captions.id.clone());
+                }
+                sessions.append(&mut day_sessions);
+            }
+        }
+
+        FixtureSet {
+            site: SiteBuilder::new().with_screens(&screens).build(),
+            screens,
+            films,
+            film_packages,
+            attributes: vec![matinee, open_captions],
+            sessions,
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Per-screen timelines of [`Session`]s over a business day
+//!
+//! The primary type is [`ScreenTimeline`], which lays out the pre-show, feature
+//! and cleanup segments of every session on a [`Screen`] in order, and finds
+//! the idle time between them.
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use serde::Serialize;
+
+use crate::{
+    film::{Film, FilmId},
+    screen::{Screen, ScreenId},
+    session::{Session, SessionId, SessionList},
+};
+
+/// A span of time between two instants
+#[derive(Serialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct TimeSpan {
+    /// The start of the span (inclusive)
+    pub start: NaiveDateTime,
+    /// The end of the span (exclusive)
+    pub end: NaiveDateTime,
+}
+impl TimeSpan {
+    /// Create a new [`TimeSpan`] between two instants
+    #[must_use]
+    pub const fn new(start: NaiveDateTime, end: NaiveDateTime) -> Self {
+        Self { start, end }
+    }
+
+    /// Get the length of this [`TimeSpan`]
+    #[must_use]
+    pub fn duration(&self) -> TimeDelta {
+        self.end - self.start
+    }
+
+    /// Get the length of this [`TimeSpan`] in whole minutes
+    #[must_use]
+    pub fn minutes(&self) -> i64 {
+        self.duration().num_minutes()
+    }
+
+    /// Returns whether the given instant falls within this [`TimeSpan`]
+    #[must_use]
+    pub fn contains(&self, instant: NaiveDateTime) -> bool {
+        self.start <= instant && instant < self.end
+    }
+}
+
+/// The range of times a show could start at, as found by
+/// [`ScreenTimeline::slots_for`]
+///
+/// Unlike a [`TimeSpan`], both ends are inclusive, so a show that fits a free
+/// span exactly has `earliest == latest`.
+#[derive(Serialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct StartWindow {
+    /// The earliest possible start time
+    pub earliest: NaiveDateTime,
+    /// The latest possible start time
+    pub latest: NaiveDateTime,
+}
+impl StartWindow {
+    /// Create a new [`StartWindow`] between two start times
+    #[must_use]
+    pub const fn new(earliest: NaiveDateTime, latest: NaiveDateTime) -> Self {
+        Self { earliest, latest }
+    }
+
+    /// Returns whether a show could start at the given instant
+    #[must_use]
+    pub fn contains(&self, instant: NaiveDateTime) -> bool {
+        self.earliest <= instant && instant <= self.latest
+    }
+}
+
+/// A single [`Session`] laid out on a [`ScreenTimeline`]
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct TimelineEntry {
+    /// The ID of the session
+    pub session_id: SessionId,
+    /// The ID of the film shown in the session
+    pub film_id: FilmId,
+    /// The title of the film shown in the session
+    pub title: String,
+    /// The pre-show segment (trailers, advertising) of the session
+    pub pre_show: TimeSpan,
+    /// The feature segment of the session
+    pub feature: TimeSpan,
+    /// The cleanup segment of the session
+    pub cleanup: TimeSpan,
+}
+impl TimelineEntry {
+    /// Create a [`TimelineEntry`] from a [`Session`]
+    #[must_use]
+    pub fn new(session: &Session) -> Self {
+        Self {
+            session_id: session.id,
+            film_id: session.film_id.clone(),
+            title: session.title.clone(),
+            pre_show: TimeSpan::new(session.pre_show_start_time, session.feature_start_time),
+            feature: TimeSpan::new(session.feature_start_time, session.feature_end_time),
+            cleanup: TimeSpan::new(session.feature_end_time, session.cleanup_end_time),
+        }
+    }
+
+    /// Get the full span the screen is occupied by this session, from the
+    /// start of the pre-show until the end of the cleanup
+    #[must_use]
+    pub const fn span(&self) -> TimeSpan {
+        TimeSpan::new(self.pre_show.start, self.cleanup.end)
+    }
+}
+
+/// The sessions on a single [`Screen`] over one business day, in order
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct ScreenTimeline {
+    /// The ID of the screen
+    pub screen_id: ScreenId,
+    /// The name of the screen
+    pub screen_name: String,
+    /// The business day this timeline covers
+    pub business_day: NaiveDate,
+    /// The span of the business day; sessions whose pre-show starts within
+    /// this span are included in the timeline
+    pub window: TimeSpan,
+    /// The sessions on the screen, ordered by the start of their pre-show
+    pub entries: Vec<TimelineEntry>,
+}
+impl ScreenTimeline {
+    /// The time of day at which a business day starts by default, so that late
+    /// shows running past midnight belong to the previous day
+    #[allow(clippy::missing_panics_doc)]
+    pub const DEFAULT_DAY_START: NaiveTime =
+        NaiveTime::from_hms_opt(6, 0, 0).expect("06:00 should exist");
+
+    /// Build the [`ScreenTimeline`] of a [`Screen`] for a business day starting
+    /// at [`ScreenTimeline::DEFAULT_DAY_START`]
+    #[must_use]
+    pub fn new(sessions: &SessionList, screen: &Screen, business_day: NaiveDate) -> Self {
+        Self::with_day_start(sessions, screen, business_day, Self::DEFAULT_DAY_START)
+    }
+
+    /// Build the [`ScreenTimeline`] of a [`Screen`] for a business day starting
+    /// at the given time of day and lasting 24 hours
+    #[must_use]
+    pub fn with_day_start(
+        sessions: &SessionList,
+        screen: &Screen,
+        business_day: NaiveDate,
+        day_start: NaiveTime,
+    ) -> Self {
+        let start = business_day.and_time(day_start);
+        let window = TimeSpan::new(start, start + TimeDelta::days(1));
+
+        let mut entries: Vec<TimelineEntry> = sessions
+            .iter()
+            .filter(|session| {
+                session.screen_id == screen.id && window.contains(session.pre_show_start_time)
+            })
+            .map(TimelineEntry::new)
+            .collect();
+        entries.sort_by_key(|entry| entry.pre_show.start);
+
+        Self {
+            screen_id: screen.id,
+            screen_name: screen.name.clone(),
+            business_day,
+            window,
+            entries,
+        }
+    }
+
+    /// Get the first show of the day, if any
+    #[must_use]
+    pub fn first_show(&self) -> Option<&TimelineEntry> {
+        self.entries.first()
+    }
+
+    /// Get the last show of the day, if any
+    #[must_use]
+    pub fn last_show(&self) -> Option<&TimelineEntry> {
+        self.entries.iter().max_by_key(|entry| entry.feature.start)
+    }
+
+    /// Get every span within the business day during which the screen is not
+    /// occupied by a session, including before the first and after the last
+    /// show
+    #[must_use]
+    pub fn free_spans(&self) -> Vec<TimeSpan> {
+        let mut free = Vec::new();
+        let mut cursor = self.window.start;
+        for entry in &self.entries {
+            if entry.pre_show.start > cursor {
+                free.push(TimeSpan::new(cursor, entry.pre_show.start));
+            }
+            cursor = cursor.max(entry.cleanup.end);
+        }
+        if cursor < self.window.end {
+            free.push(TimeSpan::new(cursor, self.window.end));
+        }
+        free
+    }
+
+    /// Get the idle gaps between consecutive shows, from the end of one
+    /// session's cleanup to the start of the next session's pre-show
+    #[must_use]
+    pub fn idle_gaps(&self) -> Vec<TimeSpan> {
+        let (Some(first), Some(last_end)) = (
+            self.first_show(),
+            self.entries.iter().map(|entry| entry.cleanup.end).max(),
+        ) else {
+            return Vec::new();
+        };
+        self.free_spans()
+            .into_iter()
+            .filter(|span| span.start >= first.pre_show.start && span.end <= last_end)
+            .collect()
+    }
+
+    /// Get the total number of minutes the screen is occupied by sessions,
+    /// counting overlapping sessions only once
+    #[must_use]
+    pub fn utilization_minutes(&self) -> i64 {
+        let mut occupied = TimeDelta::zero();
+        let mut cursor = self.window.start;
+        for entry in &self.entries {
+            let start = entry.pre_show.start.max(cursor);
+            if entry.cleanup.end > start {
+                occupied += entry.cleanup.end - start;
+            }
+            cursor = cursor.max(entry.cleanup.end);
+        }
+        occupied.num_minutes()
+    }
+
+    /// Find the places where another show of the given [`Film`] could fit
+    ///
+    /// A show occupies the screen for `pre_show`, then [`Film::duration`], then
+    /// `cleanup`. Each returned [`StartWindow`] is the range of possible
+    /// pre-show start times within one free span of the business day.
+    #[must_use]
+    pub fn slots_for(
+        &self,
+        film: &Film,
+        pre_show: TimeDelta,
+        cleanup: TimeDelta,
+    ) -> Vec<StartWindow> {
+        let required = pre_show + TimeDelta::minutes(i64::from(film.duration)) + cleanup;
+        self.free_spans()
+            .into_iter()
+            .filter(|span| span.duration() >= required)
+            .map(|span| StartWindow::new(span.start, span.end - required))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the [`ScreenTimeline`]
+
+    use super::*;
+    use crate::testing::{FilmBuilder, ScreenBuilder, SessionBuilder};
+
+    /// Internal helper to get a time on a fixed business day
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 1)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn exact_fit_slot_contains_only_its_start() {
+        let film = FilmBuilder::new("ST00000001").with_duration(90).build();
+        let screen = ScreenBuilder::new(1).build();
+        // Each session occupies the screen for two hours, leaving 14:00-16:00 free
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screen, at(12)).build(),
+            SessionBuilder::new(2, &film, &screen, at(16)).build(),
+        ]);
+        let timeline = ScreenTimeline::new(&sessions, &screen, at(0).date());
+
+        let slots = timeline.slots_for(&film, TimeDelta::minutes(15), TimeDelta::minutes(15));
+        let gap = slots
+            .iter()
+            .find(|slot| slot.earliest == at(14))
+            .expect("the show should fit the gap exactly");
+        assert_eq!(gap.latest, at(14));
+        assert!(gap.contains(at(14)));
+        assert!(!gap.contains(at(14) + TimeDelta::seconds(30)));
+    }
+
+    #[test]
+    fn slots_end_at_the_latest_start() {
+        let film = FilmBuilder::new("ST00000001").with_duration(90).build();
+        let screen = ScreenBuilder::new(1).build();
+        // The first session ends at 14:00, leaving 14:00-17:00 free
+        let sessions = SessionList::from(vec![
+            SessionBuilder::new(1, &film, &screen, at(12)).build(),
+            SessionBuilder::new(2, &film, &screen, at(17)).build(),
+        ]);
+        let timeline = ScreenTimeline::new(&sessions, &screen, at(0).date());
+
+        let slots = timeline.slots_for(&film, TimeDelta::minutes(15), TimeDelta::minutes(15));
+        let gap = slots
+            .iter()
+            .find(|slot| slot.earliest == at(14))
+            .expect("the show should fit the gap");
+        assert_eq!(gap.latest, at(15));
+        assert!(gap.contains(at(15)));
+        assert!(!gap.contains(at(15) + TimeDelta::seconds(1)));
+    }
+}
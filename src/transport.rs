@@ -0,0 +1,93 @@
+//! The [`Transport`] used by the [`Client`] to make HTTP requests
+//!
+//! A [`Transport`] takes a [`TransportRequest`] and returns a
+//! [`TransportResponse`] holding the status code, headers and raw body. The
+//! [`Client`] builds requests and decodes responses itself, so a transport
+//! only needs to move bytes. [`ReqwestTransport`] is the default; tests can
+//! plug in an in-memory transport, and other HTTP stacks or middleware can be
+//! used by wrapping one transport in another. The record/replay transports in
+//! [`crate::cassette`] are built this way.
+//!
+//! [`Client`]: crate::client::Client
+
+use std::{fmt::Debug, future::Future, pin::Pin};
+
+use reqwest::{Method, StatusCode, Url, header::HeaderMap};
+
+use crate::error::ApiResult;
+
+/// The future returned by [`Transport::send`]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = ApiResult<TransportResponse>> + Send + 'a>>;
+
+/// An HTTP request made by a [`crate::client::Client`]
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    /// The HTTP method of the request
+    pub method: Method,
+    /// The full URL of the request
+    pub url: Url,
+    /// The headers of the request, including the `VeeziAccessToken`
+    pub headers: HeaderMap,
+}
+
+/// An HTTP response returned by a [`Transport`]
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    /// The status code of the response
+    pub status: StatusCode,
+    /// The headers of the response
+    pub headers: HeaderMap,
+    /// The raw body of the response
+    pub body: Vec<u8>,
+}
+
+/// A way of sending HTTP requests
+///
+/// Implementations should only return an error if no response was received
+/// at all; error status codes are returned as normal responses and turned
+/// into [`LibVeeziError::Status`](crate::error::LibVeeziError::Status) by the
+/// client.
+pub trait Transport: Send + Sync {
+    /// Send a request and wait for its response
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_>;
+}
+
+/// The default [`Transport`], backed by a [`reqwest::Client`]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    /// The underlying HTTP client
+    http: reqwest::Client,
+}
+impl ReqwestTransport {
+    /// Create a new [`ReqwestTransport`] with a default [`reqwest::Client`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(http: reqwest::Client) -> Self {
+        Self { http }
+    }
+}
+impl Transport for ReqwestTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let resp = self
+                .http
+                .request(request.method, request.url)
+                .headers(request.headers)
+                .send()
+                .await?;
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let body = resp.bytes().await?.to_vec();
+            Ok(TransportResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
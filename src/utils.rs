@@ -1,6 +1,6 @@
 //! Internal utilities for libveezi
 
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Helper function used to deserialize `[{Id:1},{Id:2}]` into `vec![1, 2]`
 pub fn deserialize_id_array<'de, D>(deserializer: D) -> Result<Vec<u32>, D::Error>
@@ -17,3 +17,22 @@ where
     let helper_vec: Vec<IdHelper> = Deserialize::deserialize(deserializer)?;
     Ok(helper_vec.into_iter().map(|attr| attr.id).collect())
 }
+
+/// Helper function used to serialize `vec![1, 2]` into `[{Id:1},{Id:2}]`, the
+/// inverse of [`deserialize_id_array`]
+pub fn serialize_id_array<S>(ids: &[u32], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    #[derive(Serialize)]
+    #[serde(rename_all = "PascalCase")]
+    #[allow(clippy::missing_docs_in_private_items)]
+    struct IdHelper {
+        id: u32,
+    }
+
+    ids.iter()
+        .map(|&id| IdHelper { id })
+        .collect::<Vec<_>>()
+        .serialize(serializer)
+}
@@ -0,0 +1,593 @@
+//! A polling [`Watcher`] that emits [`WatchEvent`]s when Veezi data changes
+//!
+//! Veezi does not offer webhooks, so the [`Watcher`] periodically polls the
+//! session, film, film package and attribute endpoints and diffs every response
+//! against the previous snapshot. The diffing functions ([`diff_sessions`],
+//! [`diff_films`], [`diff_film_packages`] and [`diff_attributes`]) are also
+//! exposed for callers that manage their own snapshots.
+
+use std::{collections::HashMap, future, hash::Hash, sync::Arc, time::Duration};
+
+use log::{debug, warn};
+use tokio::{
+    select,
+    sync::mpsc::Sender,
+    time::{Interval, MissedTickBehavior, interval},
+};
+
+use crate::{
+    attr::{Attribute, AttributeId},
+    client::Client,
+    error::LibVeeziError,
+    film::{Film, FilmId, FilmStatus},
+    package::{FilmPackage, FilmPackageId},
+    session::{Session, SessionId, SessionStatus},
+};
+
+/// The kinds of resources polled by a [`Watcher`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum WatchedResource {
+    /// The full list of [`Session`]s (`v1/session`)
+    Sessions,
+    /// The full list of [`Film`]s (`v4/film`)
+    Films,
+    /// The full list of [`FilmPackage`]s (`v1/filmpackage`)
+    FilmPackages,
+    /// The full list of [`Attribute`]s (`v1/attribute`)
+    Attributes,
+}
+
+/// A change detected by a [`Watcher`] between two consecutive polls
+///
+/// Any modification to an existing item produces a generic `*Changed` event.
+/// Some modifications additionally produce a more specific event (such as
+/// [`WatchEvent::SessionSoldOut`]), which is emitted before the generic one.
+#[derive(Debug)]
+pub enum WatchEvent {
+    /// A new [`Session`] has been scheduled
+    SessionAdded(Session),
+    /// A [`Session`] is no longer listed (it has ended or was removed)
+    SessionRemoved(Session),
+    /// A [`Session`] has become sold out
+    SessionSoldOut(Session),
+    /// The [`Session::status`] of a [`Session`] has changed
+    SessionStatusChanged {
+        /// The updated session
+        session: Session,
+        /// The status of the session before the change
+        previous: SessionStatus,
+    },
+    /// Any field of a [`Session`] has changed
+    SessionChanged {
+        /// The session as of the previous poll
+        old: Box<Session>,
+        /// The session as of the latest poll
+        new: Box<Session>,
+    },
+    /// A new [`Film`] has been added
+    FilmAdded(Film),
+    /// A [`Film`] is no longer listed
+    FilmRemoved(Film),
+    /// The [`Film::status`] of a [`Film`] has changed
+    FilmStatusChanged {
+        /// The updated film
+        film: Film,
+        /// The status of the film before the change
+        previous: FilmStatus,
+    },
+    /// Any field of a [`Film`] has changed
+    FilmChanged {
+        /// The film as of the previous poll
+        old: Box<Film>,
+        /// The film as of the latest poll
+        new: Box<Film>,
+    },
+    /// A new [`FilmPackage`] has been added
+    FilmPackageAdded(FilmPackage),
+    /// A [`FilmPackage`] is no longer listed
+    FilmPackageRemoved(FilmPackage),
+    /// Any field of a [`FilmPackage`] has changed
+    FilmPackageChanged {
+        /// The film package as of the previous poll
+        old: FilmPackage,
+        /// The film package as of the latest poll
+        new: FilmPackage,
+    },
+    /// A new [`Attribute`] has been added
+    AttributeAdded(Attribute),
+    /// An [`Attribute`] is no longer listed
+    AttributeRemoved(Attribute),
+    /// Any field of an [`Attribute`] has changed
+    AttributeChanged {
+        /// The attribute as of the previous poll
+        old: Attribute,
+        /// The attribute as of the latest poll
+        new: Attribute,
+    },
+    /// Polling a resource failed; the previous snapshot is kept and the
+    /// resource will be polled again on its next tick
+    PollFailed {
+        /// The resource that failed to poll
+        resource: WatchedResource,
+        /// The error returned by the [`Client`]
+        error: LibVeeziError,
+    },
+}
+
+/// A single change between two lists of items, as found by [`diff_by_key`]
+enum Change<'a, T> {
+    /// The item only exists in the new list
+    Added(&'a T),
+    /// The item only exists in the old list
+    Removed(&'a T),
+    /// The item exists in both lists but differs
+    Changed(&'a T, &'a T),
+}
+
+/// Internal helper to diff two lists of items keyed by their ID
+///
+/// Added and changed items are reported in the order of `new`, followed by
+/// removed items in the order of `old`.
+fn diff_by_key<'a, T, K>(old: &'a [T], new: &'a [T], key: impl Fn(&T) -> K) -> Vec<Change<'a, T>>
+where
+    T: PartialEq,
+    K: Eq + Hash,
+{
+    let old_by_key: HashMap<K, &T> = old.iter().map(|item| (key(item), item)).collect();
+    let new_by_key: HashMap<K, &T> = new.iter().map(|item| (key(item), item)).collect();
+
+    let mut changes = Vec::new();
+    for item in new {
+        match old_by_key.get(&key(item)) {
+            None => changes.push(Change::Added(item)),
+            Some(previous) if *previous != item => changes.push(Change::Changed(previous, item)),
+            Some(_) => {}
+        }
+    }
+    for item in old {
+        if !new_by_key.contains_key(&key(item)) {
+            changes.push(Change::Removed(item));
+        }
+    }
+    changes
+}
+
+/// Compute the [`WatchEvent`]s describing the changes from `old` to `new`
+#[must_use]
+pub fn diff_sessions(old: &[Session], new: &[Session]) -> Vec<WatchEvent> {
+    let mut events = Vec::new();
+    for change in diff_by_key(old, new, |session| -> SessionId { session.id }) {
+        match change {
+            Change::Added(session) => events.push(WatchEvent::SessionAdded(session.clone())),
+            Change::Removed(session) => events.push(WatchEvent::SessionRemoved(session.clone())),
+            Change::Changed(old, new) => {
+                if new.tickets_sold_out && !old.tickets_sold_out {
+                    events.push(WatchEvent::SessionSoldOut(new.clone()));
+                }
+                if new.status != old.status {
+                    events.push(WatchEvent::SessionStatusChanged {
+                        session: new.clone(),
+                        previous: old.status,
+                    });
+                }
+                events.push(WatchEvent::SessionChanged {
+                    old: Box::new(old.clone()),
+                    new: Box::new(new.clone()),
+                });
+            }
+        }
+    }
+    events
+}
+
+/// Compute the [`WatchEvent`]s describing the changes from `old` to `new`
+#[must_use]
+pub fn diff_films(old: &[Film], new: &[Film]) -> Vec<WatchEvent> {
+    let mut events = Vec::new();
+    for change in diff_by_key(old, new, |film| -> FilmId { film.id.clone() }) {
+        match change {
+            Change::Added(film) => events.push(WatchEvent::FilmAdded(film.clone())),
+            Change::Removed(film) => events.push(WatchEvent::FilmRemoved(film.clone())),
+            Change::Changed(old, new) => {
+                if new.status != old.status {
+                    events.push(WatchEvent::FilmStatusChanged {
+                        film: new.clone(),
+                        previous: old.status,
+                    });
+                }
+                events.push(WatchEvent::FilmChanged {
+                    old: Box::new(old.clone()),
+                    new: Box::new(new.clone()),
+                });
+            }
+        }
+    }
+    events
+}
+
+/// Compute the [`WatchEvent`]s describing the changes from `old` to `new`
+#[must_use]
+pub fn diff_film_packages(old: &[FilmPackage], new: &[FilmPackage]) -> Vec<WatchEvent> {
+    diff_by_key(old, new, |package| -> FilmPackageId { package.id })
+        .into_iter()
+        .map(|change| match change {
+            Change::Added(package) => WatchEvent::FilmPackageAdded(package.clone()),
+            Change::Removed(package) => WatchEvent::FilmPackageRemoved(package.clone()),
+            Change::Changed(old, new) => WatchEvent::FilmPackageChanged {
+                old: old.clone(),
+                new: new.clone(),
+            },
+        })
+        .collect()
+}
+
+/// Compute the [`WatchEvent`]s describing the changes from `old` to `new`
+#[must_use]
+pub fn diff_attributes(old: &[Attribute], new: &[Attribute]) -> Vec<WatchEvent> {
+    diff_by_key(old, new, |attribute| -> AttributeId {
+        attribute.id.clone()
+    })
+    .into_iter()
+    .map(|change| match change {
+        Change::Added(attribute) => WatchEvent::AttributeAdded(attribute.clone()),
+        Change::Removed(attribute) => WatchEvent::AttributeRemoved(attribute.clone()),
+        Change::Changed(old, new) => WatchEvent::AttributeChanged {
+            old: old.clone(),
+            new: new.clone(),
+        },
+    })
+    .collect()
+}
+
+/// The shortest interval a [`Watcher`] polls a resource on
+pub const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Polls the Veezi API on configurable intervals and emits [`WatchEvent`]s
+/// over a channel whenever something changes
+///
+/// Each resource is only polled if its interval is set. Intervals shorter than
+/// [`MIN_INTERVAL`], including zero, are raised to it. The first successful
+/// poll of each resource establishes the baseline snapshot and emits no events.
+pub struct Watcher {
+    /// The client used to poll the Veezi API
+    client: Arc<Client>,
+    /// Poll [`Session`]s on the given interval
+    pub session_interval: Option<Duration>,
+    /// Poll [`Film`]s on the given interval
+    pub film_interval: Option<Duration>,
+    /// Poll [`FilmPackage`]s on the given interval
+    pub film_package_interval: Option<Duration>,
+    /// Poll [`Attribute`]s on the given interval
+    pub attribute_interval: Option<Duration>,
+
+    /// The last successfully polled list of [`Session`]s
+    sessions: Option<Vec<Session>>,
+    /// The last successfully polled list of [`Film`]s
+    films: Option<Vec<Film>>,
+    /// The last successfully polled list of [`FilmPackage`]s
+    film_packages: Option<Vec<FilmPackage>>,
+    /// The last successfully polled list of [`Attribute`]s
+    attributes: Option<Vec<Attribute>>,
+}
+impl Watcher {
+    /// Create a new [`Watcher`] for the given [`Client`] with no resources
+    /// being polled
+    #[must_use]
+    pub const fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            session_interval: None,
+            film_interval: None,
+            film_package_interval: None,
+            attribute_interval: None,
+            sessions: None,
+            films: None,
+            film_packages: None,
+            attributes: None,
+        }
+    }
+
+    /// Poll [`Session`]s on the given interval, raised to [`MIN_INTERVAL`] if
+    /// shorter
+    #[must_use]
+    pub const fn with_session_interval(mut self, interval: Duration) -> Self {
+        self.session_interval = Some(clamp_interval(interval));
+        self
+    }
+
+    /// Poll [`Film`]s on the given interval, raised to [`MIN_INTERVAL`] if
+    /// shorter
+    #[must_use]
+    pub const fn with_film_interval(mut self, interval: Duration) -> Self {
+        self.film_interval = Some(clamp_interval(interval));
+        self
+    }
+
+    /// Poll [`FilmPackage`]s on the given interval, raised to [`MIN_INTERVAL`]
+    /// if shorter
+    #[must_use]
+    pub const fn with_film_package_interval(mut self, interval: Duration) -> Self {
+        self.film_package_interval = Some(clamp_interval(interval));
+        self
+    }
+
+    /// Poll [`Attribute`]s on the given interval, raised to [`MIN_INTERVAL`] if
+    /// shorter
+    #[must_use]
+    pub const fn with_attribute_interval(mut self, interval: Duration) -> Self {
+        self.attribute_interval = Some(clamp_interval(interval));
+        self
+    }
+
+    /// Poll all supported resources with default intervals
+    #[must_use]
+    pub const fn with_default_intervals(self) -> Self {
+        self.with_session_interval(Duration::from_mins(1))
+            .with_film_interval(Duration::from_mins(5))
+            .with_film_package_interval(Duration::from_mins(5))
+            .with_attribute_interval(Duration::from_mins(15))
+    }
+
+    /// Run the polling loop, sending every detected change to `events`
+    ///
+    /// The cached list for a resource is invalidated before each poll so that
+    /// the [`Watcher`] always observes fresh data. Cached individual items are
+    /// left alone, as the [`Client`] may be shared, and are refreshed by the
+    /// poll itself. This function only returns
+    /// once the receiving half of `events` has been dropped.
+    pub async fn run(mut self, events: Sender<WatchEvent>) {
+        let mut session_tick = self.session_interval.map(new_interval);
+        let mut film_tick = self.film_interval.map(new_interval);
+        let mut film_package_tick = self.film_package_interval.map(new_interval);
+        let mut attribute_tick = self.attribute_interval.map(new_interval);
+
+        debug!("Starting libveezi Watcher");
+        loop {
+            let batch = select! {
+                () = tick(&mut session_tick) => self.poll(WatchedResource::Sessions).await,
+                () = tick(&mut film_tick) => self.poll(WatchedResource::Films).await,
+                () = tick(&mut film_package_tick) => self.poll(WatchedResource::FilmPackages).await,
+                () = tick(&mut attribute_tick) => self.poll(WatchedResource::Attributes).await,
+                () = events.closed() => break,
+            };
+            for event in batch {
+                if events.send(event).await.is_err() {
+                    break;
+                }
+            }
+        }
+        debug!("Stopping libveezi Watcher, event receiver was dropped");
+    }
+
+    /// Poll a single resource, returning the events produced by diffing it
+    /// against the previous snapshot
+    async fn poll(&mut self, resource: WatchedResource) -> Vec<WatchEvent> {
+        debug!("Watcher polling {resource:?}");
+        let result = match resource {
+            WatchedResource::Sessions => {
+                self.client.invalidate_cached_session_list();
+                self.client.list_sessions().await.map(|sessions| {
+                    replace_snapshot(&mut self.sessions, sessions.into_vec(), diff_sessions)
+                })
+            }
+            WatchedResource::Films => {
+                self.client.invalidate_cached_film_list();
+                self.client
+                    .list_films()
+                    .await
+                    .map(|films| replace_snapshot(&mut self.films, films, diff_films))
+            }
+            WatchedResource::FilmPackages => {
+                self.client.invalidate_cached_film_package_list();
+                self.client.list_film_packages().await.map(|packages| {
+                    replace_snapshot(&mut self.film_packages, packages, diff_film_packages)
+                })
+            }
+            WatchedResource::Attributes => {
+                self.client.invalidate_cached_attribute_list();
+                self.client.list_attributes().await.map(|attributes| {
+                    replace_snapshot(&mut self.attributes, attributes, diff_attributes)
+                })
+            }
+        };
+
+        result.unwrap_or_else(|error| {
+            warn!("Watcher failed to poll {resource:?}: {error}");
+            vec![WatchEvent::PollFailed { resource, error }]
+        })
+    }
+}
+
+/// Internal helper to store a freshly polled snapshot, returning the events
+/// produced by diffing it against the previous one (if any)
+fn replace_snapshot<T>(
+    snapshot: &mut Option<Vec<T>>,
+    latest: Vec<T>,
+    diff: fn(&[T], &[T]) -> Vec<WatchEvent>,
+) -> Vec<WatchEvent> {
+    let events = snapshot
+        .as_deref()
+        .map(|previous| diff(previous, &latest))
+        .unwrap_or_default();
+    *snapshot = Some(latest);
+    events
+}
+
+/// Internal helper to raise an interval to at least [`MIN_INTERVAL`]
+const fn clamp_interval(period: Duration) -> Duration {
+    if period.as_nanos() < MIN_INTERVAL.as_nanos() {
+        MIN_INTERVAL
+    } else {
+        period
+    }
+}
+
+/// Internal helper to create an [`Interval`] of at least [`MIN_INTERVAL`] that
+/// delays missed ticks instead of bursting
+fn new_interval(period: Duration) -> Interval {
+    let mut interval = interval(clamp_interval(period));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    interval
+}
+
+/// Internal helper to wait for the next tick of an optional [`Interval`],
+/// never completing if the interval is disabled
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the diffing functions and polling intervals
+
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    use super::*;
+    use crate::{
+        client::ClientBuilder,
+        session::SessionStatus,
+        testing::{
+            AttributeBuilder, FilmBuilder, FilmPackageBuilder, ScreenBuilder, SessionBuilder,
+        },
+    };
+
+    /// Internal helper to get a fixed session start time
+    fn start() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 1)
+            .and_then(|date| date.and_hms_opt(19, 0, 0))
+            .unwrap_or_default()
+    }
+
+    /// Internal helper to build a session of a fixed film on a fixed screen
+    fn session(id: u32) -> SessionBuilder {
+        let film = FilmBuilder::new("ST00000001").build();
+        let screen = ScreenBuilder::new(1).build();
+        SessionBuilder::new(id, &film, &screen, start())
+    }
+
+    #[test]
+    fn identical_snapshots_produce_no_events() {
+        let sessions = vec![session(1).build(), session(2).build()];
+        assert!(diff_sessions(&sessions, &sessions).is_empty());
+    }
+
+    #[test]
+    fn added_and_removed_sessions() {
+        let old = vec![session(1).build(), session(2).build()];
+        let new = vec![session(2).build(), session(3).build()];
+        let events = diff_sessions(&old, &new);
+        assert!(matches!(
+            events.as_slice(),
+            [WatchEvent::SessionAdded(added), WatchEvent::SessionRemoved(removed)]
+                if added.id == SessionId(3) && removed.id == SessionId(1)
+        ));
+    }
+
+    #[test]
+    fn sold_out_session_emits_sold_out_before_changed() {
+        let old = vec![session(1).with_seats_sold(10).build()];
+        let new = vec![session(1).with_seats_sold(u32::MAX).build()];
+        let events = diff_sessions(&old, &new);
+        assert!(matches!(
+            events.as_slice(),
+            [WatchEvent::SessionSoldOut(sold_out), WatchEvent::SessionChanged { old, new }]
+                if sold_out.tickets_sold_out && !old.tickets_sold_out && new.tickets_sold_out
+        ));
+    }
+
+    #[test]
+    fn session_status_change_emits_status_changed() {
+        let old = vec![session(1).build()];
+        let new = vec![session(1).with_status(SessionStatus::Closed).build()];
+        let events = diff_sessions(&old, &new);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                WatchEvent::SessionStatusChanged {
+                    session,
+                    previous: SessionStatus::Open,
+                },
+                WatchEvent::SessionChanged { .. },
+            ] if session.status == SessionStatus::Closed
+        ));
+    }
+
+    #[test]
+    fn film_changes() {
+        let old = vec![
+            FilmBuilder::new("ST00000001").build(),
+            FilmBuilder::new("ST00000002").build(),
+        ];
+        let new = vec![
+            FilmBuilder::new("ST00000001")
+                .with_status(FilmStatus::Inactive)
+                .build(),
+            FilmBuilder::new("ST00000003").build(),
+        ];
+        let events = diff_films(&old, &new);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                WatchEvent::FilmStatusChanged {
+                    previous: FilmStatus::Active,
+                    ..
+                },
+                WatchEvent::FilmChanged { .. },
+                WatchEvent::FilmAdded(added),
+                WatchEvent::FilmRemoved(removed),
+            ] if added.id.as_str() == "ST00000003" && removed.id.as_str() == "ST00000002"
+        ));
+    }
+
+    #[test]
+    fn film_package_and_attribute_changes() {
+        let old_packages = vec![FilmPackageBuilder::new(1).build()];
+        let new_packages = vec![FilmPackageBuilder::new(1).with_title("Double Bill").build()];
+        assert!(matches!(
+            diff_film_packages(&old_packages, &new_packages).as_slice(),
+            [WatchEvent::FilmPackageChanged { new, .. }] if new.title == "Double Bill"
+        ));
+
+        let old_attributes = vec![AttributeBuilder::new("0000000001").build()];
+        let new_attributes = vec![
+            AttributeBuilder::new("0000000001")
+                .with_name("OC", "Open Captions")
+                .build(),
+        ];
+        assert!(matches!(
+            diff_attributes(&old_attributes, &new_attributes).as_slice(),
+            [WatchEvent::AttributeChanged { new, .. }] if new.short_name == "OC"
+        ));
+        assert!(matches!(
+            diff_attributes(&[], &old_attributes).as_slice(),
+            [WatchEvent::AttributeAdded(_)]
+        ));
+        assert!(matches!(
+            diff_attributes(&old_attributes, &[]).as_slice(),
+            [WatchEvent::AttributeRemoved(_)]
+        ));
+    }
+
+    #[tokio::test]
+    async fn short_intervals_are_clamped() {
+        let client = Arc::new(
+            ClientBuilder::new("http://localhost/", "token".to_string())
+                .build()
+                .expect("the client should build"),
+        );
+        let watcher = Watcher::new(client)
+            .with_session_interval(Duration::ZERO)
+            .with_film_interval(Duration::from_millis(10))
+            .with_attribute_interval(Duration::from_secs(30));
+        assert_eq!(watcher.session_interval, Some(MIN_INTERVAL));
+        assert_eq!(watcher.film_interval, Some(MIN_INTERVAL));
+        assert_eq!(watcher.attribute_interval, Some(Duration::from_secs(30)));
+
+        assert_eq!(new_interval(Duration::ZERO).period(), MIN_INTERVAL);
+    }
+}
@@ -0,0 +1,75 @@
+//! Integration tests for the GraphQL schema against a mock Veezi server
+#![cfg(feature = "graphql")]
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use libveezi::{graphql::build_schema, mock::MockServer, testing::ScheduleGenerator};
+
+/// Count the requests the mock server received for a path
+fn count_requests(server: &MockServer, path: &str) -> usize {
+    server
+        .requests()
+        .iter()
+        .filter(|request| request.path == path)
+        .count()
+}
+
+#[tokio::test]
+async fn nested_sessions_are_batched() {
+    let start = NaiveDate::from_ymd_opt(2025, 6, 1).expect("the start date should exist");
+    let fixtures = ScheduleGenerator::new(start).with_days(2).generate();
+    let server = fixtures
+        .mock()
+        .start()
+        .await
+        .expect("the mock should start");
+    let schema = build_schema(Arc::new(server.client()));
+
+    let response = schema.execute("{ films { id sessions { id } } }").await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().expect("the data should be JSON");
+    let listed: usize = json["films"]
+        .as_array()
+        .expect("films should be a list")
+        .iter()
+        .map(|film| film["sessions"].as_array().map_or(0, Vec::len))
+        .sum();
+    assert_eq!(listed, fixtures.sessions.len());
+    assert_eq!(count_requests(&server, "/v1/session"), 1);
+
+    // Each relationship is batched separately, but never per object
+    server.clear_requests();
+    let response = schema
+        .execute("{ screens { sessions { id } } attributes { sessions { id } } }")
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(count_requests(&server, "/v1/session"), 2);
+}
+
+#[tokio::test]
+async fn web_sales_use_the_site_once() {
+    let start = NaiveDate::from_ymd_opt(2025, 6, 1).expect("the start date should exist");
+    let fixtures = ScheduleGenerator::new(start).with_days(1).generate();
+    let server = fixtures
+        .mock()
+        .start()
+        .await
+        .expect("the mock should start");
+    let schema = build_schema(Arc::new(server.client()));
+
+    let response = schema
+        .execute("{ sessions { isOpenForSales isOpenForWebSales } }")
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    // The fixtures are in the past, so nothing is on sale
+    let json = response.data.into_json().expect("the data should be JSON");
+    assert!(
+        json["sessions"]
+            .as_array()
+            .expect("sessions should be a list")
+            .iter()
+            .all(|session| session["isOpenForWebSales"] == false)
+    );
+    assert_eq!(count_requests(&server, "/v1/site"), 1);
+}
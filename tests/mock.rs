@@ -0,0 +1,238 @@
+//! Integration tests for the full [`Client`] stack against a mock Veezi server
+
+use chrono::{NaiveDate, TimeDelta};
+use libveezi::{
+    cassette::Cassette,
+    client::{Client, ClientBuilder},
+    error::LibVeeziError,
+    mock::{DEFAULT_MOCK_TOKEN, MockFailure, MockServer},
+    session::SessionId,
+    testing::{FixtureSet, ScheduleGenerator, SessionBuilder},
+};
+use reqwest::StatusCode;
+
+/// Generate two days of fixtures and start a mock server serving them
+async fn start() -> (FixtureSet, MockServer) {
+    let start = NaiveDate::from_ymd_opt(2025, 6, 1).expect("the start date should exist");
+    let fixtures = ScheduleGenerator::new(start).with_days(2).generate();
+    let server = fixtures
+        .mock()
+        .start()
+        .await
+        .expect("the mock should start");
+    (fixtures, server)
+}
+
+/// Get the status code of a [`LibVeeziError::Status`], panicking on any other
+/// error
+fn status_of<T>(result: Result<T, LibVeeziError>) -> StatusCode {
+    match result {
+        Err(LibVeeziError::Status { status, .. }) => status,
+        Err(err) => panic!("expected a status error, got {err}"),
+        Ok(_) => panic!("expected a status error, got a response"),
+    }
+}
+
+#[tokio::test]
+async fn list_endpoints_serve_the_fixtures() {
+    let (fixtures, server) = start().await;
+    let client = server.client();
+
+    let sessions = client.list_sessions().await.expect("sessions should list");
+    assert_eq!(sessions.into_vec(), fixtures.sessions);
+    assert_eq!(
+        client.list_films().await.expect("films should list"),
+        fixtures.films
+    );
+    assert_eq!(
+        client
+            .list_film_packages()
+            .await
+            .expect("film packages should list"),
+        fixtures.film_packages
+    );
+    assert_eq!(
+        client.list_screens().await.expect("screens should list"),
+        fixtures.screens
+    );
+    assert_eq!(
+        client
+            .list_attributes()
+            .await
+            .expect("attributes should list"),
+        fixtures.attributes
+    );
+    assert_eq!(
+        client.get_site().await.expect("the site should load"),
+        fixtures.site
+    );
+}
+
+#[tokio::test]
+async fn get_endpoints_find_items_by_id() {
+    let (fixtures, server) = start().await;
+    let client = server.client();
+
+    let session = &fixtures.sessions[0];
+    assert_eq!(
+        &client
+            .get_session(session.id)
+            .await
+            .expect("the session should load"),
+        session
+    );
+    let film = &fixtures.films[0];
+    assert_eq!(
+        &client
+            .get_film(&film.id)
+            .await
+            .expect("the film should load"),
+        film
+    );
+    let package = &fixtures.film_packages[0];
+    assert_eq!(
+        &client
+            .get_film_package(package.id)
+            .await
+            .expect("the film package should load"),
+        package
+    );
+    let screen = &fixtures.screens[0];
+    assert_eq!(
+        &client
+            .get_screen(screen.id)
+            .await
+            .expect("the screen should load"),
+        screen
+    );
+    let attribute = &fixtures.attributes[0];
+    assert_eq!(
+        &client
+            .get_attribute(&attribute.id)
+            .await
+            .expect("the attribute should load"),
+        attribute
+    );
+
+    let missing: SessionId = serde_json::from_str("4294967295").expect("IDs are numbers");
+    assert_eq!(
+        status_of(client.get_session(missing).await),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn wrong_token_is_rejected() {
+    let (_, server) = start().await;
+    let client: Client = ClientBuilder::new(&server.base_url(), "wrong-token".to_string())
+        .build()
+        .expect("the mock base URL should be valid");
+
+    assert_eq!(
+        status_of(client.list_sessions().await),
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[tokio::test]
+async fn failures_become_errors() {
+    let (_, server) = start().await;
+    let client = server.client();
+
+    for (failure, status) in [
+        (MockFailure::NotFound, StatusCode::NOT_FOUND),
+        (MockFailure::TooManyRequests, StatusCode::TOO_MANY_REQUESTS),
+        (MockFailure::ServerError, StatusCode::INTERNAL_SERVER_ERROR),
+    ] {
+        server.inject_failure_times("/v4/film", failure, 1);
+        assert_eq!(status_of(client.list_films().await), status, "{failure:?}");
+    }
+
+    server.inject_failure_times("/v4/film", MockFailure::MalformedJson, 1);
+    assert!(matches!(
+        client.list_films().await,
+        Err(LibVeeziError::Decode(_))
+    ));
+
+    // Every failure was injected once, so the endpoint has recovered
+    assert!(client.list_films().await.is_ok());
+}
+
+#[tokio::test]
+async fn requests_are_recorded() {
+    let (fixtures, server) = start().await;
+    let client = server.client();
+
+    client.list_sessions().await.expect("sessions should list");
+    client
+        .get_film(&fixtures.films[0].id)
+        .await
+        .expect("the film should load");
+
+    let requests = server.requests();
+    let paths: Vec<&str> = requests
+        .iter()
+        .map(|request| request.path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "/v1/session".to_string(),
+            format!("/v4/film/{}", fixtures.films[0].id)
+        ]
+    );
+    assert!(requests.iter().all(|request| {
+        request.method == "GET" && request.access_token.as_deref() == Some(DEFAULT_MOCK_TOKEN)
+    }));
+
+    server.clear_requests();
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn web_sessions_use_the_site_time() {
+    let (fixtures, server) = start().await;
+    let now = fixtures.site.now();
+    let film = &fixtures.films[0];
+    let screen = &fixtures.screens[0];
+    server.set_sessions(vec![
+        SessionBuilder::new(1, film, screen, now - TimeDelta::hours(3)).build(),
+        SessionBuilder::new(2, film, screen, now + TimeDelta::hours(3)).build(),
+    ]);
+
+    let sessions = server
+        .client()
+        .list_web_sessions()
+        .await
+        .expect("web sessions should list");
+    let ids: Vec<u32> = sessions
+        .iter()
+        .map(|session| session.id.into_u32())
+        .collect();
+    assert_eq!(ids, [2]);
+}
+
+#[tokio::test]
+async fn cassettes_are_written_on_drop() {
+    let (_, server) = start().await;
+    let path = std::env::temp_dir().join(format!("libveezi-cassette-{}.json", std::process::id()));
+    let client = server
+        .client_builder()
+        .with_cassette_recording(path.clone())
+        .build()
+        .expect("the client should build");
+
+    client.list_films().await.expect("films should list");
+    client.get_site().await.expect("the site should load");
+    assert!(!path.exists());
+
+    drop(client);
+    let cassette = Cassette::load(&path).expect("the cassette should be written");
+    std::fs::remove_file(&path).expect("the cassette should be removed");
+    let endpoints: Vec<&str> = cassette
+        .interactions
+        .iter()
+        .map(|interaction| interaction.request.endpoint.as_str())
+        .collect();
+    assert_eq!(endpoints, ["v4/film", "v1/site"]);
+}
@@ -0,0 +1,294 @@
+//! Integration tests for the [`ProxyServer`] in front of a mock Veezi server
+
+#![cfg(feature = "server")]
+
+use chrono::{Days, NaiveDate};
+use libveezi::{
+    mock::{MockFailure, MockServer},
+    server::{API_KEY_HEADER, MAX_FILM_DAYS, ProxyServer},
+    testing::{FixtureSet, ScheduleGenerator},
+};
+use reqwest::{
+    Method, StatusCode,
+    header::{self, HeaderValue},
+};
+use serde_json::Value;
+use tokio::net::TcpListener;
+
+/// Generate three days of fixtures starting today at the site and start a mock
+/// server serving them
+async fn start_mock() -> (FixtureSet, MockServer) {
+    let today = ScheduleGenerator::new(NaiveDate::MIN)
+        .generate()
+        .site
+        .now()
+        .date();
+    let fixtures = ScheduleGenerator::new(today).with_days(3).generate();
+    let server = fixtures
+        .mock()
+        .start()
+        .await
+        .expect("the mock should start");
+    (fixtures, server)
+}
+
+/// Serve a [`ProxyServer`] on a local port, returning its base URL
+async fn serve(proxy: ProxyServer) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("the proxy should bind");
+    let addr = listener
+        .local_addr()
+        .expect("the proxy should have an address");
+    tokio::spawn(proxy.serve(listener));
+    format!("http://{addr}")
+}
+
+/// Start a mock server and a proxy with the facade in front of it
+async fn start_facade() -> (FixtureSet, MockServer, String) {
+    let (fixtures, server) = start_mock().await;
+    let proxy = ProxyServer::new(server.client().into()).with_facade();
+    let base_url = serve(proxy).await;
+    (fixtures, server, base_url)
+}
+
+/// Get a path from the proxy, returning the status and the JSON body, if any
+async fn get(base_url: &str, path: &str) -> (StatusCode, Option<Value>) {
+    let response = reqwest::get(format!("{base_url}{path}"))
+        .await
+        .expect("the proxy should respond");
+    let status = response.status();
+    (status, response.json().await.ok())
+}
+
+#[tokio::test]
+async fn showtimes_group_sessions_by_day_and_film() {
+    let (fixtures, _server, base_url) = start_facade().await;
+    let tomorrow = fixtures.films[0]
+        .opening_date
+        .date()
+        .checked_add_days(Days::new(1))
+        .expect("tomorrow should exist");
+
+    let (status, body) = get(&base_url, &format!("/showtimes?date={tomorrow}&days=1")).await;
+    assert_eq!(status, StatusCode::OK);
+    let days = body.expect("showtimes should be JSON");
+    let days = days.as_array().expect("showtimes should be a list");
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0]["date"], tomorrow.to_string());
+
+    let films = days[0]["films"].as_array().expect("films should be a list");
+    let sessions: usize = films
+        .iter()
+        .map(|film| film["sessions"].as_array().map_or(0, Vec::len))
+        .sum();
+    let expected = fixtures
+        .sessions
+        .iter()
+        .filter(|session| {
+            session.pre_show_start_time.date() == tomorrow && session.seats_available > 0
+        })
+        .count();
+    assert_eq!(sessions, expected);
+    let session = &films[0]["sessions"][0];
+    assert!(session["screen_name"].is_string());
+    assert_eq!(session["open_for_sales"], true);
+}
+
+#[tokio::test]
+async fn films_are_classified_by_window() {
+    let (fixtures, _server, base_url) = start_facade().await;
+
+    let (status, body) = get(&base_url, "/films/now-playing").await;
+    assert_eq!(status, StatusCode::OK);
+    let films = body.expect("films should be JSON");
+    let films = films.as_array().expect("films should be a list");
+    assert_eq!(films.len(), fixtures.films.len());
+    assert!(films.iter().all(|film| film["status"] == "NowPlaying"));
+    assert!(
+        films
+            .iter()
+            .all(|film| !film["next_sessions"].as_array().is_none_or(Vec::is_empty))
+    );
+
+    let (status, body) = get(&base_url, "/films/coming-soon?days=0").await;
+    assert_eq!(status, StatusCode::OK);
+    let films = body.expect("films should be JSON");
+    assert_eq!(films.as_array().map(Vec::len), Some(fixtures.films.len()));
+}
+
+#[tokio::test]
+async fn oversized_film_windows_are_rejected() {
+    let (_, _server, base_url) = start_facade().await;
+
+    let (status, _) = get(
+        &base_url,
+        &format!("/films/now-playing?days={MAX_FILM_DAYS}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    for days in [MAX_FILM_DAYS + 1, u64::MAX] {
+        let (status, _) = get(&base_url, &format!("/films/now-playing?days={days}")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = get(&base_url, &format!("/films/coming-soon?days={days}")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn screen_timelines_are_served() {
+    let (fixtures, _server, base_url) = start_facade().await;
+    let today = fixtures.films[0].opening_date.date();
+
+    let (status, body) = get(&base_url, &format!("/screens/1/timeline?date={today}")).await;
+    assert_eq!(status, StatusCode::OK);
+    let timeline = body.expect("the timeline should be JSON");
+    assert_eq!(timeline["business_day"], today.to_string());
+    let expected = fixtures
+        .sessions
+        .iter()
+        .filter(|session| {
+            session.screen_id.into_u32() == 1 && session.pre_show_start_time.date() == today
+        })
+        .count();
+    assert_eq!(timeline["entries"].as_array().map(Vec::len), Some(expected));
+
+    let (status, _) = get(&base_url, "/screens/99/timeline").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn facade_is_off_by_default() {
+    let (_, server) = start_mock().await;
+    let base_url = serve(ProxyServer::new(server.client().into())).await;
+
+    let (status, _) = get(&base_url, "/showtimes").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn api_keys_are_required_when_configured() {
+    let (_, server) = start_mock().await;
+    let proxy = ProxyServer::new(server.client().into()).with_api_keys(["downstream".to_string()]);
+    let base_url = serve(proxy).await;
+    let http = reqwest::Client::new();
+    let url = format!("{base_url}/v1/site");
+
+    let missing = http
+        .get(&url)
+        .send()
+        .await
+        .expect("the proxy should respond");
+    assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+    let wrong = http
+        .get(&url)
+        .header(API_KEY_HEADER, "not-the-key")
+        .send()
+        .await
+        .expect("the proxy should respond");
+    assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+    let right = http
+        .get(&url)
+        .header(API_KEY_HEADER, "downstream")
+        .send()
+        .await
+        .expect("the proxy should respond");
+    assert_eq!(right.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn cors_headers_are_sent_for_allowed_origins() {
+    let (_, server) = start_mock().await;
+    let proxy = ProxyServer::new(server.client().into())
+        .with_cors_origins(["https://example.com".to_string()]);
+    let base_url = serve(proxy).await;
+    let http = reqwest::Client::new();
+    let url = format!("{base_url}/v1/site");
+
+    let allowed = http
+        .get(&url)
+        .header(header::ORIGIN, "https://example.com")
+        .send()
+        .await
+        .expect("the proxy should respond");
+    assert_eq!(
+        allowed.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+        Some(&HeaderValue::from_static("https://example.com"))
+    );
+
+    let preflight = http
+        .request(Method::OPTIONS, &url)
+        .header(header::ORIGIN, "https://example.com")
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "veeziaccesstoken")
+        .send()
+        .await
+        .expect("the proxy should respond");
+    assert!(preflight.status().is_success());
+    assert!(
+        preflight
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_METHODS)
+    );
+
+    let other = http
+        .get(&url)
+        .header(header::ORIGIN, "https://elsewhere.example")
+        .send()
+        .await
+        .expect("the proxy should respond");
+    assert!(
+        !other
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+    );
+}
+
+#[tokio::test]
+async fn stats_report_cache_hits() {
+    let (_, server) = start_mock().await;
+    let client = server
+        .client_builder()
+        .with_default_caching()
+        .build()
+        .expect("the client should build");
+    let base_url = serve(ProxyServer::new(client.into())).await;
+
+    for _ in 0..2 {
+        let (status, _) = get(&base_url, "/v4/film").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (status, body) = get(&base_url, "/stats").await;
+    assert_eq!(status, StatusCode::OK);
+    let stats = body.expect("the stats should be JSON");
+    assert_eq!(stats["film_list"]["enabled"], true);
+    assert_eq!(stats["film_list"]["misses"], 1);
+    assert_eq!(stats["film_list"]["hits"], 1);
+    let upstream = server
+        .requests()
+        .iter()
+        .filter(|request| request.path == "/v4/film")
+        .count();
+    assert_eq!(upstream, 1);
+}
+
+#[tokio::test]
+async fn upstream_statuses_are_passed_through() {
+    let (_, server) = start_mock().await;
+    let base_url = serve(ProxyServer::new(server.client().into())).await;
+
+    let (status, _) = get(&base_url, "/v1/screen/99").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    for (failure, expected) in [
+        (MockFailure::TooManyRequests, StatusCode::TOO_MANY_REQUESTS),
+        (MockFailure::ServerError, StatusCode::INTERNAL_SERVER_ERROR),
+        (MockFailure::MalformedJson, StatusCode::BAD_GATEWAY),
+    ] {
+        server.inject_failure("/v4/film", failure);
+        let (status, _) = get(&base_url, "/v4/film").await;
+        assert_eq!(status, expected, "{failure:?}");
+        server.clear_failures();
+    }
+    let (status, _) = get(&base_url, "/v4/film").await;
+    assert_eq!(status, StatusCode::OK);
+}